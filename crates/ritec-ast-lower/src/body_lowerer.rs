@@ -1,6 +1,6 @@
 use ritec_ast as ast;
 use ritec_core::{Ident, UnaryOp};
-use ritec_error::Diagnostic;
+use ritec_error::{Diagnostic, ErrorCode};
 use ritec_hir as hir;
 
 use crate::Resolver;
@@ -22,7 +22,7 @@ impl<'a> BodyLowerer<'a> {
         }
     }
 
-    pub fn lower_type(&self, ty: &ast::Type) -> Result<hir::Type, Diagnostic> {
+    pub fn lower_type(&mut self, ty: &ast::Type) -> Result<hir::Type, Diagnostic> {
         self.resolver.resolve_type(ty)
     }
 
@@ -167,7 +167,18 @@ impl<'a> BodyLowerer<'a> {
             return Ok(hir::Expr::Function(function_expr));
         }
 
+        if let Some(constant) = self.resolver.resolve_const(&expr.path)? {
+            let const_expr = hir::ConstExpr {
+                constant,
+                id: self.body.next_id(),
+                span: expr.span,
+            };
+
+            return Ok(hir::Expr::Const(const_expr));
+        }
+
         let err = Diagnostic::error(format!("'{}' not defined", expr.path))
+            .with_code(ErrorCode::E0004)
             .with_msg_span("variable not found", expr.span);
 
         Err(err)
@@ -188,18 +199,20 @@ impl<'a> BodyLowerer<'a> {
 
         let hir::Type::Class(class_type) = ty else {
             let err = Diagnostic::error(format!("'{}' is not a class", expr.class.path))
+                .with_code(ErrorCode::E0011)
                 .with_msg_span("expected class", expr.class.span);
 
             return Err(err);
         };
 
-        let class = &self.resolver.program[class_type.class];
-
         let mut fields = Vec::new();
         for field in &expr.fields {
+            let class = &self.resolver.program[class_type.class];
             let Some(field_id) = class.find_field(&field.ident) else {
-                let err = Diagnostic::error(format!("'{}' has no field '{}'", class.ident, field.ident))
-                    .with_msg_span("field not found", field.ident.span());
+                let err =
+                    Diagnostic::error(format!("'{}' has no field '{}'", class.ident, field.ident))
+                        .with_code(ErrorCode::E0008)
+                        .with_msg_span("field not found", field.ident.span());
 
                 return Err(err);
             };
@@ -5,4 +5,5 @@ pub enum Error {
     ClassCompletion,
     FunctionRegistration,
     FunctionCompletion,
+    ConstCompletion,
 }
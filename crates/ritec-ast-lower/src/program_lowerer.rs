@@ -1,11 +1,11 @@
 use std::collections::HashMap;
 
 use ritec_ast as ast;
-use ritec_core::{Arena, Generic, Ident};
-use ritec_error::{Diagnostic, Emitter};
+use ritec_core::{Arena, Generic, Ident, Inline, Span};
+use ritec_error::{Diagnostic, Emitter, ErrorCode};
 use ritec_hir as hir;
 
-use crate::{BodyLowerer, Error, Resolver};
+use crate::{ArrayLength, BodyLowerer, Error, Resolver};
 
 pub struct ProgramLowerer<'a> {
     pub program: &'a mut hir::Program,
@@ -13,6 +13,8 @@ pub struct ProgramLowerer<'a> {
     pub modules: HashMap<ast::ModuleId, hir::ModuleId>,
     pub classes: HashMap<ast::ClassId, hir::ClassId>,
     pub functions: HashMap<ast::FunctionId, hir::FunctionId>,
+    pub consts: HashMap<ast::ConstId, hir::ConstId>,
+    pub lengths: Vec<ArrayLength>,
 }
 
 impl<'a> ProgramLowerer<'a> {
@@ -23,6 +25,8 @@ impl<'a> ProgramLowerer<'a> {
             modules: HashMap::new(),
             classes: HashMap::new(),
             functions: HashMap::new(),
+            consts: HashMap::new(),
+            lengths: Vec::new(),
         }
     }
 
@@ -60,12 +64,24 @@ impl<'a> ProgramLowerer<'a> {
         }
     }
 
+    pub fn cast_const(&mut self, ast: ast::ConstId) -> hir::ConstId {
+        if let Some(id) = self.consts.get(&ast) {
+            *id
+        } else {
+            let id = self.program.consts.reserve();
+            self.consts.insert(ast, id);
+            id
+        }
+    }
+
     pub fn lower(&mut self, program: &ast::Program) -> Result<(), Error> {
         self.register_modules(program);
         self.register_classes(program)?;
         self.complete_classes(program)?;
         self.register_functions(program)?;
         self.complete_functions(program)?;
+        self.complete_consts(program)?;
+        self.complete_lengths()?;
 
         Ok(())
     }
@@ -98,6 +114,13 @@ impl<'a> ProgramLowerer<'a> {
                 let ident = program.functions[id].ident.clone();
                 self.program[mod_id].functions.insert(ident, hir_id);
             }
+
+            for &id in module.consts.iter() {
+                let hir_id = self.cast_const(id);
+
+                let ident = program.consts[id].ident.clone();
+                self.program[mod_id].consts.insert(ident, hir_id);
+            }
         }
     }
 
@@ -171,10 +194,11 @@ impl<'a> ProgramLowerer<'a> {
         let module = self.cast_module(item.module);
 
         for field in item.fields.iter() {
-            let resolver = Resolver {
-                program: &self.program,
+            let mut resolver = Resolver {
+                program: self.program,
                 generics: &class.generics,
                 module,
+                lengths: &mut self.lengths,
             };
 
             let ty = resolver.resolve_type(&field.ty)?;
@@ -260,6 +284,8 @@ impl<'a> ProgramLowerer<'a> {
         id: hir::FunctionId,
         item: &ast::Function,
     ) -> Result<(), Diagnostic> {
+        let attributes = lower_attributes(&item.attributes)?;
+
         let mut generic_params = Vec::new();
         for param in item.generics.params.iter() {
             generic_params.push(Generic::new(param.ident.clone()));
@@ -267,11 +293,13 @@ impl<'a> ProgramLowerer<'a> {
 
         // lower generics
         let generics = hir::Generics::new(generic_params, item.generics.span);
+        attributes.check_export(&generics)?;
         let module = self.cast_module(item.module);
-        let resolver = Resolver {
+        let mut resolver = Resolver {
             program: self.program,
             generics: &generics,
             module,
+            lengths: &mut self.lengths,
         };
 
         let mut body = hir::Body::new();
@@ -297,6 +325,7 @@ impl<'a> ProgramLowerer<'a> {
             // assert that the argument doesn't contain inferred types
             if ty.is_inferred() {
                 let err = Diagnostic::error("cannot infer type of function argument")
+                    .with_code(ErrorCode::E0006)
                     .with_msg_span("argument type is inferred", argument.span);
 
                 return Err(err);
@@ -315,6 +344,7 @@ impl<'a> ProgramLowerer<'a> {
         // assert that the return type doesn't contain inferred types
         if return_type.is_inferred() {
             let err = Diagnostic::error("cannot infer type of function return type")
+                .with_code(ErrorCode::E0006)
                 .with_msg_span("return type is inferred", item.span);
 
             return Err(err);
@@ -327,6 +357,9 @@ impl<'a> ProgramLowerer<'a> {
             arguments,
             body,
             return_type,
+            inline: attributes.inline,
+            export: attributes.export.is_some(),
+            is_const: item.is_const,
             span: item.span,
         };
 
@@ -342,6 +375,8 @@ impl<'a> ProgramLowerer<'a> {
         class: &ast::Class,
         method: &ast::Method,
     ) -> Result<(), Diagnostic> {
+        let attributes = lower_attributes(&method.attributes)?;
+
         let mut class_generics = Vec::new();
         for param in class.generics.params.iter() {
             class_generics.push(Generic::new(param.ident.clone()));
@@ -358,14 +393,16 @@ impl<'a> ProgramLowerer<'a> {
 
         let mut function_generics = class_generics.clone();
         (function_generics.params).extend(method_generics.params.clone());
+        attributes.check_export(&function_generics)?;
 
         let module = self.cast_module(class.module);
 
         // create resolver
-        let resolver = Resolver {
+        let mut resolver = Resolver {
             program: self.program,
             generics: &function_generics,
             module,
+            lengths: &mut self.lengths,
         };
 
         let self_type = hir::Type::Class(hir::ClassType {
@@ -421,6 +458,7 @@ impl<'a> ProgramLowerer<'a> {
             // assert that the argument doesn't contain inferred types
             if ty.is_inferred() {
                 let err = Diagnostic::error("cannot infer type of function argument")
+                    .with_code(ErrorCode::E0006)
                     .with_msg_span("argument type is inferred", argument.span);
 
                 return Err(err);
@@ -439,6 +477,7 @@ impl<'a> ProgramLowerer<'a> {
         // assert that the return type doesn't contain inferred types
         if return_type.is_inferred() {
             let err = Diagnostic::error("cannot infer type of function return type")
+                .with_code(ErrorCode::E0006)
                 .with_msg_span("return type is inferred", method.span);
 
             return Err(err);
@@ -451,6 +490,9 @@ impl<'a> ProgramLowerer<'a> {
             arguments,
             body,
             return_type,
+            inline: attributes.inline,
+            export: attributes.export.is_some(),
+            is_const: false,
             span: method.span,
         };
 
@@ -523,12 +565,14 @@ impl<'a> ProgramLowerer<'a> {
         };
 
         let module = self.cast_module(class.module);
-        let hir_class = &self.program[class_id];
+        let generics = self.program[class_id].generics.clone();
+        let hir_field = self.program[class_id][field_id].clone();
 
         let resolver = Resolver {
             program: self.program,
-            generics: &hir_class.generics,
+            generics: &generics,
             module,
+            lengths: &mut self.lengths,
         };
 
         let mut body = hir::Body::new();
@@ -543,15 +587,19 @@ impl<'a> ProgramLowerer<'a> {
                 format!("{}__{}__init", class.ident, field.ident),
                 field.ident.span(),
             ),
-            generics: hir_class.generics.clone(),
+            generics,
             arguments: Vec::new(),
-            return_type: hir_class[field_id].ty.clone(),
+            return_type: hir_field.ty,
             body,
+            inline: Inline::Auto,
+            export: false,
+            is_const: false,
             span: field.span,
         };
 
-        let init = hir_class[field_id].init.unwrap();
-        self.program.functions.insert(init, function);
+        self.program
+            .functions
+            .insert(hir_field.init.unwrap(), function);
 
         Ok(())
     }
@@ -565,9 +613,10 @@ impl<'a> ProgramLowerer<'a> {
         let mut function = self.program.functions[id].clone();
         let module = self.cast_module(module);
         let resolver = Resolver {
-            program: &self.program,
+            program: self.program,
             generics: &function.generics,
             module,
+            lengths: &mut self.lengths,
         };
 
         let mut body_lowerer = BodyLowerer::new(&mut function.body, resolver);
@@ -577,4 +626,234 @@ impl<'a> ProgramLowerer<'a> {
 
         Ok(())
     }
+
+    pub fn complete_consts(&mut self, program: &ast::Program) -> Result<(), Error> {
+        let mut has_failed = false;
+
+        for (id, item) in program.consts.iter() {
+            let id = self.cast_const(id);
+            if let Err(err) = self.complete_const(id, item) {
+                self.emitter.emit(err);
+                has_failed = true;
+            }
+        }
+
+        if has_failed {
+            Err(Error::ConstCompletion)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn complete_const(
+        &mut self,
+        id: hir::ConstId,
+        item: &ast::Const,
+    ) -> Result<(), Diagnostic> {
+        let module = self.cast_module(item.module);
+        let generics = hir::Generics::new(Vec::new(), item.span);
+
+        let mut resolver = Resolver {
+            program: self.program,
+            generics: &generics,
+            module,
+            lengths: &mut self.lengths,
+        };
+
+        let ty = resolver.resolve_type(&item.ty)?;
+
+        // only scalars have a value that can be stored in a constant
+        if !matches!(
+            ty,
+            hir::Type::Bool(_) | hir::Type::Int(_) | hir::Type::Float(_) | hir::Type::Pointer(_)
+        ) || ty.is_inferred()
+        {
+            let err = Diagnostic::error("invalid type for a constant")
+                .with_code(ErrorCode::E0019)
+                .with_msg_span("expected a bool, integer, float or pointer", item.ty.span());
+
+            return Err(err);
+        }
+
+        let (body, expr) = lower_const_body(resolver, &item.value)?;
+
+        let constant = hir::Const {
+            ident: Some(item.ident.clone()),
+            ty,
+            body,
+            expr,
+            value: None,
+            span: item.span,
+        };
+
+        self.program.consts.insert(id, constant);
+
+        Ok(())
+    }
+
+    /// Lowers the lengths of the array types found while lowering, lengths
+    /// may contain array types themselves.
+    pub fn complete_lengths(&mut self) -> Result<(), Error> {
+        let mut has_failed = false;
+
+        while let Some(length) = self.lengths.pop() {
+            if let Err(err) = self.complete_length(length) {
+                self.emitter.emit(err);
+                has_failed = true;
+            }
+        }
+
+        if has_failed {
+            Err(Error::ConstCompletion)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn complete_length(&mut self, length: ArrayLength) -> Result<(), Diagnostic> {
+        let span = length.expr.span();
+        let generics = hir::Generics::new(Vec::new(), span);
+
+        let resolver = Resolver {
+            program: self.program,
+            generics: &generics,
+            module: length.module,
+            lengths: &mut self.lengths,
+        };
+
+        let (body, expr) = lower_const_body(resolver, &length.expr)?;
+
+        let constant = hir::Const {
+            ident: None,
+            ty: hir::Type::Int(hir::IntType {
+                signed: false,
+                size: None,
+                span,
+            }),
+            body,
+            expr,
+            value: None,
+            span,
+        };
+
+        self.program.consts.insert(length.constant, constant);
+
+        Ok(())
+    }
+}
+
+/// Lowers the value of a constant, returns the body and the value. The entry
+/// block of the body returns the value, so it can be built like the body of a
+/// function.
+fn lower_const_body(
+    resolver: Resolver,
+    value: &ast::Expr,
+) -> Result<(hir::Body, hir::ExprId), Diagnostic> {
+    let mut body = hir::Body::new();
+    let entry = body.blocks.reserve();
+
+    let expr = BodyLowerer::new(&mut body, resolver).lower_expr(value)?;
+    let span = body[expr].span();
+
+    let ret = hir::ReturnExpr {
+        value: Some(expr),
+        id: body.next_id(),
+        span,
+    };
+    let ret = body.exprs.push(hir::Expr::Return(ret));
+
+    let stmt = hir::ExprStmt {
+        expr: ret,
+        id: body.next_id(),
+        span,
+    };
+    let mut block = hir::Block::new();
+    block.push(stmt.into());
+    body.blocks.insert(entry, block);
+
+    Ok((body, expr))
+}
+
+/// The attributes of a function.
+struct FunctionAttributes {
+    inline: Inline,
+    /// The span of the `#[export]` attribute, if there is one.
+    export: Option<Span>,
+}
+
+impl FunctionAttributes {
+    /// Checks that the function isn't both exported and generic, only
+    /// instances of generic functions can be compiled.
+    fn check_export(&self, generics: &hir::Generics) -> Result<(), Diagnostic> {
+        match self.export {
+            Some(span) if !generics.params.is_empty() => {
+                let err = Diagnostic::error("generic functions cannot be exported")
+                    .with_code(ErrorCode::E0015)
+                    .with_msg_span("remove this attribute", span)
+                    .with_secondary("generics declared here", generics.span);
+
+                Err(err)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Lowers the attributes of a function, which are `inline` and `export`.
+fn lower_attributes(attributes: &[ast::Attribute]) -> Result<FunctionAttributes, Diagnostic> {
+    let mut inline = None;
+    let mut export = None;
+
+    for attribute in attributes {
+        let arguments: Vec<_> = attribute.arguments.iter().map(Ident::value).collect();
+
+        let is_duplicate = match attribute.ident.value() {
+            "inline" => {
+                let value = match arguments[..] {
+                    [] | ["always"] => Inline::Always,
+                    ["never"] => Inline::Never,
+                    _ => {
+                        let err = Diagnostic::error("malformed `inline` attribute")
+                            .with_code(ErrorCode::E0015)
+                            .with_msg_span("expected `inline` or `inline(never)`", attribute.span);
+
+                        return Err(err);
+                    }
+                };
+
+                inline.replace(value).is_some()
+            }
+            "export" => {
+                if !arguments.is_empty() {
+                    let err = Diagnostic::error("malformed `export` attribute")
+                        .with_code(ErrorCode::E0015)
+                        .with_msg_span("expected `export`", attribute.span);
+
+                    return Err(err);
+                }
+
+                export.replace(attribute.span).is_some()
+            }
+            _ => {
+                let err = Diagnostic::error(format!("unknown attribute `{}`", attribute.ident))
+                    .with_code(ErrorCode::E0015)
+                    .with_msg_span("not a known attribute", attribute.span);
+
+                return Err(err);
+            }
+        };
+
+        if is_duplicate {
+            let err = Diagnostic::error(format!("multiple `{}` attributes", attribute.ident))
+                .with_code(ErrorCode::E0015)
+                .with_msg_span("remove this attribute", attribute.span);
+
+            return Err(err);
+        }
+    }
+
+    Ok(FunctionAttributes {
+        inline: inline.unwrap_or_default(),
+        export,
+    })
 }
@@ -1,12 +1,22 @@
 use ritec_ast as ast;
 use ritec_core::{Ident, Span};
-use ritec_error::Diagnostic;
+use ritec_error::{Diagnostic, ErrorCode};
 use ritec_hir::{self as hir, Generics};
 
+/// The length of an array type, which is lowered to a [`hir::Const`] once
+/// every item it could refer to is registered.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArrayLength {
+    pub constant: hir::ConstId,
+    pub expr: ast::Expr,
+    pub module: hir::ModuleId,
+}
+
 pub struct Resolver<'a> {
-    pub program: &'a hir::Program,
+    pub program: &'a mut hir::Program,
     pub generics: &'a Generics,
     pub module: hir::ModuleId,
+    pub lengths: &'a mut Vec<ArrayLength>,
 }
 
 impl<'a> Resolver<'a> {
@@ -28,6 +38,7 @@ impl<'a> Resolver<'a> {
         }
 
         let err = Diagnostic::error("module not found")
+            .with_code(ErrorCode::E0004)
             .with_msg_span(format!("module '{}' not found", ident), ident.span());
 
         Err(err)
@@ -47,6 +58,7 @@ impl<'a> Resolver<'a> {
         }
 
         let err = Diagnostic::error("class not found")
+            .with_code(ErrorCode::E0004)
             .with_msg_span(format!("class '{}' not found", ident), ident.span());
 
         Err(err)
@@ -70,11 +82,23 @@ impl<'a> Resolver<'a> {
         }
 
         let err = Diagnostic::error("function not found")
+            .with_code(ErrorCode::E0004)
             .with_msg_span(format!("function '{}' not found", ident), ident.span());
 
         Err(err)
     }
 
+    fn get_const(&self, parent: hir::ModuleId, ident: &Ident) -> Option<hir::ConstId> {
+        let module = &self.program[parent];
+
+        if let Some(&constant) = module.consts.get(ident) {
+            return Some(constant);
+        }
+
+        let auto_include = &self.program[self.program.auto_include];
+        auto_include.consts.get(ident).copied()
+    }
+
     fn assert_generic_length(
         &self,
         actual: usize,
@@ -82,10 +106,12 @@ impl<'a> Resolver<'a> {
         span: Span,
     ) -> Result<(), Diagnostic> {
         if actual != expected {
-            let err = Diagnostic::error("invalid number of generic arguments").with_msg_span(
-                format!("expected {} generic arguments, found {}", expected, actual),
-                span,
-            );
+            let err = Diagnostic::error("invalid number of generic arguments")
+                .with_code(ErrorCode::E0005)
+                .with_msg_span(
+                    format!("expected {} generic arguments, found {}", expected, actual),
+                    span,
+                );
 
             Err(err)
         } else {
@@ -105,6 +131,7 @@ impl<'a> Resolver<'a> {
                 }
                 ast::PathSegment::SuperSegment(span) => {
                     let err = Diagnostic::error("invalid path")
+                        .with_code(ErrorCode::E0004)
                         .with_msg_span("cannot use `super` in the root module", *span);
 
                     return Err(err);
@@ -117,7 +144,7 @@ impl<'a> Resolver<'a> {
     }
 
     fn resolve_method(
-        &self,
+        &mut self,
         path: &ast::Path,
     ) -> Result<Option<hir::FunctionInstance>, Diagnostic> {
         let len = path.segments.len();
@@ -138,12 +165,14 @@ impl<'a> Resolver<'a> {
         };
 
         let class = &self.program[class];
+        let class_generics = class.generics.params.len();
 
         if let Some(method) = class.find_method(&method_segment.ident) {
+            let function = class[method].function;
             let mut generics = Vec::new();
 
             if class_segment.generics.len() == 0 {
-                for _ in 0..class.generics.params.len() {
+                for _ in 0..class_generics {
                     generics.push(hir::Type::inferred(class_segment.ident.span()));
                 }
             } else {
@@ -152,11 +181,10 @@ impl<'a> Resolver<'a> {
                 }
             }
 
-            let function = &self.program[class[method].function];
-            let expected = function.generics.params.len();
+            let expected = self.program[function].generics.params.len();
 
             if method_segment.generics.len() == 0 {
-                for _ in 0..expected - class.generics.params.len() {
+                for _ in 0..expected - class_generics {
                     generics.push(hir::Type::inferred(method_segment.ident.span()));
                 }
             } else {
@@ -168,7 +196,7 @@ impl<'a> Resolver<'a> {
             self.assert_generic_length(generics.len(), expected, path.span)?;
 
             let instance = hir::FunctionInstance {
-                function: class[method].function,
+                function,
                 generics,
                 span: path.span,
             };
@@ -180,7 +208,7 @@ impl<'a> Resolver<'a> {
     }
 
     fn resolve_function(
-        &self,
+        &mut self,
         path: &ast::Path,
     ) -> Result<Option<hir::FunctionInstance>, Diagnostic> {
         let len = path.segments.len();
@@ -223,7 +251,7 @@ impl<'a> Resolver<'a> {
     }
 
     pub fn resolve_constant(
-        &self,
+        &mut self,
         path: &ast::Path,
     ) -> Result<Option<hir::FunctionInstance>, Diagnostic> {
         if let Some(function) = self.resolve_method(path)? {
@@ -237,7 +265,28 @@ impl<'a> Resolver<'a> {
         Ok(None)
     }
 
-    pub fn resolve_type(&self, ty: &ast::Type) -> Result<hir::Type, Diagnostic> {
+    /// Resolves `path` to a `const` item.
+    pub fn resolve_const(&self, path: &ast::Path) -> Result<Option<hir::ConstId>, Diagnostic> {
+        let len = path.segments.len();
+        if len < 1 {
+            return Ok(None);
+        }
+
+        let ast::PathSegment::Item(ref segment) = path.segments[len - 1] else {
+            return Ok(None);
+        };
+
+        let module = self.resolve_module(&path.segments[..len - 1])?;
+        let Some(constant) = self.get_const(module, &segment.ident) else {
+            return Ok(None);
+        };
+
+        self.assert_generic_length(segment.generics.len(), 0, path.span)?;
+
+        Ok(Some(constant))
+    }
+
+    pub fn resolve_type(&mut self, ty: &ast::Type) -> Result<hir::Type, Diagnostic> {
         match ty {
             ast::Type::Inferred(ty) => Ok(self.resolve_inferred_type(ty)),
             ast::Type::Void(ty) => Ok(self.resolve_void_type(ty)),
@@ -290,7 +339,7 @@ impl<'a> Resolver<'a> {
         hir::Type::Float(float_type)
     }
 
-    pub fn resolve_pointer_type(&self, ty: &ast::PointerType) -> Result<hir::Type, Diagnostic> {
+    pub fn resolve_pointer_type(&mut self, ty: &ast::PointerType) -> Result<hir::Type, Diagnostic> {
         let pointer_type = hir::PointerType {
             pointee: Box::new(self.resolve_type(&ty.pointee)?),
             span: ty.span,
@@ -299,17 +348,29 @@ impl<'a> Resolver<'a> {
         Ok(hir::Type::Pointer(pointer_type))
     }
 
-    pub fn resolver_array_type(&self, ty: &ast::ArrayType) -> Result<hir::Type, Diagnostic> {
+    pub fn resolver_array_type(&mut self, ty: &ast::ArrayType) -> Result<hir::Type, Diagnostic> {
+        let element = self.resolve_type(&ty.element)?;
+
+        // the length may call functions that aren't registered yet
+        let length = ArrayLength {
+            constant: self.program.consts.reserve(),
+            expr: ty.length.as_ref().clone(),
+            module: self.module,
+        };
+
         let array_type = hir::ArrayType {
-            element: Box::new(self.resolve_type(&ty.element)?),
-            size: ty.size,
+            element: Box::new(element),
+            length: length.constant,
+            size: 0,
             span: ty.span,
         };
 
+        self.lengths.push(length);
+
         Ok(hir::Type::Array(array_type))
     }
 
-    pub fn resolve_slice_type(&self, ty: &ast::SliceType) -> Result<hir::Type, Diagnostic> {
+    pub fn resolve_slice_type(&mut self, ty: &ast::SliceType) -> Result<hir::Type, Diagnostic> {
         let slice_type = hir::SliceType {
             element: Box::new(self.resolve_type(&ty.element)?),
             span: ty.span,
@@ -318,7 +379,10 @@ impl<'a> Resolver<'a> {
         Ok(hir::Type::Slice(slice_type))
     }
 
-    pub fn resolve_function_type(&self, ty: &ast::FunctionType) -> Result<hir::Type, Diagnostic> {
+    pub fn resolve_function_type(
+        &mut self,
+        ty: &ast::FunctionType,
+    ) -> Result<hir::Type, Diagnostic> {
         let mut arguments = Vec::new();
         for argument in &ty.arguments {
             arguments.push(self.resolve_type(argument)?);
@@ -333,7 +397,7 @@ impl<'a> Resolver<'a> {
         Ok(hir::Type::Function(function_type))
     }
 
-    pub fn resolve_tuple_type(&self, ty: &ast::TupleType) -> Result<hir::Type, Diagnostic> {
+    pub fn resolve_tuple_type(&mut self, ty: &ast::TupleType) -> Result<hir::Type, Diagnostic> {
         let mut fields = Vec::new();
         for field in &ty.fields {
             fields.push(self.resolve_type(field)?);
@@ -347,7 +411,7 @@ impl<'a> Resolver<'a> {
         Ok(hir::Type::Tuple(tuple_type))
     }
 
-    pub fn resolve_path_type(&self, ty: &ast::PathType) -> Result<hir::Type, Diagnostic> {
+    pub fn resolve_path_type(&mut self, ty: &ast::PathType) -> Result<hir::Type, Diagnostic> {
         // resolve generics
         if let Some(ident) = ty.path.get_ident() {
             for generic in self.generics.params.iter() {
@@ -362,12 +426,16 @@ impl<'a> Resolver<'a> {
         let module = self.resolve_module(&ty.path.segments[..len - 1])?;
 
         let ast::PathSegment::Item(segment) = &ty.path.segments[len - 1] else {
-            let err = Diagnostic::error("expected item");    
+            let err = Diagnostic::error("expected item")
+                .with_code(ErrorCode::E0004)
+                .with_span(ty.span);
             return Err(err);
         };
 
         let Some(&class) = self.program[module].classes.get(&segment.ident) else {
-            let err = Diagnostic::error(format!("'{}' not defined", ty.path)).with_span(ty.span);
+            let err = Diagnostic::error(format!("'{}' not defined", ty.path))
+                .with_code(ErrorCode::E0004)
+                .with_span(ty.span);
             return Err(err);
         };
 
@@ -7,6 +7,7 @@ pub enum Item {
     Module(ModuleItem),
     Class(Class),
     Function(Function),
+    Const(Const),
 }
 
 impl Item {
@@ -15,6 +16,7 @@ impl Item {
             Item::Function(item) => item.span,
             Item::Class(item) => item.span,
             Item::Module(item) => item.span,
+            Item::Const(item) => item.span,
         }
     }
 }
@@ -43,6 +45,14 @@ pub struct Field {
     pub span: Span,
 }
 
+/// An attribute like `#[inline]` or `#[inline(never)]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Attribute {
+    pub ident: Ident,
+    pub arguments: Vec<Ident>,
+    pub span: Span,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum SelfArgument {
     Owned,
@@ -51,6 +61,7 @@ pub enum SelfArgument {
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Method {
+    pub attributes: Vec<Attribute>,
     pub ident: Ident,
     pub generics: Generics,
     pub self_argument: Option<SelfArgument>,
@@ -84,6 +95,8 @@ pub type FunctionId = Id<Function>;
 #[derive(Clone, Debug, PartialEq)]
 pub struct Function {
     pub module: ModuleId,
+    pub attributes: Vec<Attribute>,
+    pub is_const: bool,
     pub ident: Ident,
     pub generics: Generics,
     pub arguments: Vec<FunctionArgument>,
@@ -92,6 +105,18 @@ pub struct Function {
     pub span: Span,
 }
 
+pub type ConstId = Id<Const>;
+
+/// A constant like `const SIZE: usize = 4 * 1024;`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Const {
+    pub module: ModuleId,
+    pub ident: Ident,
+    pub ty: Type,
+    pub value: Expr,
+    pub span: Span,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct ModuleItem {
     pub ident: Ident,
@@ -3,6 +3,7 @@ mod expr;
 mod generic;
 mod item;
 mod path;
+mod print;
 mod program;
 mod stmt;
 mod ty;
@@ -1,13 +1,10 @@
-use std::{
-    fmt::{self, Display},
-    hash::{Hash, Hasher},
-};
+use std::fmt::{self, Display};
 
 use ritec_core::{Ident, Span};
 
 use crate::Type;
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ItemSegment {
     pub ident: Ident,
     pub generics: Vec<Type>,
@@ -19,7 +16,7 @@ impl Display for ItemSegment {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum PathSegment {
     Item(ItemSegment),
     SuperSegment(Span),
@@ -90,15 +87,6 @@ impl PartialEq for Path {
     }
 }
 
-impl Eq for Path {}
-
-impl Hash for Path {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.is_absolute.hash(state);
-        self.segments.hash(state);
-    }
-}
-
 impl Display for Path {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.is_absolute() {
@@ -0,0 +1,404 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{
+    Attribute, Block, Class, Const, Expr, Function, FunctionArgument, Generics, Method, ModuleId,
+    Path, PathSegment, Program, SelfArgument, Stmt, Type,
+};
+
+fn indent(f: &mut Formatter<'_>, depth: usize) -> fmt::Result {
+    for _ in 0..depth {
+        write!(f, "\t")?;
+    }
+
+    Ok(())
+}
+
+fn comma_separated<T: Display>(f: &mut Formatter<'_>, items: &[T]) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+
+        write!(f, "{}", item)?;
+    }
+
+    Ok(())
+}
+
+fn generic_args(f: &mut Formatter<'_>, types: &[Type]) -> fmt::Result {
+    if types.is_empty() {
+        return Ok(());
+    }
+
+    write!(f, "<")?;
+    comma_separated(f, types)?;
+    write!(f, ">")
+}
+
+/// Prints `path` with the generic arguments of its segments, which the
+/// [`Display`] of [`Path`] leaves out.
+fn path(f: &mut Formatter<'_>, path: &Path) -> fmt::Result {
+    if path.is_absolute() {
+        write!(f, "::")?;
+    }
+
+    for (i, segment) in path.segments.iter().enumerate() {
+        if i > 0 {
+            write!(f, "::")?;
+        }
+
+        write!(f, "{}", segment)?;
+
+        if let PathSegment::Item(item) = segment {
+            generic_args(f, &item.generics)?;
+        }
+    }
+
+    Ok(())
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Inferred(_) => write!(f, "_"),
+            Type::Void(_) => write!(f, "void"),
+            Type::Bool(_) => write!(f, "bool"),
+            Type::Int(ty) => write!(f, "{}", ty),
+            Type::Float(ty) => write!(f, "{}", ty),
+            Type::Pointer(ty) => write!(f, "*{}", ty.pointee),
+            Type::Array(ty) => {
+                write!(f, "[{}; ", ty.element)?;
+                expr(f, &ty.length, 0)?;
+                write!(f, "]")
+            }
+            Type::Slice(ty) => write!(f, "[{}]", ty.element),
+            Type::Function(ty) => {
+                write!(f, "fn(")?;
+                comma_separated(f, &ty.arguments)?;
+                write!(f, ") -> {}", ty.return_type)
+            }
+            Type::Tuple(ty) => {
+                write!(f, "(")?;
+                comma_separated(f, &ty.fields)?;
+                write!(f, ")")
+            }
+            Type::Path(ty) => path(f, &ty.path),
+        }
+    }
+}
+
+impl Display for Attribute {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "#[{}", self.ident)?;
+
+        if !self.arguments.is_empty() {
+            write!(f, "(")?;
+            comma_separated(f, &self.arguments)?;
+            write!(f, ")")?;
+        }
+
+        write!(f, "]")
+    }
+}
+
+impl Display for Generics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.params.is_empty() {
+            return Ok(());
+        }
+
+        let params: Vec<_> = self.params.iter().map(|param| &param.ident).collect();
+        write!(f, "<")?;
+        comma_separated(f, &params)?;
+        write!(f, ">")
+    }
+}
+
+impl Display for FunctionArgument {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.ident, self.ty)
+    }
+}
+
+fn block(f: &mut Formatter<'_>, block: &Block, depth: usize) -> fmt::Result {
+    writeln!(f, "{{")?;
+
+    for stmt in &block.stmts {
+        indent(f, depth + 1)?;
+
+        match stmt {
+            Stmt::Let(stmt) => {
+                write!(f, "let {}", stmt.ident)?;
+
+                if let Some(ref ty) = stmt.ty {
+                    write!(f, ": {}", ty)?;
+                }
+
+                if let Some(ref init) = stmt.init {
+                    write!(f, " = ")?;
+                    expr(f, init, depth + 1)?;
+                }
+
+                write!(f, ";")?;
+            }
+            Stmt::Expr(stmt) => {
+                expr(f, &stmt.expr, depth + 1)?;
+
+                if stmt.expr.stmt_needs_semi() {
+                    write!(f, ";")?;
+                }
+            }
+        }
+
+        writeln!(f)?;
+    }
+
+    indent(f, depth)?;
+    write!(f, "}}")
+}
+
+fn exprs(f: &mut Formatter<'_>, exprs: &[Expr], depth: usize) -> fmt::Result {
+    for (i, e) in exprs.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+
+        expr(f, e, depth)?;
+    }
+
+    Ok(())
+}
+
+/// Prints `expr` as it was written, the parentheses of the source are kept
+/// as [`Expr::Paren`], so none are added.
+fn expr(f: &mut Formatter<'_>, e: &Expr, depth: usize) -> fmt::Result {
+    match e {
+        Expr::Paren(e) => {
+            write!(f, "(")?;
+            expr(f, &e.expr, depth)?;
+            write!(f, ")")
+        }
+        Expr::Path(e) => path(f, &e.path),
+        Expr::Literal(e) => write!(f, "{}", e.literal),
+        Expr::ClassInit(e) => {
+            path(f, &e.class.path)?;
+            write!(f, " {{")?;
+
+            for (i, field) in e.fields.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ",")?;
+                }
+
+                write!(f, " {}: ", field.ident)?;
+                expr(f, &field.expr, depth)?;
+            }
+
+            if !e.fields.is_empty() {
+                write!(f, " ")?;
+            }
+
+            write!(f, "}}")
+        }
+        Expr::Field(e) => {
+            expr(f, &e.class, depth)?;
+            write!(f, ".{}", e.field)?;
+            generic_args(f, &e.generics)
+        }
+        Expr::As(e) => {
+            expr(f, &e.expr, depth)?;
+            write!(f, " as {}", e.ty)
+        }
+        Expr::Call(e) => {
+            expr(f, &e.callee, depth)?;
+            write!(f, "(")?;
+            exprs(f, &e.arguments, depth)?;
+            write!(f, ")")
+        }
+        Expr::Unary(e) => {
+            write!(f, "{}", e.operator)?;
+            expr(f, &e.operand, depth)
+        }
+        Expr::Binary(e) => {
+            expr(f, &e.lhs, depth)?;
+            write!(f, " {} ", e.operator)?;
+            expr(f, &e.rhs, depth)
+        }
+        Expr::Assign(e) => {
+            expr(f, &e.lhs, depth)?;
+            write!(f, " = ")?;
+            expr(f, &e.rhs, depth)
+        }
+        Expr::Return(e) => {
+            write!(f, "return")?;
+
+            if let Some(ref value) = e.value {
+                write!(f, " ")?;
+                expr(f, value, depth)?;
+            }
+
+            Ok(())
+        }
+        Expr::Break(_) => write!(f, "break"),
+        Expr::Block(e) => block(f, &e.block, depth),
+        Expr::If(e) => {
+            write!(f, "if ")?;
+            expr(f, &e.condition, depth)?;
+            write!(f, " ")?;
+            expr(f, &e.then_block, depth)?;
+
+            if let Some(ref else_block) = e.else_block {
+                write!(f, " else ")?;
+                expr(f, else_block, depth)?;
+            }
+
+            Ok(())
+        }
+        Expr::Loop(e) => {
+            write!(f, "loop ")?;
+            block(f, &e.block, depth)
+        }
+        Expr::While(e) => {
+            write!(f, "while ")?;
+            expr(f, &e.condition, depth)?;
+            write!(f, " ")?;
+            block(f, &e.block, depth)
+        }
+    }
+}
+
+fn attributes(f: &mut Formatter<'_>, attributes: &[Attribute], depth: usize) -> fmt::Result {
+    for attribute in attributes {
+        writeln!(f, "{}", attribute)?;
+        indent(f, depth)?;
+    }
+
+    Ok(())
+}
+
+fn method(f: &mut Formatter<'_>, method: &Method, depth: usize) -> fmt::Result {
+    attributes(f, &method.attributes, depth)?;
+    write!(f, "fn {}{}(", method.ident, method.generics)?;
+
+    match method.self_argument {
+        Some(SelfArgument::Owned) => write!(f, "self")?,
+        Some(SelfArgument::Pointer) => write!(f, "&self")?,
+        None => {}
+    }
+
+    if method.self_argument.is_some() && !method.arguments.is_empty() {
+        write!(f, ", ")?;
+    }
+
+    comma_separated(f, &method.arguments)?;
+    write!(f, ")")?;
+
+    if let Some(ref return_type) = method.return_type {
+        write!(f, " -> {}", return_type)?;
+    }
+
+    write!(f, " ")?;
+    block(f, &method.body, depth)
+}
+
+fn class(f: &mut Formatter<'_>, class: &Class, depth: usize) -> fmt::Result {
+    writeln!(f, "class {}{} {{", class.ident, class.generics)?;
+
+    for field in &class.fields {
+        indent(f, depth + 1)?;
+        write!(f, "{}: {}", field.ident, field.ty)?;
+
+        if let Some(ref init) = field.init {
+            write!(f, " = ")?;
+            expr(f, init, depth + 1)?;
+        }
+
+        writeln!(f, ",")?;
+    }
+
+    for m in &class.methods {
+        indent(f, depth + 1)?;
+        method(f, m, depth + 1)?;
+        writeln!(f)?;
+    }
+
+    indent(f, depth)?;
+    write!(f, "}}")
+}
+
+fn function(f: &mut Formatter<'_>, function: &Function, depth: usize) -> fmt::Result {
+    attributes(f, &function.attributes, depth)?;
+
+    if function.is_const {
+        write!(f, "const ")?;
+    }
+
+    write!(f, "fn {}{}(", function.ident, function.generics)?;
+    comma_separated(f, &function.arguments)?;
+    write!(f, ")")?;
+
+    if let Some(ref return_type) = function.return_type {
+        write!(f, " -> {}", return_type)?;
+    }
+
+    write!(f, " ")?;
+    block(f, &function.body, depth)
+}
+
+fn constant(f: &mut Formatter<'_>, constant: &Const) -> fmt::Result {
+    write!(f, "const {}: {} = ", constant.ident, constant.ty)?;
+    expr(f, &constant.value, 0)?;
+    write!(f, ";")
+}
+
+impl Program {
+    /// Prints the items of `module`, with the modules declared in it written
+    /// inline as `mod name { .. }`.
+    fn print_module(&self, f: &mut Formatter<'_>, module: ModuleId, depth: usize) -> fmt::Result {
+        let module = &self.modules[module];
+        let mut first = true;
+
+        let mut item = |f: &mut Formatter<'_>| {
+            if !first {
+                writeln!(f)?;
+            }
+
+            first = false;
+            indent(f, depth)
+        };
+
+        for &id in &module.modules {
+            item(f)?;
+            writeln!(f, "mod {} {{", self.modules[id].ident)?;
+            self.print_module(f, id, depth + 1)?;
+            indent(f, depth)?;
+            writeln!(f, "}}")?;
+        }
+
+        for &id in &module.consts {
+            item(f)?;
+            constant(f, &self.consts[id])?;
+            writeln!(f)?;
+        }
+
+        for &id in &module.classes {
+            item(f)?;
+            class(f, &self.classes[id], depth)?;
+            writeln!(f)?;
+        }
+
+        for &id in &module.functions {
+            item(f)?;
+            function(f, &self.functions[id], depth)?;
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Prints the program as Rite source code, with every module written inline.
+impl Display for Program {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.print_module(f, self.root_module, 0)
+    }
+}
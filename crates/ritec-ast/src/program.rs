@@ -1,6 +1,6 @@
 use ritec_core::{Arena, Id, Ident};
 
-use crate::{Class, ClassId, Function, FunctionId};
+use crate::{Class, ClassId, Const, ConstId, Function, FunctionId};
 
 pub type ModuleId = Id<Module>;
 
@@ -10,6 +10,7 @@ pub struct Module {
     pub modules: Vec<ModuleId>,
     pub classes: Vec<ClassId>,
     pub functions: Vec<FunctionId>,
+    pub consts: Vec<ConstId>,
 }
 
 impl Module {
@@ -19,6 +20,7 @@ impl Module {
             modules: Vec::new(),
             classes: Vec::new(),
             functions: Vec::new(),
+            consts: Vec::new(),
         }
     }
 }
@@ -29,6 +31,7 @@ pub struct Program {
     pub modules: Arena<Module>,
     pub classes: Arena<Class>,
     pub functions: Arena<Function>,
+    pub consts: Arena<Const>,
 }
 
 impl Program {
@@ -41,6 +44,7 @@ impl Program {
             modules,
             classes: Arena::new(),
             functions: Arena::new(),
+            consts: Arena::new(),
         }
     }
 
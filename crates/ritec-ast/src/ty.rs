@@ -1,11 +1,8 @@
-use std::{
-    fmt::{self, Display},
-    hash::Hash,
-};
+use std::fmt::{self, Display};
 
 use ritec_core::{FloatSize, IntSize, Span};
 
-use crate::Path;
+use crate::{Expr, Path};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct InferredType {
@@ -59,45 +56,45 @@ impl Display for FloatType {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct PointerType {
     pub pointee: Box<Type>,
     pub span: Span,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ArrayType {
     pub element: Box<Type>,
-    pub size: usize,
+    pub length: Box<Expr>,
     pub span: Span,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct SliceType {
     pub element: Box<Type>,
     pub span: Span,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct FunctionType {
     pub arguments: Vec<Type>,
     pub return_type: Box<Type>,
     pub span: Span,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct TupleType {
     pub fields: Vec<Type>,
     pub span: Span,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct PathType {
     pub path: Path,
     pub span: Span,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Type {
     Inferred(InferredType),
     Void(VoidType),
@@ -0,0 +1,289 @@
+use std::collections::HashMap;
+
+use ritec_core::{FloatSize, IntSize, SourceMap};
+use ritec_error::Diagnostic;
+use ritec_mir as mir;
+
+use crate::FunctionBuilder;
+
+/// Includes and definitions every generated file starts with.
+const PRELUDE: &str = "\
+#include <math.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+/* empty structs aren't allowed in C */
+typedef struct { char _; } rt_void;
+
+static void rt_panic(const char *message) {
+	fputs(message, stderr);
+	abort();
+}
+";
+
+pub struct CodegenCx<'c> {
+    pub program: &'c mir::Program,
+    /// Used to print the location of failed runtime checks.
+    pub source_map: &'c SourceMap,
+    types: HashMap<mir::Type, String>,
+    /// The number of named types, used to keep their names unique.
+    named_types: usize,
+    /// Forward declarations of structs and typedefs of function pointers.
+    declarations: String,
+    /// Struct definitions, every struct is defined after the types of its
+    /// fields.
+    definitions: String,
+    pub prototypes: String,
+    pub functions: String,
+}
+
+impl<'c> CodegenCx<'c> {
+    pub fn new(program: &'c mir::Program, source_map: &'c SourceMap) -> Self {
+        Self {
+            program,
+            source_map,
+            types: HashMap::new(),
+            named_types: 0,
+            declarations: String::new(),
+            definitions: String::new(),
+            prototypes: String::new(),
+            functions: String::new(),
+        }
+    }
+
+    /// Adds the prototype and definition of `instance`.
+    pub fn build_function(&mut self, instance: &mir::Instance) -> Result<(), Diagnostic> {
+        let generics = instance.generics.clone();
+        let mut builder = FunctionBuilder::new(self, instance.function, generics);
+        builder.build()
+    }
+
+    /// Returns the symbol of `function` instantiated with `generics`, C `main`
+    /// is reserved for the entry point that calls `main`.
+    pub fn symbol(&self, function: mir::FunctionId, generics: &[mir::Type]) -> String {
+        match mir::mangle(self.program, function, generics) {
+            symbol if symbol == "main" => String::from("rt_main"),
+            symbol => symbol,
+        }
+    }
+
+    /// Returns the name of the C type of `ty`, which must not contain any
+    /// generics.
+    pub fn c_type(&mut self, ty: &mir::Type) -> Result<String, Diagnostic> {
+        if let Some(name) = self.types.get(ty) {
+            return Ok(name.clone());
+        }
+
+        let name = match ty {
+            mir::Type::Void => String::from("rt_void"),
+            mir::Type::Bool => String::from("bool"),
+            mir::Type::Int(ty) => String::from(int_type(ty)),
+            mir::Type::Float(float) => match float.size {
+                FloatSize::F16 => return Err(unsupported(ty)),
+                FloatSize::F32 => String::from("float"),
+                FloatSize::F64 => String::from("double"),
+            },
+            mir::Type::Pointer(pointer) => format!("{}*", self.c_type(pointer.pointee())?),
+            mir::Type::Function(function) => {
+                let return_type = self.c_type(&function.return_type)?;
+
+                let mut arguments = Vec::new();
+                for argument in &function.arguments {
+                    arguments.push(self.c_type(argument)?);
+                }
+
+                if arguments.is_empty() {
+                    arguments.push(String::from("void"));
+                }
+
+                let name = self.type_name("fn");
+                self.declarations.push_str(&format!(
+                    "typedef {} (*{})({}); /* {} */\n",
+                    return_type,
+                    name,
+                    arguments.join(", "),
+                    ty
+                ));
+
+                name
+            }
+            mir::Type::Array(_) | mir::Type::Tuple(_) | mir::Type::Class(_) => {
+                return self.build_struct(ty);
+            }
+            mir::Type::Slice(_) | mir::Type::Generic(_) => return Err(unsupported(ty)),
+        };
+
+        self.types.insert(ty.clone(), name.clone());
+        Ok(name)
+    }
+
+    fn type_name(&mut self, prefix: &str) -> String {
+        let name = format!("{}_{}", prefix, self.named_types);
+        self.named_types += 1;
+        name
+    }
+
+    fn build_struct(&mut self, ty: &mir::Type) -> Result<String, Diagnostic> {
+        let name = match ty {
+            mir::Type::Array(_) => self.type_name("array"),
+            mir::Type::Tuple(_) => self.type_name("tuple"),
+            _ => self.type_name("class"),
+        };
+
+        // named before the fields are built, so fields can point to the struct
+        self.types.insert(ty.clone(), name.clone());
+        self.declarations
+            .push_str(&format!("typedef struct {0} {0};\n", name));
+
+        let mut fields = Vec::new();
+        match ty {
+            mir::Type::Array(array) => {
+                let element = self.c_type(&array.element)?;
+
+                if array.size > 0 {
+                    fields.push(format!("{} e[{}];", element, array.size));
+                }
+            }
+            mir::Type::Tuple(tuple) => {
+                for (i, field) in tuple.fields.iter().enumerate() {
+                    fields.push(format!("{} f{};", self.c_type(field)?, i));
+                }
+            }
+            mir::Type::Class(class_type) => {
+                let class = &self.program[class_type.class];
+                let generics = mir::GenericMap::new(&class.generics, &class_type.generics);
+
+                for (i, field) in class.fields.iter().enumerate() {
+                    let mut field_type = field.ty.clone();
+                    field_type.instantiate(&generics);
+
+                    let field_type = self.c_type(&field_type)?;
+                    fields.push(format!("{} f{}; /* {} */", field_type, i, field.ident));
+                }
+            }
+            _ => unreachable!(),
+        }
+
+        if fields.is_empty() {
+            fields.push(String::from("char _;"));
+        }
+
+        self.definitions
+            .push_str(&format!("/* {} */\nstruct {} {{\n", ty, name));
+        for field in fields {
+            self.definitions.push_str(&format!("\t{}\n", field));
+        }
+        self.definitions.push_str("};\n\n");
+
+        Ok(name)
+    }
+
+    /// Adds a C `main` that calls `main`, which has been given the symbol
+    /// `symbol`.
+    pub fn build_entry(&mut self, main: mir::FunctionId, symbol: &str) -> Result<(), Diagnostic> {
+        let function = &self.program[main];
+
+        let arguments = match function.arguments.as_slice() {
+            [] => String::new(),
+            [argc, argv] => format!(
+                "({})argc, ({})argv",
+                self.c_type(&argc.ty)?,
+                self.c_type(&argv.ty)?
+            ),
+            _ => {
+                return Err(Diagnostic::error(
+                    "`main` must take either no arguments or `argc` and `argv`",
+                )
+                .with_msg_span("defined here", function.ident.span()));
+            }
+        };
+
+        let body = match function.return_type {
+            mir::Type::Int(_) => format!("\treturn (int){}({});\n", symbol, arguments),
+            _ => format!("\t{}({});\n\treturn 0;\n", symbol, arguments),
+        };
+
+        self.functions.push_str(&format!(
+            "int main(int argc, char **argv) {{\n{}}}\n\n",
+            body
+        ));
+
+        Ok(())
+    }
+
+    /// Returns the complete C source file.
+    pub fn finish(self) -> String {
+        let mut source = String::from(PRELUDE);
+
+        for section in [
+            &self.declarations,
+            &self.definitions,
+            &self.prototypes,
+            &self.functions,
+        ] {
+            if !section.is_empty() {
+                source.push('\n');
+                source.push_str(section.trim_end());
+                source.push('\n');
+            }
+        }
+
+        source
+    }
+}
+
+pub fn int_type(ty: &mir::IntType) -> &'static str {
+    match (ty.signed, ty.size) {
+        (true, Some(IntSize::I8)) => "int8_t",
+        (true, Some(IntSize::I16)) => "int16_t",
+        (true, Some(IntSize::I32)) => "int32_t",
+        (true, Some(IntSize::I64)) => "int64_t",
+        (true, Some(IntSize::I128)) => "__int128",
+        (true, None) => "intptr_t",
+        (false, Some(IntSize::I8)) => "uint8_t",
+        (false, Some(IntSize::I16)) => "uint16_t",
+        (false, Some(IntSize::I32)) => "uint32_t",
+        (false, Some(IntSize::I64)) => "uint64_t",
+        (false, Some(IntSize::I128)) => "unsigned __int128",
+        (false, None) => "uintptr_t",
+    }
+}
+
+/// Returns the minimum and maximum value of `ty` as C expressions.
+pub fn int_limits(ty: &mir::IntType) -> (&'static str, &'static str) {
+    match (ty.signed, ty.size) {
+        (true, Some(IntSize::I8)) => ("INT8_MIN", "INT8_MAX"),
+        (true, Some(IntSize::I16)) => ("INT16_MIN", "INT16_MAX"),
+        (true, Some(IntSize::I32)) => ("INT32_MIN", "INT32_MAX"),
+        (true, Some(IntSize::I64)) => ("INT64_MIN", "INT64_MAX"),
+        (true, Some(IntSize::I128)) => (
+            "(-(__int128)(~(unsigned __int128)0 >> 1) - 1)",
+            "((__int128)(~(unsigned __int128)0 >> 1))",
+        ),
+        (true, None) => ("INTPTR_MIN", "INTPTR_MAX"),
+        (false, Some(IntSize::I8)) => ("0", "UINT8_MAX"),
+        (false, Some(IntSize::I16)) => ("0", "UINT16_MAX"),
+        (false, Some(IntSize::I32)) => ("0", "UINT32_MAX"),
+        (false, Some(IntSize::I64)) => ("0", "UINT64_MAX"),
+        (false, Some(IntSize::I128)) => ("0", "(~(unsigned __int128)0)"),
+        (false, None) => ("0", "UINTPTR_MAX"),
+    }
+}
+
+/// Returns an unsigned type at least as wide as `ty`, and at least as wide as
+/// `int`, so arithmetic on it wraps instead of overflowing.
+pub fn wrapping_type(ty: &mir::IntType) -> &'static str {
+    match ty.size {
+        Some(IntSize::I128) => "unsigned __int128",
+        Some(_) => "uint64_t",
+        None => "uintptr_t",
+    }
+}
+
+fn unsupported(ty: &mir::Type) -> Diagnostic {
+    Diagnostic::error(format!("`{}` is not supported by the C backend", ty))
+}
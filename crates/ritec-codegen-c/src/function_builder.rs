@@ -0,0 +1,498 @@
+use ritec_error::Diagnostic;
+use ritec_mir as mir;
+
+use crate::{int_limits, wrapping_type, CodegenCx};
+
+pub struct FunctionBuilder<'a, 'c> {
+    pub cx: &'a mut CodegenCx<'c>,
+    pub function: mir::FunctionId,
+    pub generics: Vec<mir::Type>,
+    pub out: String,
+}
+
+impl<'a, 'c> FunctionBuilder<'a, 'c> {
+    pub fn new(
+        cx: &'a mut CodegenCx<'c>,
+        function: mir::FunctionId,
+        generics: Vec<mir::Type>,
+    ) -> Self {
+        Self {
+            cx,
+            function,
+            generics,
+            out: String::new(),
+        }
+    }
+
+    pub fn function(&self) -> &'c mir::Function {
+        &self.cx.program[self.function]
+    }
+
+    /// Returns `ty` with the generics of the function instantiated.
+    pub fn instantiate(&self, ty: &mir::Type) -> mir::Type {
+        let mut ty = ty.clone();
+        let function = self.function();
+        ty.instantiate(&mir::GenericMap::new(&function.generics, &self.generics));
+        ty
+    }
+
+    pub fn build_type(&mut self, ty: &mir::Type) -> Result<String, Diagnostic> {
+        let ty = self.instantiate(ty);
+        self.cx.c_type(&ty)
+    }
+
+    pub fn symbol(&self) -> String {
+        self.cx.symbol(self.function, &self.generics)
+    }
+
+    pub fn build(&mut self) -> Result<(), Diagnostic> {
+        let function = self.function();
+        let symbol = self.symbol();
+
+        let return_type = self.build_type(&function.return_type)?;
+
+        let mut arguments = Vec::new();
+        let mut argument_types = Vec::new();
+        for (i, argument) in function.arguments.iter().enumerate() {
+            let ty = self.build_type(&argument.ty)?;
+            arguments.push(format!("{} a{}", ty, i));
+            argument_types.push(ty);
+        }
+
+        if argument_types.is_empty() {
+            arguments.push(String::from("void"));
+            argument_types.push(String::from("void"));
+        }
+
+        let mangled = mir::mangle(self.cx.program, self.function, &self.generics);
+        self.out.push_str(&format!(
+            "/* {} */\n{} {}({}) {{\n",
+            mir::demangle(&mangled).unwrap_or(mangled),
+            return_type,
+            symbol,
+            arguments.join(", ")
+        ));
+
+        // declare locals
+        for (local_id, local) in function.body.locals.iter() {
+            let ty = self.build_type(&local.ty)?;
+            let name = local_name(local_id);
+
+            match local.ident {
+                Some(ref ident) => {
+                    let line = format!("\t{} {}; /* {} */\n", ty, name, ident);
+                    self.out.push_str(&line);
+                }
+                None => self.out.push_str(&format!("\t{} {};\n", ty, name)),
+            }
+        }
+
+        // store arguments in locals
+        for (i, argument) in function.arguments.iter().enumerate() {
+            let local = local_name(argument.local);
+            self.out.push_str(&format!("\t{} = a{};\n", local, i));
+        }
+
+        // jump to the first block
+        if let Some(first_block) = function.body.blocks.keys().next() {
+            self.out
+                .push_str(&format!("\tgoto {};\n", block_name(first_block)));
+        }
+
+        for (block_id, block) in function.body.blocks.iter() {
+            self.out.push_str(&format!("{}:\n", block_name(block_id)));
+
+            // a label must be followed by a statement
+            if block.is_empty() {
+                self.out.push_str("\t;\n");
+            }
+
+            self.build_block(block)?;
+        }
+
+        self.out.push_str("}\n\n");
+
+        self.cx.prototypes.push_str(&format!(
+            "{} {}({});\n",
+            return_type,
+            symbol,
+            argument_types.join(", ")
+        ));
+        self.cx.functions.push_str(&self.out);
+
+        if symbol == "rt_main" {
+            self.cx.build_entry(self.function, &symbol)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn build_block(&mut self, block: &mir::Block) -> Result<(), Diagnostic> {
+        for statement in &block.statements {
+            self.build_statement(statement)?;
+        }
+
+        if let Some(ref term) = block.terminator {
+            self.build_terminator(term)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn build_statement(&mut self, statement: &mir::Statement) -> Result<(), Diagnostic> {
+        let line = match &statement.kind {
+            mir::StatementKind::Assign(assign) => {
+                let place = self.build_place(&assign.place);
+                let value = self.build_value(&assign.value)?;
+                format!("{} = {};", place, value)
+            }
+            mir::StatementKind::Drop(value) => {
+                format!("(void){};", self.build_value(value)?)
+            }
+        };
+
+        self.out.push_str(&format!("\t{}\n", line));
+        Ok(())
+    }
+
+    pub fn build_terminator(&mut self, terminator: &mir::Terminator) -> Result<(), Diagnostic> {
+        match &terminator.kind {
+            mir::TerminatorKind::Goto(block) => {
+                self.out
+                    .push_str(&format!("\tgoto {};\n", block_name(*block)));
+            }
+            mir::TerminatorKind::Return(operand) => {
+                let value = self.build_operand(operand)?;
+                self.out.push_str(&format!("\treturn {};\n", value));
+            }
+            mir::TerminatorKind::Switch(operand, targets) => {
+                let value = self.build_operand(operand)?;
+                self.out.push_str(&format!("\tswitch ({}) {{\n", value));
+
+                for (case, target) in &targets.targets {
+                    let target = block_name(*target);
+                    self.out
+                        .push_str(&format!("\tcase {}u: goto {};\n", case, target));
+                }
+
+                let default = block_name(targets.default);
+                self.out
+                    .push_str(&format!("\tdefault: goto {};\n\t}}\n", default));
+            }
+            mir::TerminatorKind::Assert(assert) => {
+                let cond = self.build_operand(&assert.cond)?;
+                let not = if assert.expected { "!" } else { "" };
+
+                let span = terminator.source_info.span;
+                let message = assert.message.panic_message(span, self.cx.source_map);
+
+                self.out.push_str(&format!(
+                    "\tif ({}{}) rt_panic({});\n",
+                    not,
+                    cond,
+                    string_literal(&message)
+                ));
+                self.out
+                    .push_str(&format!("\tgoto {};\n", block_name(assert.target)));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn build_place(&self, place: &mir::Place) -> String {
+        let mut out = local_name(place.local);
+
+        for proj in &place.proj {
+            match proj {
+                mir::Projection::Deref => out = format!("(*{})", out),
+                mir::Projection::Field(field) => {
+                    out = format!("{}.f{}", out, field.as_raw_index());
+                }
+            }
+        }
+
+        out
+    }
+
+    pub fn operand_type(&self, operand: &mir::Operand) -> mir::Type {
+        let program = self.cx.program;
+        program.operand_type(self.function(), &self.generics, operand)
+    }
+
+    pub fn build_operand(&mut self, operand: &mir::Operand) -> Result<String, Diagnostic> {
+        match operand {
+            mir::Operand::Copy(place) | mir::Operand::Move(place) => Ok(self.build_place(place)),
+            mir::Operand::Constant(constant) => self.build_constant(constant),
+        }
+    }
+
+    pub fn build_constant(&mut self, constant: &mir::Constant) -> Result<String, Diagnostic> {
+        Ok(match constant {
+            mir::Constant::Void => String::from("(rt_void){0}"),
+            mir::Constant::Null(ty) => {
+                let ty = self.build_type(&mir::Type::pointer(ty.clone()))?;
+                format!("(({})0)", ty)
+            }
+            mir::Constant::Function(id, generics) => {
+                let generics: Vec<_> = generics.iter().map(|g| self.instantiate(g)).collect();
+                self.cx.symbol(*id, &generics)
+            }
+            mir::Constant::Integer(i, ty) => {
+                let c_type = self.cx.c_type(&mir::Type::Int(ty.clone()))?;
+
+                if !ty.signed {
+                    format!("(({}){}u)", c_type, *i as u64)
+                } else if *i == i64::MIN {
+                    format!("(({})(-{} - 1))", c_type, i64::MAX)
+                } else {
+                    format!("(({}){})", c_type, i)
+                }
+            }
+            mir::Constant::Float(f, ty) => {
+                let c_type = self.cx.c_type(&mir::Type::Float(ty.clone()))?;
+
+                if f.is_nan() {
+                    format!("(({})NAN)", c_type)
+                } else if f.is_infinite() && *f > 0.0 {
+                    format!("(({})INFINITY)", c_type)
+                } else if f.is_infinite() {
+                    format!("(({})-INFINITY)", c_type)
+                } else {
+                    format!("(({}){:?})", c_type, f)
+                }
+            }
+            mir::Constant::Bool(b) => format!("{}", b),
+        })
+    }
+
+    pub fn build_value(&mut self, value: &mir::Value) -> Result<String, Diagnostic> {
+        Ok(match value {
+            mir::Value::Use(operand) => self.build_operand(operand)?,
+            mir::Value::Address(place) => format!("(&{})", self.build_place(place)),
+            mir::Value::UnaryOp(op, operand) => {
+                let ty = self.operand_type(operand);
+                let value = self.build_operand(operand)?;
+
+                match (op, ty) {
+                    (mir::UnaryOp::IntNot, mir::Type::Int(ty)) => {
+                        format!("(({})~{})", self.cx.c_type(&ty.into())?, value)
+                    }
+                    (mir::UnaryOp::IntNot, _) => format!("(!{})", value),
+                    (mir::UnaryOp::IntNeg, mir::Type::Int(ty)) => format!(
+                        "(({})(0 - ({}){}))",
+                        self.cx.c_type(&ty.clone().into())?,
+                        wrapping_type(&ty),
+                        value
+                    ),
+                    (mir::UnaryOp::IntNeg, ty) => unreachable!("negation of {}", ty),
+                    (mir::UnaryOp::FloatNeg, _) => format!("(-{})", value),
+                }
+            }
+            mir::Value::BinaryOp(op, lhs, rhs) => self.build_binary_op(*op, lhs, rhs)?,
+            mir::Value::Call(callee, arguments) => {
+                let callee = self.build_operand(callee)?;
+
+                let mut args = Vec::new();
+                for argument in arguments {
+                    args.push(self.build_operand(argument)?);
+                }
+
+                format!("{}({})", callee, args.join(", "))
+            }
+            mir::Value::Intrinsic(intrinsic) => self.build_intrinsic(intrinsic)?,
+        })
+    }
+
+    pub fn build_binary_op(
+        &mut self,
+        op: mir::BinOp,
+        lhs: &mir::Operand,
+        rhs: &mir::Operand,
+    ) -> Result<String, Diagnostic> {
+        let ty = self.operand_type(lhs);
+        let lhs = self.build_operand(lhs)?;
+        let rhs = self.build_operand(rhs)?;
+
+        // signed overflow is undefined in C, but wraps in MIR
+        let wrapping = |this: &mut Self, op: &str| -> Result<String, Diagnostic> {
+            let mir::Type::Int(ref int) = ty else {
+                unreachable!("integer arithmetic on {}", ty)
+            };
+
+            let wrapping = wrapping_type(int);
+            Ok(format!(
+                "(({})(({}){} {} ({}){}))",
+                this.cx.c_type(&ty)?,
+                wrapping,
+                lhs,
+                op,
+                wrapping,
+                rhs
+            ))
+        };
+
+        let binary = |op: &str| format!("({} {} {})", lhs, op, rhs);
+
+        Ok(match op {
+            mir::BinOp::IntAdd => wrapping(self, "+")?,
+            mir::BinOp::IntSub => wrapping(self, "-")?,
+            mir::BinOp::IntMul => wrapping(self, "*")?,
+            mir::BinOp::IntDivSigned | mir::BinOp::IntDivUnsigned => {
+                format!("(({}){})", self.cx.c_type(&ty)?, binary("/"))
+            }
+            mir::BinOp::IntEq | mir::BinOp::FloatEq => binary("=="),
+            mir::BinOp::IntNe => binary("!="),
+            mir::BinOp::IntLtSigned | mir::BinOp::IntLtUnsigned | mir::BinOp::FloatLt => {
+                binary("<")
+            }
+            mir::BinOp::IntLeSigned | mir::BinOp::IntLeUnsigned | mir::BinOp::FloatLe => {
+                binary("<=")
+            }
+            mir::BinOp::IntGtSigned | mir::BinOp::IntGtUnsigned | mir::BinOp::FloatGt => {
+                binary(">")
+            }
+            mir::BinOp::IntGeSigned | mir::BinOp::IntGeUnsigned | mir::BinOp::FloatGe => {
+                binary(">=")
+            }
+            mir::BinOp::FloatAdd => binary("+"),
+            mir::BinOp::FloatSub => binary("-"),
+            mir::BinOp::FloatMul => binary("*"),
+            mir::BinOp::FloatDiv => binary("/"),
+            // ordered, false if either is NaN
+            mir::BinOp::FloatNe => format!("({0} < {1} || {0} > {1})", lhs, rhs),
+        })
+    }
+
+    pub fn build_intrinsic(&mut self, intrinsic: &mir::Intrinsic) -> Result<String, Diagnostic> {
+        Ok(match intrinsic {
+            mir::Intrinsic::Sizeof(ty) => {
+                format!("((uintptr_t)sizeof({}))", self.build_type(ty)?)
+            }
+            mir::Intrinsic::Alignof(ty) => format!(
+                "((uintptr_t)offsetof(struct {{ char c; {} t; }}, t))",
+                self.build_type(ty)?
+            ),
+            mir::Intrinsic::Bitcast(operand, ty) => {
+                let from = self.operand_type(operand);
+                let value = self.build_operand(operand)?;
+                let to = self.build_type(ty)?;
+
+                let is_pointer =
+                    |ty: &mir::Type| matches!(ty, mir::Type::Pointer(_) | mir::Type::Function(_));
+
+                if is_pointer(&from) && is_pointer(&self.instantiate(ty)) {
+                    format!("(({}){})", to, value)
+                } else {
+                    // reinterpret the bits through a union
+                    let from = self.cx.c_type(&from)?;
+                    format!(
+                        "(((union {{ {} from; {} to; }}){{ .from = {} }}).to)",
+                        from, to, value
+                    )
+                }
+            }
+            mir::Intrinsic::Malloc(count, ty) => {
+                let ty = self.build_type(ty)?;
+                let count = self.build_operand(count)?;
+                format!("(({}*)malloc((size_t){} * sizeof({})))", ty, count, ty)
+            }
+            mir::Intrinsic::Free(ptr) => {
+                format!("(free({}), (rt_void){{0}})", self.build_operand(ptr)?)
+            }
+            mir::Intrinsic::Memcpy(dst, src, size) => format!(
+                "(memcpy({}, {}, {}), (rt_void){{0}})",
+                self.build_operand(dst)?,
+                self.build_operand(src)?,
+                self.build_operand(size)?
+            ),
+            mir::Intrinsic::PtrToInt(operand, _, to) => {
+                let to = self.cx.c_type(&to.clone().into())?;
+                format!("(({})(uintptr_t){})", to, self.build_operand(operand)?)
+            }
+            mir::Intrinsic::IntToPtr(operand, _, to) => {
+                let to = self.build_type(&to.clone().into())?;
+                format!("(({})(uintptr_t){})", to, self.build_operand(operand)?)
+            }
+            mir::Intrinsic::PtrToPtr(operand, _, to) => {
+                let to = self.build_type(&to.clone().into())?;
+                format!("(({}){})", to, self.build_operand(operand)?)
+            }
+            mir::Intrinsic::IntToInt(operand, _, to)
+            | mir::Intrinsic::FloatToInt(operand, _, to) => {
+                let to = self.cx.c_type(&to.clone().into())?;
+                format!("(({}){})", to, self.build_operand(operand)?)
+            }
+            mir::Intrinsic::IntToFloat(operand, _, to)
+            | mir::Intrinsic::FloatToFloat(operand, _, to) => {
+                let to = self.cx.c_type(&to.clone().into())?;
+                format!("(({}){})", to, self.build_operand(operand)?)
+            }
+            mir::Intrinsic::Overflows(op, lhs, rhs) => {
+                let mir::Type::Int(ty) = self.operand_type(lhs) else {
+                    unreachable!("overflow check of non-integers");
+                };
+
+                let a = self.build_operand(lhs)?;
+                let b = self.build_operand(rhs)?;
+                let (min, max) = int_limits(&ty);
+
+                // compare against the limits before computing, since signed
+                // overflow is undefined in C
+                match (op, ty.signed) {
+                    (mir::BinOp::IntAdd, false) => format!("({} > {} - {})", a, max, b),
+                    (mir::BinOp::IntAdd, true) => format!(
+                        "(({1} > 0 && {0} > {3} - {1}) || ({1} < 0 && {0} < {2} - {1}))",
+                        a, b, min, max
+                    ),
+                    (mir::BinOp::IntSub, false) => format!("({} < {})", a, b),
+                    (mir::BinOp::IntSub, true) => format!(
+                        "(({1} < 0 && {0} > {3} + {1}) || ({1} > 0 && {0} < {2} + {1}))",
+                        a, b, min, max
+                    ),
+                    (mir::BinOp::IntMul, false) => {
+                        format!("({1} != 0 && {0} > {2} / {1})", a, b, max)
+                    }
+                    (mir::BinOp::IntMul, true) => format!(
+                        "({0} > 0 ? ({1} > 0 ? {0} > {3} / {1} : {1} < {2} / {0}) \
+                         : ({1} > 0 ? {0} < {2} / {1} : {0} != 0 && {1} < {3} / {0}))",
+                        a, b, min, max
+                    ),
+                    // `MIN / -1` is the only signed division that overflows
+                    (mir::BinOp::IntDivSigned, true) => {
+                        format!("({1} == -1 && {0} == {2})", a, b, min)
+                    }
+                    _ => unreachable!("overflow check of {:?}", op),
+                }
+            }
+            mir::Intrinsic::Wrapping(op, lhs, rhs) => self.build_binary_op(*op, lhs, rhs)?,
+        })
+    }
+}
+
+/// Returns `string` as a C string literal.
+fn string_literal(string: &str) -> String {
+    let mut out = String::from("\"");
+
+    for byte in string.bytes() {
+        match byte {
+            b'"' => out.push_str("\\\""),
+            b'\\' => out.push_str("\\\\"),
+            b'\n' => out.push_str("\\n"),
+            // octal escapes, unlike hex, end after three digits
+            b' '..=b'~' => out.push(byte as char),
+            _ => out.push_str(&format!("\\{:03o}", byte)),
+        }
+    }
+
+    out.push('"');
+    out
+}
+
+fn local_name(local: mir::LocalId) -> String {
+    format!("_{}", local.as_raw_index())
+}
+
+fn block_name(block: mir::BlockId) -> String {
+    format!("bb{}", block.as_raw_index())
+}
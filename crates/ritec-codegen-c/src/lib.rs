@@ -0,0 +1,132 @@
+//! Translates MIR to C99, so programs can be compiled without LLVM.
+//!
+//! Every class, tuple and array becomes a struct, every instance of a
+//! function becomes a C function named after its mangled symbol, and MIR
+//! blocks become labels jumped between with `goto`. 128 bit integers use the
+//! `__int128` extension supported by GCC and Clang.
+
+mod context;
+mod function_builder;
+
+pub use context::*;
+pub use function_builder::*;
+
+use std::{fs, path::Path, process::Command};
+
+use ritec_core::{temp_path, SourceMap};
+use ritec_error::{Diagnostic, ErrorCode};
+use ritec_mir as mir;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CCodegen {
+    /// Extra flags passed to the C compiler, e.g. `-O2`.
+    pub cflags: Vec<String>,
+}
+
+impl CCodegen {
+    pub const fn new() -> Self {
+        Self { cflags: Vec::new() }
+    }
+
+    /// Translates `program` to a C source file.
+    pub fn emit(
+        &self,
+        program: &mir::Program,
+        source_map: &SourceMap,
+    ) -> Result<String, Diagnostic> {
+        let instances = mir::collect_instances(program)?;
+        let mut cx = CodegenCx::new(program, source_map);
+
+        for instance in &instances {
+            cx.build_function(instance)?;
+        }
+
+        Ok(cx.finish())
+    }
+
+    /// Translates `program` to C and compiles it into the executable `output`
+    /// with the system C compiler.
+    pub fn build_executable(
+        &self,
+        program: &mir::Program,
+        source_map: &SourceMap,
+        output: &Path,
+    ) -> Result<(), Diagnostic> {
+        let source = self.emit(program, source_map)?;
+
+        let file_name = output.file_stem().unwrap_or_default().to_string_lossy();
+        let path = temp_path(&file_name, "c");
+
+        fs::write(&path, source).map_err(|err| {
+            Diagnostic::error(format!(
+                "failed to write C source '{}': {}",
+                path.display(),
+                err
+            ))
+            .with_code(ErrorCode::E0012)
+        })?;
+
+        let result = self.compile(&path, output);
+        let _ = fs::remove_file(&path);
+
+        result
+    }
+
+    fn compile(&self, source: &Path, output: &Path) -> Result<(), Diagnostic> {
+        let mut command = Command::new("cc");
+        command.arg("-std=c99").args(&self.cflags);
+        command.arg(source).arg("-o").arg(output);
+
+        let result = command.output().map_err(|err| {
+            Diagnostic::error(format!("failed to run C compiler `cc`: {}", err))
+                .with_code(ErrorCode::E0012)
+        })?;
+
+        if !result.status.success() {
+            let stderr = String::from_utf8_lossy(&result.stderr);
+
+            return Err(Diagnostic::error(format!(
+                "compiling with `cc` failed: {}",
+                result.status
+            ))
+            .with_code(ErrorCode::E0012)
+            .with_note(stderr.trim_end().to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Compiles `program` to a temporary executable and runs it with `args`,
+    /// the first of which is the name of the program. Returns the exit code
+    /// of the program.
+    pub fn run(
+        &self,
+        program: &mir::Program,
+        source_map: &SourceMap,
+        args: &[String],
+    ) -> Result<i32, Diagnostic> {
+        let executable = temp_path("rite-c", "");
+        self.build_executable(program, source_map, &executable)?;
+
+        let mut command = Command::new(&executable);
+        if let Some((name, args)) = args.split_first() {
+            command.args(args);
+
+            #[cfg(unix)]
+            std::os::unix::process::CommandExt::arg0(&mut command, name);
+            #[cfg(not(unix))]
+            let _ = name;
+        }
+
+        let status = command.status();
+        let _ = fs::remove_file(&executable);
+
+        let status = status.map_err(|err| {
+            Diagnostic::error(format!("failed to run program: {}", err)).with_code(ErrorCode::E0012)
+        })?;
+
+        status
+            .code()
+            .ok_or_else(|| Diagnostic::error(format!("program terminated by {}", status)))
+    }
+}
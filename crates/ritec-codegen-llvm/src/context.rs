@@ -1,53 +1,122 @@
 use std::{collections::HashMap, ops::Deref};
 
 use inkwell::{
-    context::Context, execution_engine::ExecutionEngine, module::Module, targets::TargetData,
-    values::FunctionValue, OptimizationLevel,
+    attributes::{Attribute, AttributeLoc},
+    context::Context,
+    module::{Linkage, Module},
+    targets::{TargetData, TargetMachine},
+    values::FunctionValue,
+    AddressSpace,
 };
+use ritec_core::SourceMap;
 use ritec_mir as mir;
 
-use crate::FunctionBuilder;
+use crate::{DebugInfo, FunctionBuilder};
 
 pub struct CodegenCx<'c> {
     pub context: &'c Context,
     pub module: Module<'c>,
-    pub execution_engine: ExecutionEngine<'c>,
+    pub target_data: TargetData,
     pub program: &'c mir::Program,
-    pub functions: HashMap<(mir::FunctionId, Vec<mir::Type>), FunctionValue<'c>>,
+    /// Used to print the location of failed runtime checks.
+    pub source_map: &'c SourceMap,
+    pub functions: HashMap<mir::Instance, FunctionValue<'c>>,
+    pub debug_info: Option<DebugInfo<'c>>,
 }
 
 impl<'c> CodegenCx<'c> {
-    pub fn new(context: &'c Context, program: &'c mir::Program) -> Self {
+    pub fn new(
+        context: &'c Context,
+        program: &'c mir::Program,
+        source_map: &'c SourceMap,
+        target_machine: &TargetMachine,
+    ) -> Self {
         let module = context.create_module("main");
-        let execution_engine = module
-            .create_jit_execution_engine(OptimizationLevel::None)
-            .unwrap();
+        let target_data = target_machine.get_target_data();
+
+        module.set_triple(&target_machine.get_triple());
+        module.set_data_layout(&target_data.get_data_layout());
 
         Self {
             context,
             module,
-            execution_engine,
+            target_data,
             program,
+            source_map,
             functions: HashMap::new(),
+            debug_info: None,
         }
     }
 
     pub fn target_data(&self) -> &TargetData {
-        self.execution_engine.get_target_data()
+        &self.target_data
     }
 
-    pub fn build_function(
-        &mut self,
-        function: mir::FunctionId,
-        generics: &[mir::Type],
-    ) -> FunctionValue<'c> {
-        let instance = (function, generics.to_vec());
+    /// Returns `rite.panic`, which writes a message of the given length to
+    /// stderr and aborts, adding it to the module the first time.
+    pub fn panic_function(&mut self) -> FunctionValue<'c> {
+        if let Some(function) = self.module.get_function("rite.panic") {
+            return function;
+        }
+
+        let context = self.context;
+        let usize_type = context.ptr_sized_int_type(&self.target_data, None);
+        let message_type = context.i8_type().ptr_type(AddressSpace::Generic);
+
+        let write_type = usize_type.fn_type(
+            &[
+                context.i32_type().into(),
+                message_type.into(),
+                usize_type.into(),
+            ],
+            false,
+        );
+        let write = self.module.add_function("write", write_type, None);
+        let abort_type = context.void_type().fn_type(&[], false);
+        let abort = self.module.add_function("abort", abort_type, None);
 
-        if let Some(function) = self.functions.get(&instance) {
-            return *function;
+        let panic_type = context
+            .void_type()
+            .fn_type(&[message_type.into(), usize_type.into()], false);
+        let panic = self
+            .module
+            .add_function("rite.panic", panic_type, Some(Linkage::Private));
+
+        for name in ["noreturn", "cold", "noinline"] {
+            let kind = Attribute::get_named_enum_kind_id(name);
+            let attribute = context.create_enum_attribute(kind, 0);
+            panic.add_attribute(AttributeLoc::Function, attribute);
         }
 
-        let mut builder = FunctionBuilder::new(self, function, instance.1);
+        let builder = context.create_builder();
+        builder.position_at_end(context.append_basic_block(panic, "entry"));
+
+        // write to stderr
+        let stderr = context.i32_type().const_int(2, false).into();
+        let message = panic.get_nth_param(0).unwrap().into();
+        let len = panic.get_nth_param(1).unwrap().into();
+        builder.build_call(write, &[stderr, message, len], "write");
+        builder.build_call(abort, &[], "");
+        builder.build_unreachable();
+
+        panic
+    }
+
+    /// Adds `instance` to the module without a body.
+    pub fn declare_function(&mut self, instance: &mir::Instance) -> FunctionValue<'c> {
+        if let Some(&fn_value) = self.functions.get(instance) {
+            return fn_value;
+        }
+
+        let generics = instance.generics.clone();
+        let mut builder = FunctionBuilder::new(self, instance.function, generics);
+        builder.declare()
+    }
+
+    /// Builds the body of `instance`, which must already be declared.
+    pub fn define_function(&mut self, instance: &mir::Instance) -> FunctionValue<'c> {
+        let generics = instance.generics.clone();
+        let mut builder = FunctionBuilder::new(self, instance.function, generics);
         builder.build()
     }
 }
@@ -0,0 +1,397 @@
+use std::{collections::HashMap, env, path::Path};
+
+use inkwell::{
+    debug_info::{
+        debug_metadata_version, AsDIScope, DICompileUnit, DIFile, DIFlags, DIFlagsConstants,
+        DIType, DWARFEmissionKind, DWARFSourceLanguage, DebugInfoBuilder,
+    },
+    module::{FlagBehavior, Module},
+    types::{AnyType, BasicTypeEnum},
+    values::{FunctionValue, PointerValue},
+    AddressSpace,
+};
+use mir::GenericMap;
+use ritec_core::{FileId, SourceMap, Span};
+use ritec_mir as mir;
+
+use crate::FunctionBuilder;
+
+const DW_ATE_ADDRESS: u32 = 0x01;
+const DW_ATE_BOOLEAN: u32 = 0x02;
+const DW_ATE_FLOAT: u32 = 0x04;
+const DW_ATE_SIGNED: u32 = 0x05;
+const DW_ATE_UNSIGNED: u32 = 0x08;
+
+/// DWARF debug information for a module, with a compile unit for every
+/// [`SourceFile`](ritec_core::SourceFile) in the [`SourceMap`].
+pub struct DebugInfo<'c> {
+    pub source_map: &'c SourceMap,
+    units: HashMap<FileId, (DebugInfoBuilder<'c>, DICompileUnit<'c>)>,
+    types: HashMap<mir::Type, DIType<'c>>,
+    is_optimized: bool,
+}
+
+impl<'c> DebugInfo<'c> {
+    pub fn new(module: &Module<'c>, source_map: &'c SourceMap, is_optimized: bool) -> Self {
+        let context = module.get_context();
+        let i32_type = context.i32_type();
+
+        let version = i32_type.const_int(debug_metadata_version() as u64, false);
+        module.add_basic_value_flag("Debug Info Version", FlagBehavior::Warning, version);
+        let dwarf_version = i32_type.const_int(4, false);
+        module.add_basic_value_flag("Dwarf Version", FlagBehavior::Warning, dwarf_version);
+
+        let mut units = HashMap::new();
+        for (id, file) in source_map.iter() {
+            let path = match env::current_dir() {
+                Ok(dir) => dir.join(&file.path),
+                Err(_) => file.path.clone(),
+            };
+
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+            let directory = path.parent().unwrap_or(Path::new("")).to_string_lossy();
+
+            let unit = module.create_debug_info_builder(
+                true,
+                DWARFSourceLanguage::C,
+                &file_name,
+                &directory,
+                "ritec",
+                is_optimized,
+                "",
+                0,
+                "",
+                DWARFEmissionKind::Full,
+                0,
+                false,
+                false,
+                "",
+                "",
+            );
+
+            units.insert(id, unit);
+        }
+
+        Self {
+            source_map,
+            units,
+            types: HashMap::new(),
+            is_optimized,
+        }
+    }
+
+    /// Returns the debug info builder and compile unit of `file`.
+    pub fn unit(&self, file: FileId) -> Option<&(DebugInfoBuilder<'c>, DICompileUnit<'c>)> {
+        self.units.get(&file)
+    }
+
+    /// Returns the file and one-based line and column of `span`.
+    pub fn lookup(&self, span: Span) -> Option<(FileId, u32, u32)> {
+        let (file, line, column) = self.source_map.lookup(span)?;
+        Some((file, line as u32, column as u32))
+    }
+
+    /// Resolves all the debug information, must be called before the module
+    /// is emitted.
+    pub fn finalize(&self) {
+        for (builder, _) in self.units.values() {
+            builder.finalize();
+        }
+    }
+}
+
+impl<'a, 'c> FunctionBuilder<'a, 'c> {
+    /// Returns the builder and file of the compile unit of `file`, or of the
+    /// function being built.
+    fn debug_unit(&self, file: Option<FileId>) -> (&DebugInfoBuilder<'c>, DIFile<'c>) {
+        let debug_info = self.cx.debug_info.as_ref().unwrap();
+        let (builder, unit) = debug_info.unit(file.or(self.debug_file).unwrap()).unwrap();
+        (builder, unit.get_file())
+    }
+
+    /// Creates the subprogram of `fn_value`, functions without a source
+    /// location get no debug information.
+    pub fn build_debug_function(
+        &mut self,
+        fn_value: FunctionValue<'c>,
+        name: &str,
+        linkage_name: &str,
+    ) {
+        let function = self.function();
+
+        let Some(ref debug_info) = self.cx.debug_info else {
+            return;
+        };
+
+        let Some((file, line, _)) = debug_info.lookup(function.ident.span()) else {
+            return;
+        };
+
+        if debug_info.unit(file).is_none() {
+            return;
+        }
+
+        self.debug_file = Some(file);
+
+        let mut arguments = Vec::new();
+        for argument in &function.arguments {
+            arguments.push(self.debug_type(&argument.ty));
+        }
+
+        let return_type = match function.return_type {
+            mir::Type::Void => None,
+            ref ty => Some(self.debug_type(ty)),
+        };
+
+        let (builder, file) = self.debug_unit(None);
+        let ty = builder.create_subroutine_type(file, return_type, &arguments, DIFlags::ZERO);
+
+        let is_optimized = self.cx.debug_info.as_ref().unwrap().is_optimized;
+        let subprogram = builder.create_function(
+            file.as_debug_info_scope(),
+            name,
+            Some(linkage_name),
+            file,
+            line,
+            ty,
+            false,
+            true,
+            line,
+            DIFlags::PUBLIC,
+            is_optimized,
+        );
+
+        fn_value.set_subprogram(subprogram);
+        self.subprogram = Some(subprogram);
+        self.set_debug_location(function.ident.span());
+    }
+
+    /// Attaches the location of `span` to the instructions built after this,
+    /// dummy spans keep the previous location.
+    pub fn set_debug_location(&self, span: Span) {
+        let Some(subprogram) = self.subprogram else {
+            return;
+        };
+
+        let debug_info = self.cx.debug_info.as_ref().unwrap();
+        let Some((file, line, column)) = debug_info.lookup(span) else {
+            return;
+        };
+
+        if Some(file) != self.debug_file {
+            return;
+        }
+
+        let scope = subprogram.as_debug_info_scope();
+        let (builder, _) = self.debug_unit(None);
+        let location = builder.create_debug_location(self.cx(), line, column, scope, None);
+        self.builder.set_current_debug_location(self.cx(), location);
+    }
+
+    /// Declares the named `local` stored at `value`, arguments are declared
+    /// as parameters.
+    pub fn build_debug_local(
+        &mut self,
+        local_id: mir::LocalId,
+        local: &mir::Local,
+        value: PointerValue<'c>,
+    ) {
+        let Some(subprogram) = self.subprogram else {
+            return;
+        };
+
+        let Some(ref ident) = local.ident else {
+            return;
+        };
+
+        let debug_info = self.cx.debug_info.as_ref().unwrap();
+        let Some((file, line, column)) = debug_info.lookup(local.source_info.span) else {
+            return;
+        };
+
+        if Some(file) != self.debug_file {
+            return;
+        }
+
+        let ty = self.debug_type(&local.ty);
+        let scope = subprogram.as_debug_info_scope();
+
+        let function = self.function();
+        let argument = function.arguments.iter().position(|a| a.local == local_id);
+
+        let (builder, file) = self.debug_unit(None);
+        let variable = match argument {
+            Some(index) => builder.create_parameter_variable(
+                scope,
+                ident.value(),
+                index as u32 + 1,
+                file,
+                line,
+                ty,
+                true,
+                DIFlags::ZERO,
+            ),
+            None => builder.create_auto_variable(
+                scope,
+                ident.value(),
+                file,
+                line,
+                ty,
+                true,
+                DIFlags::ZERO,
+                0,
+            ),
+        };
+
+        let location = builder.create_debug_location(self.cx(), line, column, scope, None);
+        let block = self.builder.get_insert_block().unwrap();
+        builder.insert_declare_at_end(value, Some(variable), None, location, block);
+    }
+
+    /// Returns the debug type of `ty`, with the generics of the function
+    /// instantiated.
+    pub fn debug_type(&mut self, ty: &mir::Type) -> DIType<'c> {
+        let mut ty = ty.clone();
+        let function = self.function();
+        ty.instantiate(&GenericMap::new(&function.generics, &self.generics));
+
+        let debug_info = self.cx.debug_info.as_ref().unwrap();
+        if let Some(&debug_type) = debug_info.types.get(&ty) {
+            return debug_type;
+        }
+
+        let debug_type = self.build_debug_type(&ty);
+        let debug_info = self.cx.debug_info.as_mut().unwrap();
+        debug_info.types.insert(ty, debug_type);
+
+        debug_type
+    }
+
+    fn build_debug_type(&mut self, ty: &mir::Type) -> DIType<'c> {
+        let llvm_type = self.build_type(ty);
+        let size = self
+            .cx
+            .target_data()
+            .get_bit_size(&llvm_type as &dyn AnyType);
+        let align = self
+            .cx
+            .target_data()
+            .get_abi_alignment(&llvm_type as &dyn AnyType)
+            * 8;
+        let name = ty.to_string();
+
+        let basic_type = |this: &Self, encoding| {
+            let (builder, _) = this.debug_unit(None);
+            let basic = builder.create_basic_type(&name, size, encoding, DIFlags::ZERO);
+            basic.unwrap().as_type()
+        };
+
+        match ty {
+            mir::Type::Bool => basic_type(self, DW_ATE_BOOLEAN),
+            mir::Type::Int(int) if int.signed => basic_type(self, DW_ATE_SIGNED),
+            mir::Type::Int(_) => basic_type(self, DW_ATE_UNSIGNED),
+            mir::Type::Float(_) => basic_type(self, DW_ATE_FLOAT),
+            mir::Type::Function(_) => basic_type(self, DW_ATE_ADDRESS),
+            mir::Type::Pointer(pointer) => {
+                let pointee = self.debug_type(pointer.pointee());
+                let (builder, _) = self.debug_unit(None);
+                let pointer =
+                    builder.create_pointer_type(&name, pointee, size, align, AddressSpace::Generic);
+                pointer.as_type()
+            }
+            mir::Type::Array(array) => {
+                let element = self.debug_type(&array.element);
+                let (builder, _) = self.debug_unit(None);
+                let range = 0..array.size as i64;
+                let array = builder.create_array_type(element, size, align, &[range]);
+                array.as_type()
+            }
+            mir::Type::Void | mir::Type::Tuple(_) | mir::Type::Class(_) => {
+                let BasicTypeEnum::StructType(struct_type) = llvm_type else {
+                    unreachable!()
+                };
+
+                let mut fields = Vec::new();
+                let mut file = None;
+                let mut line = 0;
+                match ty {
+                    mir::Type::Tuple(tuple) => {
+                        for (i, field) in tuple.fields.iter().enumerate() {
+                            fields.push((i.to_string(), field.clone(), 0));
+                        }
+                    }
+                    mir::Type::Class(class_type) => {
+                        let class = &self.cx.program[class_type.class];
+                        let generics = GenericMap::new(&class.generics, &class_type.generics);
+                        let debug_info = self.cx.debug_info.as_ref().unwrap();
+
+                        if let Some((class_file, class_line, _)) =
+                            debug_info.lookup(class.ident.span())
+                        {
+                            if debug_info.unit(class_file).is_some() {
+                                file = Some(class_file);
+                                line = class_line;
+                            }
+                        }
+
+                        for field in &class.fields {
+                            let mut field_type = field.ty.clone();
+                            field_type.instantiate(&generics);
+
+                            let field_line = match debug_info.lookup(field.ident.span()) {
+                                Some((_, field_line, _)) => field_line,
+                                None => 0,
+                            };
+
+                            fields.push((field.ident.to_string(), field_type, field_line));
+                        }
+                    }
+                    _ => {}
+                }
+
+                let mut elements = Vec::new();
+                for (i, (field_name, field_type, field_line)) in fields.into_iter().enumerate() {
+                    let field_type = self.debug_type(&field_type);
+                    let offset = self
+                        .cx
+                        .target_data()
+                        .offset_of_element(&struct_type, i as u32);
+
+                    let (builder, unit_file) = self.debug_unit(file);
+                    let member = builder.create_member_type(
+                        unit_file.as_debug_info_scope(),
+                        &field_name,
+                        unit_file,
+                        field_line,
+                        field_type.get_size_in_bits(),
+                        field_type.get_align_in_bits(),
+                        offset.unwrap_or_default() * 8,
+                        DIFlags::PUBLIC,
+                        field_type,
+                    );
+
+                    elements.push(member.as_type());
+                }
+
+                let (builder, unit_file) = self.debug_unit(file);
+                let struct_type = builder.create_struct_type(
+                    unit_file.as_debug_info_scope(),
+                    &name,
+                    unit_file,
+                    line,
+                    size,
+                    align,
+                    DIFlags::PUBLIC,
+                    None,
+                    &elements,
+                    0,
+                    None,
+                    &name,
+                );
+
+                struct_type.as_type()
+            }
+            mir::Type::Slice(_) | mir::Type::Generic(_) => unreachable!(),
+        }
+    }
+}
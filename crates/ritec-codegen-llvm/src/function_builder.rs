@@ -4,12 +4,13 @@ use inkwell::{
     basic_block::BasicBlock,
     builder::Builder,
     context::Context,
+    debug_info::DISubprogram,
     types::{BasicType, BasicTypeEnum, FloatType, FunctionType, IntType, PointerType},
     values::{BasicValueEnum, CallableValue, FunctionValue, PointerValue},
     AddressSpace, FloatPredicate, IntPredicate,
 };
 use mir::GenericMap;
-use ritec_core::FloatSize;
+use ritec_core::{FileId, FloatSize, Span};
 use ritec_mir as mir;
 
 use crate::CodegenCx;
@@ -22,6 +23,8 @@ pub struct FunctionBuilder<'a, 'c> {
     pub generics: Vec<mir::Type>,
     pub locals: HashMap<mir::LocalId, PointerValue<'c>>,
     pub blocks: HashMap<mir::BlockId, BasicBlock<'c>>,
+    pub debug_file: Option<FileId>,
+    pub subprogram: Option<DISubprogram<'c>>,
 }
 
 impl<'a, 'c> FunctionBuilder<'a, 'c> {
@@ -40,6 +43,8 @@ impl<'a, 'c> FunctionBuilder<'a, 'c> {
             generics,
             locals: HashMap::new(),
             blocks: HashMap::new(),
+            debug_file: None,
+            subprogram: None,
         }
     }
 
@@ -146,33 +151,44 @@ impl<'a, 'c> FunctionBuilder<'a, 'c> {
         }
     }
 
-    pub fn build(&mut self) -> FunctionValue<'c> {
-        // add function
-        let function = &self.cx.program[self.function];
-        let generics: Vec<_> = self.generics.iter().map(mir::Type::to_string).collect();
-        let function_name = if !generics.is_empty() {
-            format!("{}<{}>", function.ident, generics.join(", "))
-        } else {
-            format!("{}", function.ident)
-        };
+    pub fn instance(&self) -> mir::Instance {
+        mir::Instance::new(self.function, self.generics.clone())
+    }
+
+    /// Adds the function to the module, without a body.
+    pub fn declare(&mut self) -> FunctionValue<'c> {
+        let function = self.function();
+        let symbol = mir::mangle(self.cx.program, self.function, &self.generics);
 
         let fn_type = self.build_function_type(&function.ty());
-        let fn_value = self.cx.module.add_function(&function_name, fn_type, None);
+        let fn_value = self.cx.module.add_function(&symbol, fn_type, None);
+        self.cx.functions.insert(self.instance(), fn_value);
+
+        fn_value
+    }
+
+    /// Builds the body of the function, which must already be declared.
+    pub fn build(&mut self) -> FunctionValue<'c> {
+        let function = self.function();
+        let fn_value = self.cx.functions[&self.instance()];
         self.fn_value = Some(fn_value);
 
-        let instance = (self.function, self.generics.clone());
-        self.cx.functions.insert(instance, fn_value);
+        let symbol = fn_value.get_name().to_string_lossy().into_owned();
+        let name = self.instance().name(self.cx.program);
 
         // create entry block
         let block = self.cx.append_basic_block(fn_value, "entry");
         self.builder.position_at_end(block);
 
+        self.build_debug_function(fn_value, &name, &symbol);
+
         // allocate locals on the stack
         for (local_id, local) in function.body.locals.iter() {
             let ty = self.build_type(&local.ty);
             let name = format!("_{}", local_id.as_raw_index());
             let value = self.builder.build_alloca(ty, &name);
             self.locals.insert(local_id, value);
+            self.build_debug_local(local_id, local, value);
         }
 
         // store arguments in locals
@@ -214,13 +230,15 @@ impl<'a, 'c> FunctionBuilder<'a, 'c> {
     }
 
     pub fn build_statement(&mut self, statement: &mir::Statement) {
-        match statement {
-            mir::Statement::Assign(assign) => {
+        self.set_debug_location(statement.source_info.span);
+
+        match &statement.kind {
+            mir::StatementKind::Assign(assign) => {
                 let place = self.build_place(&assign.place);
                 let value = self.build_value(&assign.value);
                 self.builder.build_store(place, value);
             }
-            mir::Statement::Drop(value) => {
+            mir::StatementKind::Drop(value) => {
                 let _ = self.build_value(value);
             }
         }
@@ -281,11 +299,12 @@ impl<'a, 'c> FunctionBuilder<'a, 'c> {
                     resolved.push(generic);
                 }
 
-                self.cx
-                    .build_function(*id, &resolved)
-                    .as_global_value()
-                    .as_pointer_value()
-                    .into()
+                let instance = mir::Instance::new(*id, resolved);
+                let fn_value = self.cx.functions.get(&instance).unwrap_or_else(|| {
+                    panic!("`{}` was not collected", instance.name(self.cx.program))
+                });
+
+                fn_value.as_global_value().as_pointer_value().into()
             }
             mir::Constant::Integer(i, ty) => {
                 let ty = match ty.size {
@@ -502,21 +521,13 @@ impl<'a, 'c> FunctionBuilder<'a, 'c> {
         match intrinsic {
             mir::Intrinsic::Sizeof(ty) => {
                 let ty = self.build_type(ty);
-                ty.size_of().unwrap().into()
+                let size = self.cx.target_data().get_abi_size(&ty);
+                self.usize_type().const_int(size, false).into()
             }
             mir::Intrinsic::Alignof(ty) => {
                 let ty = self.build_type(ty);
-
-                let align = match ty {
-                    BasicTypeEnum::ArrayType(ty) => ty.get_alignment(),
-                    BasicTypeEnum::FloatType(ty) => ty.get_alignment(),
-                    BasicTypeEnum::IntType(ty) => ty.get_alignment(),
-                    BasicTypeEnum::PointerType(ty) => ty.get_alignment(),
-                    BasicTypeEnum::StructType(ty) => ty.get_alignment(),
-                    BasicTypeEnum::VectorType(ty) => ty.get_alignment(),
-                };
-
-                align.into()
+                let align = self.cx.target_data().get_abi_alignment(&ty);
+                self.usize_type().const_int(align as u64, false).into()
             }
             mir::Intrinsic::Bitcast(operand, ty) => {
                 let operand = self.build_operand(operand);
@@ -624,9 +635,97 @@ impl<'a, 'c> FunctionBuilder<'a, 'c> {
                         .into()
                 }
             }
+            mir::Intrinsic::Overflows(op, lhs, rhs) => self.build_overflows(*op, lhs, rhs),
+            mir::Intrinsic::Wrapping(op, lhs, rhs) => self.build_binary_op(*op, lhs, rhs),
         }
     }
 
+    /// Builds whether `op` of `lhs` and `rhs` overflows.
+    pub fn build_overflows(
+        &mut self,
+        op: mir::BinOp,
+        lhs: &mir::Operand,
+        rhs: &mir::Operand,
+    ) -> BasicValueEnum<'c> {
+        let program = self.cx.program;
+        let ty = program.operand_type(self.function(), &self.generics, lhs);
+        let mir::Type::Int(ty) = ty else {
+            panic!("overflow check on non-integer type: {}", ty);
+        };
+
+        let lhs = self.build_operand(lhs).into_int_value();
+        let rhs = self.build_operand(rhs).into_int_value();
+        let int_type = lhs.get_type();
+
+        // signed division only overflows for `MIN / -1`
+        if let mir::BinOp::IntDivSigned = op {
+            let bits = int_type.get_bit_width();
+            let min = int_type.const_int(1 << (bits - 1), false);
+            let minus_one = int_type.const_all_ones();
+
+            let is_min = (self.builder).build_int_compare(IntPredicate::EQ, lhs, min, "is_min");
+            let is_minus_one =
+                (self.builder).build_int_compare(IntPredicate::EQ, rhs, minus_one, "is_minus_one");
+
+            return self
+                .builder
+                .build_and(is_min, is_minus_one, "overflows")
+                .into();
+        }
+
+        let sign = if ty.signed { "s" } else { "u" };
+        let op = match op {
+            mir::BinOp::IntAdd => "add",
+            mir::BinOp::IntSub => "sub",
+            mir::BinOp::IntMul => "mul",
+            _ => panic!("overflow check on invalid operator: {:?}", op),
+        };
+        let name = format!(
+            "llvm.{}{}.with.overflow.i{}",
+            sign,
+            op,
+            int_type.get_bit_width()
+        );
+
+        let intrinsic = match self.cx.module.get_function(&name) {
+            Some(intrinsic) => intrinsic,
+            None => {
+                let bool_type = self.cx().bool_type();
+                let result = self
+                    .cx()
+                    .struct_type(&[int_type.into(), bool_type.into()], false);
+                let fn_type = result.fn_type(&[int_type.into(), int_type.into()], false);
+                self.cx.module.add_function(&name, fn_type, None)
+            }
+        };
+
+        let result = self
+            .builder
+            .build_call(intrinsic, &[lhs.into(), rhs.into()], "checked")
+            .try_as_basic_value()
+            .left()
+            .unwrap()
+            .into_struct_value();
+
+        (self.builder)
+            .build_extract_value(result, 1, "overflows")
+            .unwrap()
+    }
+
+    /// Builds a call to `rite.panic` with `message`, which never returns.
+    pub fn build_panic(&mut self, message: mir::AssertMessage, span: Span) {
+        let message = message.panic_message(span, self.cx.source_map);
+        let len = self.usize_type().const_int(message.len() as u64, false);
+        let message = self
+            .builder
+            .build_global_string_ptr(&message, "panic_message")
+            .as_pointer_value();
+
+        let panic = self.cx.panic_function();
+        (self.builder).build_call(panic, &[message.into(), len.into()], "");
+        self.builder.build_unreachable();
+    }
+
     pub fn build_call(
         &mut self,
         callee: &mir::Operand,
@@ -648,15 +747,17 @@ impl<'a, 'c> FunctionBuilder<'a, 'c> {
     }
 
     pub fn build_terminator(&mut self, terminator: &mir::Terminator) {
-        match terminator {
-            mir::Terminator::Goto(block) => {
+        self.set_debug_location(terminator.source_info.span);
+
+        match &terminator.kind {
+            mir::TerminatorKind::Goto(block) => {
                 self.builder.build_unconditional_branch(self.blocks[block]);
             }
-            mir::Terminator::Return(operand) => {
+            mir::TerminatorKind::Return(operand) => {
                 let value = self.build_operand(operand);
                 self.builder.build_return(Some(&value));
             }
-            mir::Terminator::Switch(value, targets) => {
+            mir::TerminatorKind::Switch(value, targets) => {
                 let value = self.build_operand(value).into_int_value();
 
                 let mut cases = Vec::new();
@@ -668,6 +769,22 @@ impl<'a, 'c> FunctionBuilder<'a, 'c> {
                 let default = self.blocks[&targets.default];
                 self.builder.build_switch(value, default, &cases);
             }
+            mir::TerminatorKind::Assert(assert) => {
+                let cond = self.build_operand(&assert.cond).into_int_value();
+
+                let fn_value = self.fn_value.unwrap();
+                let target = self.blocks[&assert.target];
+                let panic = self.cx().append_basic_block(fn_value, "panic");
+
+                if assert.expected {
+                    (self.builder).build_conditional_branch(cond, target, panic);
+                } else {
+                    (self.builder).build_conditional_branch(cond, panic, target);
+                }
+
+                self.builder.position_at_end(panic);
+                self.build_panic(assert.message, terminator.source_info.span);
+            }
         }
     }
 }
@@ -1,40 +1,339 @@
 mod context;
+mod debug_info;
 mod function_builder;
+mod linker;
+mod optimize;
 
 pub use context::*;
+pub use debug_info::*;
 pub use function_builder::*;
+pub use linker::*;
+pub use optimize::*;
 
-use inkwell::context::Context;
+use std::{
+    ffi::{c_char, CString},
+    fmt::{self, Display},
+    fs,
+    path::Path,
+    ptr,
+};
+
+use inkwell::{
+    context::Context,
+    targets::{
+        CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine, TargetTriple,
+    },
+    types::AnyType,
+    AddressSpace,
+};
+use ritec_core::{temp_path, IntSize, OptLevel, SourceMap, TargetLayout};
+use ritec_error::{Diagnostic, ErrorCode};
 use ritec_mir as mir;
 
-type MainFn = unsafe extern "C" fn(i32, *const *const i8) -> i32;
+type MainFn = unsafe extern "C" fn(i32, *const *const c_char) -> i32;
 
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
-pub struct LLVMCodegen;
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OutputType {
+    LlvmIr,
+    Assembly,
+    Object,
+}
+
+impl Display for OutputType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LlvmIr => write!(f, "LLVM IR"),
+            Self::Assembly => write!(f, "assembly"),
+            Self::Object => write!(f, "object file"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct LLVMCodegen {
+    pub opt_level: OptLevel,
+    /// The target triple to compile for, `None` compiles for the host.
+    pub target: Option<String>,
+    /// Whether to emit DWARF debug information.
+    pub debug_info: bool,
+}
 
 impl LLVMCodegen {
-    pub fn compile(program: &mir::Program) {
-        let context = Context::create();
-        let mut cx = CodegenCx::new(&context, program);
+    pub const fn new() -> Self {
+        Self {
+            opt_level: OptLevel::O0,
+            target: None,
+            debug_info: false,
+        }
+    }
+
+    /// Returns `true` if code is generated for the host, the vendor of the
+    /// target triple is ignored.
+    pub fn is_host(&self) -> bool {
+        fn components(triple: &TargetTriple) -> Vec<String> {
+            let triple = TargetMachine::normalize_triple(triple);
+            let triple = triple.as_str().to_string_lossy();
 
-        for (id, function) in program.functions.iter() {
-            if !function.generics.is_empty() {
-                continue;
+            let mut components: Vec<_> = triple.split('-').map(String::from).collect();
+            if components.len() > 1 {
+                components.remove(1);
             }
 
-            cx.build_function(id, &[]);
+            components
         }
 
-        cx.module.print_to_stderr();
+        match self.target {
+            Some(ref triple) => {
+                let host = TargetMachine::get_default_triple();
+                components(&TargetTriple::create(triple)) == components(&host)
+            }
+            None => true,
+        }
+    }
+
+    /// Creates a [`TargetMachine`] for [`Self::target`], or for the host if
+    /// no target is set.
+    pub fn target_machine(&self) -> Result<TargetMachine, Diagnostic> {
+        let (triple, cpu, features) = match self.target {
+            Some(ref triple) => {
+                Target::initialize_all(&InitializationConfig::default());
 
-        let main_address = cx.execution_engine.get_function_address("main").unwrap();
+                let triple = TargetTriple::create(triple);
+                (triple, String::new(), String::new())
+            }
+            None => {
+                Target::initialize_native(&InitializationConfig::default()).map_err(|err| {
+                    Diagnostic::error(format!("failed to initialize native target: {}", err))
+                        .with_code(ErrorCode::E0012)
+                })?;
+
+                let cpu = TargetMachine::get_host_cpu_name().to_string();
+                let features = TargetMachine::get_host_cpu_features().to_string();
+                (TargetMachine::get_default_triple(), cpu, features)
+            }
+        };
+
+        let target = Target::from_triple(&triple).map_err(|err| {
+            let triple = triple.as_str().to_string_lossy();
+            Diagnostic::error(format!("unsupported target '{}': {}", triple, err))
+                .with_code(ErrorCode::E0012)
+        })?;
+
+        let target_machine = target.create_target_machine(
+            &triple,
+            &cpu,
+            &features,
+            llvm_opt_level(self.opt_level),
+            RelocMode::PIC,
+            CodeModel::Default,
+        );
+
+        target_machine.ok_or_else(|| {
+            let triple = triple.as_str().to_string_lossy();
+            Diagnostic::error(format!("failed to create target machine for '{}'", triple))
+                .with_code(ErrorCode::E0012)
+        })
+    }
+
+    /// Returns the layout of types on [`Self::target`], taken from the data
+    /// layout of its target machine so `sizeof` and `alignof` in constants
+    /// agree with the generated code.
+    pub fn target_layout(&self) -> Result<TargetLayout, Diagnostic> {
+        let target_data = self.target_machine()?.get_target_data();
+        let context = Context::create();
+
+        let align = |ty: &dyn AnyType| target_data.get_abi_alignment(ty) as u64;
+        let int_align =
+            |size: IntSize| align(&context.custom_width_int_type(size.bit_width() as u32));
+
+        let sizes = [
+            IntSize::I8,
+            IntSize::I16,
+            IntSize::I32,
+            IntSize::I64,
+            IntSize::I128,
+        ];
+        let pointer = context.i8_type().ptr_type(AddressSpace::Generic);
+
+        Ok(TargetLayout {
+            pointer_size: target_data.get_pointer_byte_size(None) as u64,
+            pointer_align: align(&pointer),
+            int_aligns: sizes.map(int_align),
+            float_aligns: [
+                align(&context.f16_type()),
+                align(&context.f32_type()),
+                align(&context.f64_type()),
+            ],
+        })
+    }
+
+    fn build_module<'c>(
+        &self,
+        context: &'c Context,
+        program: &'c mir::Program,
+        source_map: &'c SourceMap,
+        target_machine: &TargetMachine,
+    ) -> Result<CodegenCx<'c>, Diagnostic> {
+        let instances = mir::collect_instances(program)?;
+        let mut cx = CodegenCx::new(context, program, source_map, target_machine);
+
+        if self.debug_info {
+            let is_optimized = self.opt_level != OptLevel::O0;
+            cx.debug_info = Some(DebugInfo::new(&cx.module, source_map, is_optimized));
+        }
+
+        // declare everything first, so calls never need to build another function
+        for instance in &instances {
+            cx.declare_function(instance);
+        }
+
+        for instance in &instances {
+            cx.define_function(instance);
+        }
+
+        if let Some(ref debug_info) = cx.debug_info {
+            debug_info.finalize();
+        }
+
+        optimize(self.opt_level, &cx.module);
+
+        Ok(cx)
+    }
+
+    /// Compiles `program` and runs its `main` function in-process with
+    /// `args`, returns the value returned by `main`.
+    pub fn run(
+        &self,
+        program: &mir::Program,
+        source_map: &SourceMap,
+        args: &[String],
+    ) -> Result<i32, Diagnostic> {
+        if !self.is_host() {
+            let target = self.target.as_deref().unwrap_or_default();
+            let host = TargetMachine::get_default_triple();
+
+            return Err(Diagnostic::error(format!(
+                "cannot run a program compiled for '{}' on '{}'",
+                target,
+                host.as_str().to_string_lossy()
+            ))
+            .with_code(ErrorCode::E0012));
+        }
+
+        let target_machine = self.target_machine()?;
+
+        let context = Context::create();
+        let cx = self.build_module(&context, program, source_map, &target_machine)?;
+
+        let execution_engine = cx
+            .module
+            .create_jit_execution_engine(llvm_opt_level(self.opt_level))
+            .map_err(|err| {
+                Diagnostic::error(format!("failed to create execution engine: {}", err))
+                    .with_code(ErrorCode::E0012)
+            })?;
+
+        let main_address = execution_engine
+            .get_function_address("main")
+            .map_err(|_| Diagnostic::error("`main` function not found"))?;
 
         let main = unsafe { std::mem::transmute::<_, MainFn>(main_address) };
 
-        let args = std::env::args().collect::<Vec<_>>();
-        let args = args.iter().map(|arg| arg.as_ptr()).collect::<Vec<_>>();
+        // `argv` is a null terminated array of C strings, which must outlive the call
+        let args = args
+            .iter()
+            .map(|arg| CString::new(arg.as_bytes()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| Diagnostic::error("program arguments cannot contain nul bytes"))?;
+
+        let mut argv = args.iter().map(|arg| arg.as_ptr()).collect::<Vec<_>>();
+        argv.push(ptr::null());
+
+        Ok(unsafe { main(args.len() as i32, argv.as_ptr()) })
+    }
+
+    /// Compiles `program` to each of `output_types`, returning the bytes of
+    /// the outputs in the same order. The module is only built once.
+    pub fn emit(
+        &self,
+        program: &mir::Program,
+        source_map: &SourceMap,
+        output_types: &[OutputType],
+    ) -> Result<Vec<Vec<u8>>, Diagnostic> {
+        let target_machine = self.target_machine()?;
+
+        let context = Context::create();
+        let cx = self.build_module(&context, program, source_map, &target_machine)?;
+
+        let mut outputs = Vec::with_capacity(output_types.len());
+        for &output_type in output_types {
+            let file_type = match output_type {
+                OutputType::LlvmIr => {
+                    outputs.push(cx.module.print_to_string().to_bytes().to_vec());
+                    continue;
+                }
+                OutputType::Assembly => FileType::Assembly,
+                OutputType::Object => FileType::Object,
+            };
+
+            let buffer = target_machine
+                .write_to_memory_buffer(&cx.module, file_type)
+                .map_err(|err| {
+                    Diagnostic::error(format!("failed to emit {}: {}", output_type, err))
+                        .with_code(ErrorCode::E0012)
+                })?;
+
+            outputs.push(buffer.as_slice().to_vec());
+        }
+
+        Ok(outputs)
+    }
+
+    /// Compiles `program` to a native object file at `path`.
+    pub fn write_object(
+        &self,
+        program: &mir::Program,
+        source_map: &SourceMap,
+        path: &Path,
+    ) -> Result<(), Diagnostic> {
+        let object = self
+            .emit(program, source_map, &[OutputType::Object])?
+            .remove(0);
+
+        fs::write(path, object).map_err(|err| {
+            Diagnostic::error(format!(
+                "failed to write object file '{}': {}",
+                path.display(),
+                err
+            ))
+            .with_code(ErrorCode::E0012)
+        })
+    }
+
+    /// Compiles `program` to an object file and links it into the executable `output`.
+    pub fn build_executable(
+        &self,
+        program: &mir::Program,
+        source_map: &SourceMap,
+        output: &Path,
+    ) -> Result<(), Diagnostic> {
+        if !self.is_host() {
+            let target = self.target.as_deref().unwrap_or_default();
+
+            return Err(Diagnostic::error(format!(
+                "cannot link executables for '{}', emit an object file with `--emit=obj` instead",
+                target
+            ))
+            .with_code(ErrorCode::E0012));
+        }
+
+        let file_name = output.file_stem().unwrap_or_default().to_string_lossy();
+        let object = temp_path(&file_name, "o");
+
+        self.write_object(program, source_map, &object)?;
+        let result = link(&[&object], output);
+        let _ = fs::remove_file(&object);
 
-        let result = unsafe { main(args.len() as i32, args.as_ptr() as _) };
-        println!("Result: {}", result);
+        result
     }
 }
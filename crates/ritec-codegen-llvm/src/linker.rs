@@ -0,0 +1,25 @@
+use std::{path::Path, process::Command};
+
+use ritec_error::{Diagnostic, ErrorCode};
+
+/// Links `objects` into the executable `output` using the system C compiler.
+pub fn link(objects: &[&Path], output: &Path) -> Result<(), Diagnostic> {
+    let mut command = Command::new("cc");
+    command.args(objects).arg("-o").arg(output);
+
+    let result = command.output().map_err(|err| {
+        Diagnostic::error(format!("failed to run linker `cc`: {}", err)).with_code(ErrorCode::E0012)
+    })?;
+
+    if !result.status.success() {
+        let stderr = String::from_utf8_lossy(&result.stderr);
+
+        return Err(
+            Diagnostic::error(format!("linking with `cc` failed: {}", result.status))
+                .with_code(ErrorCode::E0012)
+                .with_note(stderr.trim_end().to_string()),
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use inkwell::{module::Module, passes::PassManager, OptimizationLevel};
+use ritec_core::OptLevel;
+
+/// Returns the LLVM optimization level the target machine uses for `level`.
+pub const fn llvm_opt_level(level: OptLevel) -> OptimizationLevel {
+    match level {
+        OptLevel::O0 => OptimizationLevel::None,
+        OptLevel::O1 => OptimizationLevel::Less,
+        OptLevel::O2 | OptLevel::Os => OptimizationLevel::Default,
+        OptLevel::O3 => OptimizationLevel::Aggressive,
+    }
+}
+
+/// Runs the pass pipeline for `level` on `module`.
+pub fn optimize(level: OptLevel, module: &Module<'_>) {
+    if level == OptLevel::O0 {
+        return;
+    }
+
+    let passes = PassManager::create(());
+
+    // every mir local gets an alloca, promote them to registers first
+    passes.add_promote_memory_to_register_pass();
+    passes.add_instruction_combining_pass();
+    passes.add_cfg_simplification_pass();
+    passes.add_early_cse_pass();
+
+    let is_o2 = matches!(level, OptLevel::O2 | OptLevel::O3 | OptLevel::Os);
+
+    if is_o2 {
+        if level != OptLevel::Os {
+            passes.add_function_inlining_pass();
+        }
+
+        passes.add_ipsccp_pass();
+        passes.add_global_optimizer_pass();
+        passes.add_scalar_repl_aggregates_pass();
+        passes.add_instruction_combining_pass();
+        passes.add_reassociate_pass();
+        passes.add_gvn_pass();
+        passes.add_sccp_pass();
+        passes.add_dead_store_elimination_pass();
+        passes.add_aggressive_dce_pass();
+        passes.add_tail_call_elimination_pass();
+        passes.add_cfg_simplification_pass();
+    }
+
+    if level == OptLevel::O3 {
+        passes.add_loop_rotate_pass();
+        passes.add_licm_pass();
+        passes.add_ind_var_simplify_pass();
+        passes.add_loop_unroll_pass();
+        passes.add_loop_vectorize_pass();
+        passes.add_slp_vectorize_pass();
+        passes.add_instruction_combining_pass();
+        passes.add_cfg_simplification_pass();
+    }
+
+    if is_o2 {
+        passes.add_global_dce_pass();
+        passes.add_constant_merge_pass();
+    }
+
+    passes.run_on(module);
+}
+
+#[cfg(test)]
+mod tests {
+    use inkwell::context::Context;
+
+    use super::*;
+
+    #[test]
+    fn promotes_allocas() {
+        let context = Context::create();
+        let module = context.create_module("test");
+        let builder = context.create_builder();
+
+        let i32_type = context.i32_type();
+        let function = module.add_function("f", i32_type.fn_type(&[], false), None);
+        builder.position_at_end(context.append_basic_block(function, "entry"));
+
+        let local = builder.build_alloca(i32_type, "local");
+        builder.build_store(local, i32_type.const_int(6, false));
+        let value = builder.build_load(local, "value");
+        builder.build_return(Some(&value));
+
+        optimize(OptLevel::O0, &module);
+        assert!(module.print_to_string().to_string().contains("alloca"));
+
+        optimize(OptLevel::O1, &module);
+        let ir = module.print_to_string().to_string();
+        assert!(!ir.contains("alloca"));
+        assert!(ir.contains("ret i32 6"));
+    }
+}
@@ -0,0 +1,337 @@
+use std::collections::{HashMap, HashSet};
+
+use ritec_core::{FloatSize, Ident, Inline, Span, TargetLayout};
+use ritec_error::{Diagnostic, Emitter, ErrorCode};
+use ritec_hir as hir;
+use ritec_mir as mir;
+use ritec_mir_build::ProgramBuilder;
+use ritec_mir_interp::{decode_int, Interpreter};
+
+/// The maximum number of statements and terminators executed for a single
+/// constant.
+pub const STEP_LIMIT: u64 = 1_000_000;
+
+/// Why a constant couldn't be evaluated.
+enum Error {
+    Diagnostic(Diagnostic),
+    /// A constant that is used failed to evaluate, which has already been
+    /// reported.
+    Failed,
+}
+
+impl From<Diagnostic> for Error {
+    fn from(err: Diagnostic) -> Self {
+        Self::Diagnostic(err)
+    }
+}
+
+/// The items a constant uses, found by walking its body and the bodies of the
+/// functions it calls.
+#[derive(Default)]
+struct Uses {
+    /// The constants used, and where they are used.
+    consts: Vec<(hir::ConstId, Span)>,
+    /// The functions called, including the initializers of class fields.
+    functions: Vec<hir::FunctionId>,
+    classes: HashSet<hir::ClassId>,
+    /// Classes whose fields haven't been walked yet.
+    pending: Vec<hir::ClassId>,
+}
+
+impl Uses {
+    fn add_function(&mut self, function: hir::FunctionId) {
+        if !self.functions.contains(&function) {
+            self.functions.push(function);
+        }
+    }
+
+    fn add_class(&mut self, class: hir::ClassId) {
+        if self.classes.insert(class) {
+            self.pending.push(class);
+        }
+    }
+
+    /// Adds the lengths and classes used by `ty`, not by the types in it.
+    fn add_type(&mut self, ty: &hir::Type) {
+        match ty {
+            hir::Type::Array(array) => self.consts.push((array.length, array.span)),
+            hir::Type::Class(class) => self.add_class(class.class),
+            _ => {}
+        }
+    }
+}
+
+/// Evaluates constants and the lengths of array types at compile time, by
+/// building them to MIR and running them in the [`Interpreter`].
+pub struct ConstEvaluator<'a> {
+    pub program: &'a mut hir::Program,
+    /// The target constants are evaluated for.
+    pub target: TargetLayout,
+    pub emitter: &'a mut dyn Emitter,
+    /// The values of evaluated constants, `None` if evaluation failed.
+    values: HashMap<hir::ConstId, Option<hir::ConstValue>>,
+    /// The constants being evaluated, used to detect cycles.
+    evaluating: Vec<hir::ConstId>,
+}
+
+impl<'a> ConstEvaluator<'a> {
+    pub fn new(
+        program: &'a mut hir::Program,
+        target: TargetLayout,
+        emitter: &'a mut dyn Emitter,
+    ) -> Self {
+        Self {
+            program,
+            target,
+            emitter,
+            values: HashMap::new(),
+            evaluating: Vec::new(),
+        }
+    }
+
+    /// Returns the value of `constant`, evaluating it if it hasn't been
+    /// already, and stores it in the program. Errors are emitted once, after
+    /// which `None` is returned.
+    pub fn evaluate(&mut self, constant: hir::ConstId) -> Option<hir::ConstValue> {
+        if let Some(&value) = self.values.get(&constant) {
+            return value;
+        }
+
+        self.evaluating.push(constant);
+
+        let value = match self.evaluate_const(constant) {
+            Ok(value) => Some(value),
+            Err(Error::Diagnostic(err)) => {
+                self.emitter.emit(err);
+                None
+            }
+            Err(Error::Failed) => None,
+        };
+
+        self.evaluating.pop();
+
+        if let Some(value) = value {
+            self.store(constant, value);
+        }
+
+        self.values.insert(constant, value);
+        value
+    }
+
+    fn evaluate_const(&mut self, id: hir::ConstId) -> Result<hir::ConstValue, Error> {
+        let uses = self.uses(id)?;
+
+        for &(constant, span) in &uses.consts {
+            if self.evaluating.contains(&constant) {
+                return Err(self.cycle(constant, span).into());
+            }
+
+            if self.evaluate(constant).is_none() {
+                return Err(Error::Failed);
+            }
+        }
+
+        let (mir, function) = self.build(id, &uses)?;
+        Ok(self.run(id, &mir, function)?)
+    }
+
+    /// Stores the value of `constant` in the program, and in the array types
+    /// it is the length of.
+    fn store(&mut self, constant: hir::ConstId, value: hir::ConstValue) {
+        self.program[constant].value = Some(value);
+
+        let hir::ConstValue::Int(length) = value else {
+            return;
+        };
+
+        if self.program[constant].ident.is_none() {
+            self.program.visit_types_mut(&mut |ty| {
+                if let hir::Type::Array(array_type) = ty {
+                    if array_type.length == constant {
+                        array_type.size = length as usize;
+                    }
+                }
+            });
+        }
+    }
+
+    fn cycle(&self, constant: hir::ConstId, span: Span) -> Diagnostic {
+        let constant = &self.program[constant];
+
+        Diagnostic::error(format!(
+            "cycle detected when evaluating {}",
+            constant.describe()
+        ))
+        .with_code(ErrorCode::E0019)
+        .with_msg_span("used here while it is being evaluated", span)
+        .with_secondary("defined here", constant.span)
+    }
+
+    /// Returns what `constant` uses, checking that it only calls `const fn`s.
+    fn uses(&self, constant: hir::ConstId) -> Result<Uses, Diagnostic> {
+        let program = &*self.program;
+
+        let mut uses = Uses::default();
+        self.body_uses(&program[constant].body, &mut uses)?;
+
+        let mut walked = 0;
+        loop {
+            if let Some(class) = uses.pending.pop() {
+                for field in program[class].fields.values() {
+                    field.ty.visit(&mut |ty| uses.add_type(ty));
+
+                    if let Some(init) = field.init {
+                        uses.add_function(init);
+                    }
+                }
+            } else if let Some(&function) = uses.functions.get(walked) {
+                walked += 1;
+
+                let function = &program[function];
+                (function.return_type).visit(&mut |ty| uses.add_type(ty));
+                self.body_uses(&function.body, &mut uses)?;
+            } else {
+                return Ok(uses);
+            }
+        }
+    }
+
+    fn body_uses(&self, body: &hir::Body, uses: &mut Uses) -> Result<(), Diagnostic> {
+        body.visit_types(&mut |ty| uses.add_type(ty));
+
+        for expr in body.exprs.values() {
+            match expr {
+                hir::Expr::Const(expr) => uses.consts.push((expr.constant, expr.span)),
+                hir::Expr::ClassInit(expr) => uses.add_class(expr.class.class),
+                hir::Expr::Function(expr) => {
+                    let function = &self.program[expr.instance.function];
+
+                    if !function.is_const {
+                        let mut err = Diagnostic::error(format!(
+                            "cannot call non-const function `{}`",
+                            function.ident
+                        ))
+                        .with_code(ErrorCode::E0019)
+                        .with_msg_span("not a `const fn`", expr.span);
+
+                        if !function.span.is_dummy() {
+                            err = err.with_secondary("defined here", function.ident.span());
+                        }
+
+                        return Err(err);
+                    }
+
+                    uses.add_function(expr.instance.function);
+                }
+                hir::Expr::MethodCall(expr) => {
+                    let err = Diagnostic::error(format!(
+                        "cannot call non-const method `{}`",
+                        expr.method
+                    ))
+                    .with_code(ErrorCode::E0019)
+                    .with_msg_span("methods can't be `const fn`s", expr.span);
+
+                    return Err(err);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Builds the MIR of `constant` as a function returning its value, and of
+    /// the functions it uses. Returns the program and the function.
+    fn build(
+        &self,
+        constant: hir::ConstId,
+        uses: &Uses,
+    ) -> Result<(mir::Program, hir::FunctionId), Diagnostic> {
+        let program = &*self.program;
+        let constant = &program[constant];
+
+        let ident = match constant.ident {
+            Some(ref ident) => ident.clone(),
+            None => Ident::new("length", constant.span),
+        };
+
+        let function = hir::Function {
+            ident,
+            generics: hir::Generics::new(Vec::new(), constant.span),
+            arguments: Vec::new(),
+            return_type: constant.ty.clone(),
+            body: constant.body.clone(),
+            inline: Inline::Auto,
+            export: false,
+            is_const: true,
+            span: constant.span,
+        };
+
+        let mut builder = ProgramBuilder::new(program);
+        // constants always check overflow, like the program does with checks
+        builder.checks = true;
+
+        for (id, class) in program.classes.iter() {
+            builder.build_class(id, class)?;
+        }
+
+        // warnings are reported when the program itself is built
+        let mut warnings = Vec::new();
+        for &id in &uses.functions {
+            builder.build_function(id, &program[id], &mut warnings)?;
+        }
+
+        let id = program.functions.next_id();
+        builder.build_function(id, &function, &mut warnings)?;
+
+        let mut mir = builder.mir;
+        mir.set_class_paths();
+
+        Ok((mir, id))
+    }
+
+    /// Runs `function`, which returns the value of `constant`.
+    fn run(
+        &self,
+        constant: hir::ConstId,
+        program: &mir::Program,
+        function: hir::FunctionId,
+    ) -> Result<hir::ConstValue, Diagnostic> {
+        let constant = &self.program[constant];
+
+        let mut interpreter = Interpreter::with_target(program, self.target);
+        interpreter.step_limit = Some(STEP_LIMIT);
+
+        let function: mir::FunctionId = function.cast();
+        let instance = mir::Instance::new(function, Vec::new());
+        let bytes = interpreter.call(instance, Vec::new()).map_err(|err| {
+            err.with_code(ErrorCode::E0019)
+                .with_note(format!("while evaluating {}", constant.describe()))
+        })?;
+
+        Ok(match program[function].return_type {
+            mir::Type::Bool => hir::ConstValue::Bool(bytes[0] != 0),
+            mir::Type::Int(ref ty) => hir::ConstValue::Int(decode_int(&bytes, ty.signed)),
+            mir::Type::Float(ref ty) => match ty.size {
+                FloatSize::F32 => {
+                    hir::ConstValue::Float(f32::from_le_bytes(bytes.try_into().unwrap()) as f64)
+                }
+                FloatSize::F64 => {
+                    hir::ConstValue::Float(f64::from_le_bytes(bytes.try_into().unwrap()))
+                }
+                FloatSize::F16 => unreachable!("the interpreter doesn't support `f16`"),
+            },
+            mir::Type::Pointer(_) => {
+                if decode_int(&bytes, false) != 0 {
+                    let err = Diagnostic::error("constant pointers must be null")
+                        .with_code(ErrorCode::E0019)
+                        .with_msg_span("this pointer isn't null", constant.span);
+                    return Err(err);
+                }
+
+                hir::ConstValue::Null
+            }
+            ref ty => unreachable!("constants can't have the type `{}`", ty),
+        })
+    }
+}
@@ -0,0 +1,34 @@
+//! Evaluates constants at compile time.
+//!
+//! The value of a `const` item or array length is built to MIR like the body
+//! of a function returning it, together with the `const fn`s it calls, and
+//! run by the MIR interpreter with the [`TargetLayout`] of the target, so
+//! constants follow the same rules as the program at runtime. Constants are
+//! evaluated before the constants using them.
+
+mod evaluator;
+
+pub use evaluator::*;
+
+use ritec_core::TargetLayout;
+use ritec_error::Emitter;
+use ritec_hir as hir;
+
+/// Evaluates every constant in `program`, storing their values and the
+/// lengths of array types. Errors are emitted to `emitter`, returns `false`
+/// if there were any.
+pub fn evaluate_program(
+    program: &mut hir::Program,
+    target: TargetLayout,
+    emitter: &mut dyn Emitter,
+) -> bool {
+    let constants: Vec<_> = program.consts.keys().collect();
+    let mut evaluator = ConstEvaluator::new(program, target, emitter);
+
+    let mut evaluated = true;
+    for id in constants {
+        evaluated &= evaluator.evaluate(id).is_some();
+    }
+
+    evaluated
+}
@@ -137,12 +137,16 @@ impl<T> Arena<T> {
         }
     }
 
-    /// Inserts an `item` into the arena at the given `id`.
+    /// Inserts an `item` into the arena at the given `id`, the ids skipped
+    /// to get there are free for reuse.
     pub fn insert(&mut self, id: Id<T>, item: T) -> Option<T> {
         if id.index >= self.arena.len() {
+            self.free.extend(self.arena.len()..id.index);
             self.arena.resize_with(id.index + 1, || None);
         }
 
+        self.free.retain(|&index| index != id.index);
+
         let slot = self.arena.get_mut(id.index)?;
         slot.replace(item)
     }
@@ -347,7 +351,7 @@ impl<T: Debug> Debug for Arena<T> {
 
 #[cfg(test)]
 mod tests {
-    use super::Arena;
+    use super::{Arena, Id};
 
     #[test]
     fn insert() {
@@ -359,6 +363,19 @@ mod tests {
         assert_eq!(arena[id], 1);
     }
 
+    #[test]
+    fn insert_past_end() {
+        let mut arena = Arena::new();
+
+        arena.insert(Id::from_raw_index(2), 2);
+        assert_eq!(arena.len(), 1);
+        assert_eq!(arena.keys().collect::<Vec<_>>(), [Id::from_raw_index(2)]);
+
+        let id = arena.push(0);
+        assert!(id.as_raw_index() < 2);
+        assert_eq!(arena.len(), 2);
+    }
+
     #[test]
     fn push() {
         let mut arena = Arena::new();
@@ -0,0 +1,21 @@
+/// Whether calls to a function are inlined, set with the `#[inline]` and
+/// `#[inline(never)]` attributes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Inline {
+    /// Left to the size of the function.
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl Inline {
+    /// Returns the attribute setting `self`, `None` for [`Inline::Auto`].
+    pub const fn attribute(self) -> Option<&'static str> {
+        match self {
+            Self::Auto => None,
+            Self::Always => Some("#[inline]"),
+            Self::Never => Some("#[inline(never)]"),
+        }
+    }
+}
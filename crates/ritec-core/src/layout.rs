@@ -0,0 +1,161 @@
+use std::mem;
+
+use crate::{FloatSize, IntSize};
+
+/// The size, alignment and field offsets of a type, laid out like a C struct.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Layout {
+    pub size: u64,
+    pub align: u64,
+    /// The offsets of the fields of tuples and classes.
+    pub fields: Vec<u64>,
+}
+
+impl Layout {
+    pub const VOID: Self = Self {
+        size: 0,
+        align: 1,
+        fields: Vec::new(),
+    };
+
+    /// Appends a field with `layout` to a struct, returning its offset.
+    pub fn push_field(&mut self, layout: &Layout) -> u64 {
+        let offset = align_to(self.size, layout.align);
+        self.fields.push(offset);
+        self.size = offset + layout.size;
+        self.align = self.align.max(layout.align);
+        offset
+    }
+
+    /// Returns the layout of an array of `length` elements of this layout, or
+    /// `None` if its size overflows. Element `i` is at `i * self.size`.
+    pub fn array(&self, length: u64) -> Option<Self> {
+        Some(Self {
+            size: self.size.checked_mul(length)?,
+            align: self.align,
+            fields: Vec::new(),
+        })
+    }
+
+    /// Pads the size of a struct to a multiple of its alignment.
+    pub fn finish(mut self) -> Self {
+        self.size = align_to(self.size, self.align);
+        self
+    }
+}
+
+/// The properties of a target that the layout of types depends on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TargetLayout {
+    /// The size of pointers, `usize` and `isize` in bytes.
+    pub pointer_size: u64,
+    pub pointer_align: u64,
+    /// The alignments of `i8`, `i16`, `i32`, `i64` and `i128`.
+    pub int_aligns: [u64; 5],
+    /// The alignments of `f16`, `f32` and `f64`.
+    pub float_aligns: [u64; 3],
+}
+
+impl TargetLayout {
+    /// The layout of the target the compiler itself runs on, which is the
+    /// target of the C backend.
+    pub const HOST: Self = Self {
+        pointer_size: mem::size_of::<usize>() as u64,
+        pointer_align: mem::align_of::<usize>() as u64,
+        int_aligns: [
+            mem::align_of::<i8>() as u64,
+            mem::align_of::<i16>() as u64,
+            mem::align_of::<i32>() as u64,
+            mem::align_of::<i64>() as u64,
+            mem::align_of::<i128>() as u64,
+        ],
+        float_aligns: [
+            2,
+            mem::align_of::<f32>() as u64,
+            mem::align_of::<f64>() as u64,
+        ],
+    };
+
+    pub const fn bool(&self) -> Layout {
+        Layout {
+            size: 1,
+            align: 1,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Returns the layout of an integer of `size`, `None` for `usize` and
+    /// `isize`, which are laid out like the integer as large as a pointer.
+    pub const fn int(&self, size: Option<IntSize>) -> Layout {
+        let size = match size {
+            Some(size) => size,
+            None => match IntSize::from_byte_size(self.pointer_size as usize) {
+                Some(size) => size,
+                None => return self.pointer(),
+            },
+        };
+
+        let index = match size {
+            IntSize::I8 => 0,
+            IntSize::I16 => 1,
+            IntSize::I32 => 2,
+            IntSize::I64 => 3,
+            IntSize::I128 => 4,
+        };
+
+        Layout {
+            size: size.byte_size() as u64,
+            align: self.int_aligns[index],
+            fields: Vec::new(),
+        }
+    }
+
+    pub const fn float(&self, size: FloatSize) -> Layout {
+        let index = match size {
+            FloatSize::F16 => 0,
+            FloatSize::F32 => 1,
+            FloatSize::F64 => 2,
+        };
+
+        Layout {
+            size: size.byte_size() as u64,
+            align: self.float_aligns[index],
+            fields: Vec::new(),
+        }
+    }
+
+    pub const fn pointer(&self) -> Layout {
+        Layout {
+            size: self.pointer_size,
+            align: self.pointer_align,
+            fields: Vec::new(),
+        }
+    }
+}
+
+pub const fn align_to(offset: u64, align: u64) -> u64 {
+    offset.div_ceil(align) * align
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usize_is_laid_out_like_an_int() {
+        let target = TargetLayout {
+            pointer_size: 4,
+            pointer_align: 4,
+            int_aligns: [1, 2, 4, 8, 8],
+            float_aligns: [2, 4, 8],
+        };
+
+        assert_eq!(target.int(None), target.int(Some(IntSize::I32)));
+        assert_eq!(target.int(Some(IntSize::I64)).align, 8);
+        assert_eq!(target.float(FloatSize::F64).size, 8);
+
+        let array = target.int(Some(IntSize::I16)).array(3).unwrap();
+        assert_eq!((array.size, array.align), (6, 2));
+        assert!(target.int(None).array(u64::MAX).is_none());
+    }
+}
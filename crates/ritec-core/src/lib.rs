@@ -2,20 +2,28 @@ mod arena;
 mod float;
 mod generic;
 mod ident;
+mod inline;
 mod integer;
+mod layout;
 mod literal;
 mod operator;
+mod opt_level;
 mod source_map;
 mod span;
+mod temp;
 
 pub use arena::*;
 pub use float::*;
 pub use generic::*;
 pub use ident::*;
+pub use inline::*;
 pub use integer::*;
+pub use layout::*;
 pub use literal::*;
 pub use operator::*;
+pub use opt_level::*;
 pub use source_map::*;
 pub use span::*;
+pub use temp::*;
 
 pub use tracing::{debug, error, info, trace, warn};
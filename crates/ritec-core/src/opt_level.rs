@@ -0,0 +1,58 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// How much a program is optimized, set with `-O`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum OptLevel {
+    #[default]
+    O0,
+    O1,
+    O2,
+    O3,
+    /// Optimize for size.
+    Os,
+}
+
+impl Display for OptLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::O0 => write!(f, "0"),
+            Self::O1 => write!(f, "1"),
+            Self::O2 => write!(f, "2"),
+            Self::O3 => write!(f, "3"),
+            Self::Os => write!(f, "s"),
+        }
+    }
+}
+
+impl FromStr for OptLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "0" => Ok(Self::O0),
+            "1" => Ok(Self::O1),
+            "2" => Ok(Self::O2),
+            "3" => Ok(Self::O3),
+            "s" => Ok(Self::Os),
+            _ => Err(format!(
+                "unknown optimization level `{}`, expected 0, 1, 2, 3 or s",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!("2".parse(), Ok(OptLevel::O2));
+        assert_eq!("s".parse(), Ok(OptLevel::Os));
+        assert!("4".parse::<OptLevel>().is_err());
+    }
+}
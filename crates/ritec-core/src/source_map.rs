@@ -1,13 +1,76 @@
-use std::ops::Deref;
+use std::{
+    ops::Deref,
+    path::{Path, PathBuf},
+};
 
-use crate::{Arena, Id};
+use crate::{Arena, Id, Span};
 
 pub type FileId = Id<SourceFile>;
 
 #[derive(Clone, Debug)]
 pub struct SourceFile {
-    pub path: String,
+    pub path: PathBuf,
     pub text: String,
+    /// Byte offsets of the start of every line in `text`.
+    line_starts: Vec<usize>,
+}
+
+impl SourceFile {
+    pub fn new(path: impl Into<PathBuf>, text: impl Into<String>) -> Self {
+        let text = text.into();
+
+        let mut line_starts = vec![0];
+        for (i, c) in text.char_indices() {
+            if c == '\n' {
+                line_starts.push(i + 1);
+            }
+        }
+
+        Self {
+            path: path.into(),
+            text,
+            line_starts,
+        }
+    }
+
+    pub fn line_starts(&self) -> &[usize] {
+        &self.line_starts
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Returns the zero-based line containing the byte `offset`.
+    pub fn line_index(&self, offset: usize) -> usize {
+        match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        }
+    }
+
+    /// Returns the one-based `(line, column)` of the byte `offset`, columns
+    /// are counted in characters.
+    pub fn position(&self, offset: usize) -> (usize, usize) {
+        let offset = offset.min(self.text.len());
+        let line = self.line_index(offset);
+        let start = self.line_starts[line];
+        let column = self.text[start..offset].chars().count();
+
+        (line + 1, column + 1)
+    }
+
+    /// Returns the text of the one-based `line` without its line ending.
+    pub fn line_text(&self, line: usize) -> Option<&str> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let end = match self.line_starts.get(line) {
+            Some(&end) => end,
+            None => self.text.len(),
+        };
+
+        let text = &self.text[start..end];
+        Some(text.trim_end_matches('\n').trim_end_matches('\r'))
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -22,13 +85,34 @@ impl SourceMap {
         }
     }
 
-    pub fn contains_path(&self, path: &str) -> bool {
+    pub fn contains_path(&self, path: &Path) -> bool {
         self.arena.values().any(|file| file.path == path)
     }
 
     pub fn insert(&mut self, source_file: SourceFile) -> FileId {
         self.arena.push(source_file)
     }
+
+    /// Returns the file and one-based `(line, column)` of the start of `span`.
+    pub fn lookup(&self, span: Span) -> Option<(FileId, usize, usize)> {
+        let file = span.file?;
+        let (line, column) = self.arena.get(file)?.position(span.lo);
+
+        Some((file, line, column))
+    }
+
+    /// Returns `path:line:column` of the start of `span`.
+    pub fn location(&self, span: Span) -> Option<String> {
+        let (file, line, column) = self.lookup(span)?;
+        let path = self.arena[file].path.display();
+
+        Some(format!("{}:{}:{}", path, line, column))
+    }
+
+    /// Returns the text of the one-based `line` in `file`.
+    pub fn line_text(&self, file: FileId, line: usize) -> Option<&str> {
+        self.arena.get(file)?.line_text(line)
+    }
 }
 
 impl Deref for SourceMap {
@@ -38,3 +122,42 @@ impl Deref for SourceMap {
         &self.arena
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup() {
+        let mut source_map = SourceMap::new();
+        let file = source_map.insert(SourceFile::new("main.ri", "fn main() {\n\tfoo;\n}"));
+
+        assert_eq!(source_map.lookup(Span::new(0, 2, file)), Some((file, 1, 1)));
+        assert_eq!(
+            source_map.lookup(Span::new(11, 12, file)),
+            Some((file, 1, 12))
+        );
+        assert_eq!(
+            source_map.lookup(Span::new(13, 16, file)),
+            Some((file, 2, 2))
+        );
+        assert_eq!(
+            source_map.lookup(Span::new(18, 19, file)),
+            Some((file, 3, 1))
+        );
+        assert_eq!(source_map.lookup(Span::DUMMY), None);
+    }
+
+    #[test]
+    fn line_text() {
+        let mut source_map = SourceMap::new();
+        let file = source_map.insert(SourceFile::new("main.ri", "a\r\nb\n\nc"));
+
+        assert_eq!(source_map.line_text(file, 0), None);
+        assert_eq!(source_map.line_text(file, 1), Some("a"));
+        assert_eq!(source_map.line_text(file, 2), Some("b"));
+        assert_eq!(source_map.line_text(file, 3), Some(""));
+        assert_eq!(source_map.line_text(file, 4), Some("c"));
+        assert_eq!(source_map.line_text(file, 5), None);
+    }
+}
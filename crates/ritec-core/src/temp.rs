@@ -0,0 +1,38 @@
+use std::{
+    env,
+    path::PathBuf,
+    process,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Returns a path in the temporary directory starting with `stem` and ending
+/// in `extension`, if it isn't empty. Every call returns a different path, so
+/// builds running in parallel, even in one process, don't share files.
+pub fn temp_path(stem: &str, extension: &str) -> PathBuf {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+
+    let id = NEXT.fetch_add(1, Ordering::Relaxed);
+    let mut name = format!("{}-{}-{}", stem, process::id(), id);
+
+    if !extension.is_empty() {
+        name.push('.');
+        name.push_str(extension);
+    }
+
+    env::temp_dir().join(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_are_unique() {
+        let first = temp_path("main", "c");
+        let second = temp_path("main", "c");
+
+        assert_ne!(first, second);
+        assert_eq!(first.extension().unwrap(), "c");
+        assert_eq!(temp_path("rite", "").extension(), None);
+    }
+}
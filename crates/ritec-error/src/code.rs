@@ -0,0 +1,300 @@
+use std::fmt::{self, Display};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ErrorCode(u16);
+
+impl ErrorCode {
+    pub const fn as_u16(&self) -> u16 {
+        self.0
+    }
+
+    /// Finds an error code from its textual form, e.g. `E0001`.
+    pub fn find(code: &str) -> Option<Self> {
+        let code = code.strip_prefix('E')?.parse::<u16>().ok()?;
+        Self::ALL.iter().copied().find(|error| error.0 == code)
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "E{:04}", self.0)
+    }
+}
+
+macro_rules! error_codes {
+    ($($code:ident = $value:literal => $explanation:literal,)*) => {
+        impl ErrorCode {
+            $(
+                pub const $code: Self = Self($value);
+            )*
+
+            pub const ALL: &'static [Self] = &[$(Self::$code),*];
+
+            /// Returns the long description shown by `--explain`.
+            pub const fn explanation(&self) -> &'static str {
+                match self.0 {
+                    $($value => $explanation,)*
+                    _ => "",
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    E0001 = 1 => "\
+A source file could not be read.
+
+The compiler reads the root file passed on the command line, and one file per
+`mod` item, named after the module and placed next to the parent file.
+
+```
+mod vec; // reads `vec.ri`
+```
+
+Check that the file exists and is readable.
+",
+    E0002 = 2 => "\
+The parser found a token it did not expect.
+
+```
+fn main() {
+    let x = 1 // error: expected `;`
+}
+```
+
+Statements that are not blocks must end in a `;`.
+",
+    E0003 = 3 => "\
+The lexer found a character that is not part of the language, or a delimiter
+that is never closed.
+
+```
+fn main() {
+    let x = 1 $ 2; // error: unexpected character
+```
+
+Remove the character, or close the delimiter.
+",
+    E0004 = 4 => "\
+A name could not be resolved.
+
+```
+fn main() {
+    let x = y; // error: 'y' not defined
+}
+```
+
+Check the spelling of the name and that the item, module or variable is in
+scope at the point where it is used.
+",
+    E0005 = 5 => "\
+An item was given the wrong number of generic arguments.
+
+```
+fn foo<T>(value: T) {}
+
+fn main() {
+    foo<i32, i32>(1); // error: expected 1 generic arguments, found 2
+}
+```
+",
+    E0006 = 6 => "\
+The type of a function argument or return type could not be inferred.
+
+```
+fn foo(value: _) {} // error: cannot infer type of function argument
+```
+
+Function signatures must be fully annotated.
+",
+    E0007 = 7 => "\
+Two types that are required to be equal are not.
+
+```
+fn main() {
+    let x: i32 = true; // error: cannot unify types
+}
+```
+",
+    E0008 = 8 => "\
+A field was accessed that the class does not declare.
+
+```
+class Foo {
+    bar: i32,
+}
+
+fn main() {
+    let foo = Foo { bar: 0 };
+    foo.baz; // error: field not found
+}
+```
+",
+    E0009 = 9 => "\
+A method was called that the class does not declare, or on a type that is not a
+class.
+
+```
+class Foo {}
+
+fn main() {
+    let foo = Foo {};
+    foo.bar(); // error: invalid method access
+}
+```
+",
+    E0010 = 10 => "\
+An `as` cast was used between types that cannot be converted.
+
+Only casts between integers, floats and pointers are allowed.
+
+```
+fn main() {
+    let x = true as i32; // error: invalid type cast
+}
+```
+",
+    E0011 = 11 => "\
+A class was expected, but something else was found.
+
+```
+fn main() {
+    let x = i32 {}; // error: 'i32' is not a class
+}
+```
+",
+    E0012 = 12 => "\
+The compiled program could not be written to disk or linked.
+
+Executables are linked by invoking the system C compiler, `cc`, which must be
+installed and on the `PATH`. The note attached to the error contains the output
+of the linker.
+",
+    E0013 = 13 => "\
+A generic function instantiates itself with ever growing generic arguments,
+so code would have to be generated for infinitely many instances of it.
+
+```
+fn wrap<T>(value: T, depth: i32) -> i32 {
+    if depth == 0 {
+        return 0;
+    }
+
+    return wrap<*T>(&value, depth - 1); // error: `wrap<*T>` needs `wrap<**T>`...
+}
+```
+
+Generic recursion is only allowed if the generic arguments stop changing.
+",
+    E0014 = 14 => "\
+The MIR interpreter stopped because the program did something that has no
+defined behavior, like dereferencing a null or dangling pointer, accessing
+memory outside of an allocation, using memory after freeing it, or dividing by
+zero.
+
+```
+fn main(argc: i32, argv: **i8) -> i32 {
+    let ptr: *i32 = malloc(1);
+    free(ptr);
+
+    return *ptr; // error: use of heap memory after it was freed
+}
+```
+
+The error names the function and block that was executing, and the functions
+that called it.
+",
+    E0015 = 15 => "\
+An attribute is unknown or malformed.
+
+Functions and methods accept `#[inline]`, which asks for every call to the
+function to be inlined, and `#[inline(never)]`, which prevents it. Each
+function may have at most one of them. `#[export]` compiles a function even if
+`main` never calls it, it takes no arguments and generic functions can't be
+exported.
+
+```
+#[inline(sometimes)] // error: malformed `inline` attribute
+fn add(a: i32, b: i32) -> i32 {
+    return a + b;
+}
+```
+",
+    E0016 = 16 => "\
+A MIR file could not be parsed.
+
+Files ending in `.mir` are read as the textual form of MIR, the format printed
+by `--emit=mir`, instead of Rite source code. Every local must be declared with
+its type and every constant must carry its type.
+
+```
+fn main(_0: i32, _1: **i8) -> i32 {
+    let _0: i32;
+    let _1: **i8;
+
+    bb0: {
+        return 0 // error: invalid literal `0`, expected e.g. `0i32`
+    }
+}
+```
+",
+    E0017 = 17 => "\
+A function that returns a value can reach the end of its body without
+returning one.
+
+```
+fn sign(x: i32) -> i32 {
+    if x < 0 {
+        return -1;
+    }
+
+    if x > 0 {
+        return 1;
+    }
+} // error: not all paths return a value
+```
+
+Every path through the function must end in a `return`, or in a `loop` that
+is never left. Functions returning `void` return implicitly at the end.
+",
+    E0018 = 18 => "\
+A runtime check failed while interpreting the program.
+
+With `--checks`, which is on by default when compiling without optimizations,
+arithmetic that overflows, division by zero and dereferencing a null pointer
+stop the program instead of wrapping around or being undefined.
+
+```
+fn main(argc: i32, argv: **i8) -> i32 {
+    let x: i8 = 127;
+    return (x + argc as i8) as i32; // error: attempt to add with overflow
+}
+```
+
+Compiled programs print the same message with the location of the check and
+abort. Use `wrapping_add`, `wrapping_sub` or `wrapping_mul` for arithmetic
+that is meant to wrap around.
+",
+    E0019 = 19 => "\
+A constant could not be evaluated at compile time.
+
+The values of `const` items and the lengths of array types are computed while
+compiling, by running them in the MIR interpreter. They may use other
+constants and call `const fn`s, like `sizeof` and `alignof`, but no other
+functions.
+
+```
+fn answer() -> usize {
+    return 42;
+}
+
+const SIZE: usize = answer(); // error: cannot call non-const function `answer`
+```
+
+Marking `answer` as a `const fn` allows it to be called in constants, as long
+as it only calls `const fn`s itself. Constants are always run with the
+runtime checks of `--checks`, so arithmetic that overflows and division by
+zero are errors, as are constants that refer to themselves.
+",
+}
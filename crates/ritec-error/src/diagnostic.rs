@@ -2,17 +2,34 @@ use std::panic::Location;
 
 use ritec_core::Span;
 
+use crate::{ErrorCode, Suggestion};
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum DiagnosticLevel {
     Error,
     Warning,
     Note,
+    Help,
+}
+
+impl DiagnosticLevel {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Note => "note",
+            Self::Help => "help",
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct DiagnosticHint {
     pub message: Option<String>,
     pub span: Option<Span>,
+    /// Primary hints point at the cause of the diagnostic, secondary hints
+    /// give context.
+    pub primary: bool,
 }
 
 impl DiagnosticHint {
@@ -20,9 +37,15 @@ impl DiagnosticHint {
         Self {
             message: None,
             span: None,
+            primary: true,
         }
     }
 
+    pub fn secondary(mut self) -> Self {
+        self.primary = false;
+        self
+    }
+
     pub fn with_message(mut self, message: impl Into<String>) -> Self {
         self.message = Some(message.into());
         self
@@ -45,11 +68,27 @@ impl From<String> for DiagnosticHint {
     }
 }
 
+impl Default for DiagnosticHint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A `note:` or `help:` attached to a [`Diagnostic`].
+#[derive(Clone, Debug)]
+pub struct SubDiagnostic {
+    pub level: DiagnosticLevel,
+    pub message: String,
+}
+
 #[derive(Clone, Debug)]
 pub struct Diagnostic {
     pub title: String,
     pub level: DiagnosticLevel,
+    pub code: Option<ErrorCode>,
     pub messages: Vec<DiagnosticHint>,
+    pub children: Vec<SubDiagnostic>,
+    pub suggestions: Vec<Suggestion>,
     pub location: &'static Location<'static>,
 }
 
@@ -59,7 +98,10 @@ impl Diagnostic {
         Self {
             title: title.into(),
             level,
+            code: None,
             messages: Vec::new(),
+            children: Vec::new(),
+            suggestions: Vec::new(),
             location: Location::caller(),
         }
     }
@@ -108,4 +150,69 @@ impl Diagnostic {
         self.add_msg_span(message, span);
         self
     }
+
+    pub fn set_code(&mut self, code: ErrorCode) -> &mut Self {
+        self.code = Some(code);
+        self
+    }
+
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        self.set_code(code);
+        self
+    }
+
+    pub fn add_secondary(&mut self, message: impl Into<String>, span: Span) -> &mut Self {
+        self.add_msg(
+            DiagnosticHint::new()
+                .with_message(message)
+                .with_msg_span(span)
+                .secondary(),
+        );
+        self
+    }
+
+    pub fn with_secondary(mut self, message: impl Into<String>, span: Span) -> Self {
+        self.add_secondary(message, span);
+        self
+    }
+
+    pub fn add_note(&mut self, message: impl Into<String>) -> &mut Self {
+        self.children.push(SubDiagnostic {
+            level: DiagnosticLevel::Note,
+            message: message.into(),
+        });
+        self
+    }
+
+    pub fn with_note(mut self, message: impl Into<String>) -> Self {
+        self.add_note(message);
+        self
+    }
+
+    pub fn add_help(&mut self, message: impl Into<String>) -> &mut Self {
+        self.children.push(SubDiagnostic {
+            level: DiagnosticLevel::Help,
+            message: message.into(),
+        });
+        self
+    }
+
+    pub fn with_help(mut self, message: impl Into<String>) -> Self {
+        self.add_help(message);
+        self
+    }
+
+    pub fn add_suggestion(&mut self, suggestion: Suggestion) -> &mut Self {
+        self.suggestions.push(suggestion);
+        self
+    }
+
+    pub fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.add_suggestion(suggestion);
+        self
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.level == DiagnosticLevel::Error
+    }
 }
@@ -0,0 +1,189 @@
+use std::io::prelude::*;
+
+use ritec_core::{SourceMap, Span};
+use serde_json::{json, Value};
+
+use crate::{Applicability, Diagnostic, DiagnosticLevel, Emitter, ErrorWriter};
+
+/// An [`Emitter`] writing each [`Diagnostic`] as a single line of JSON.
+pub struct JsonEmitter<'a> {
+    pub source_map: &'a SourceMap,
+    pub writer: &'a mut dyn Write,
+}
+
+impl<'a> JsonEmitter<'a> {
+    pub fn new(source_map: &'a SourceMap, writer: &'a mut dyn Write) -> Self {
+        Self { source_map, writer }
+    }
+
+    pub fn to_json(&self, diagnostic: &Diagnostic) -> Value {
+        let mut spans = Vec::new();
+        let mut children = Vec::new();
+
+        for hint in diagnostic.messages.iter() {
+            match hint.span {
+                Some(span) => {
+                    let mut span = self.span(span);
+                    span["is_primary"] = json!(hint.primary);
+                    span["label"] = json!(hint.message);
+                    spans.push(span);
+                }
+                None => {
+                    if let Some(ref message) = hint.message {
+                        children.push(json!({
+                            "level": DiagnosticLevel::Note.as_str(),
+                            "message": message,
+                        }));
+                    }
+                }
+            }
+        }
+
+        for child in diagnostic.children.iter() {
+            children.push(json!({
+                "level": child.level.as_str(),
+                "message": child.message,
+            }));
+        }
+
+        let mut suggestions = Vec::new();
+        for suggestion in diagnostic.suggestions.iter() {
+            let applicability = match suggestion.applicability {
+                Applicability::MachineApplicable => "machine-applicable",
+                Applicability::MaybeIncorrect => "maybe-incorrect",
+            };
+
+            suggestions.push(json!({
+                "message": suggestion.message,
+                "span": self.span(suggestion.span),
+                "replacement": suggestion.replacement,
+                "applicability": applicability,
+            }));
+        }
+
+        let mut rendered = Vec::new();
+        let writer = ErrorWriter::new(self.source_map).with_color(false);
+        writer.write(&mut rendered, diagnostic).unwrap();
+
+        json!({
+            "level": diagnostic.level.as_str(),
+            "code": diagnostic.code.map(|code| code.to_string()),
+            "message": diagnostic.title,
+            "spans": spans,
+            "children": children,
+            "suggestions": suggestions,
+            "rendered": String::from_utf8_lossy(&rendered),
+        })
+    }
+
+    fn span(&self, span: Span) -> Value {
+        let file = span.file.and_then(|file| self.source_map.get(file));
+
+        let Some(file) = file else {
+            return json!({
+                "file": null,
+                "byte_start": span.lo,
+                "byte_end": span.hi,
+            });
+        };
+
+        let (line_start, column_start) = file.position(span.lo);
+        let (line_end, column_end) = file.position(span.hi);
+
+        json!({
+            "file": file.path.display().to_string(),
+            "byte_start": span.lo,
+            "byte_end": span.hi,
+            "line_start": line_start,
+            "column_start": column_start,
+            "line_end": line_end,
+            "column_end": column_end,
+        })
+    }
+}
+
+impl Emitter for JsonEmitter<'_> {
+    fn emit(&mut self, diagnostic: Diagnostic) {
+        let json = self.to_json(&diagnostic);
+        writeln!(self.writer, "{}", json).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ritec_core::{SourceFile, SourceMap, Span};
+    use serde_json::{json, Value};
+
+    use crate::{Diagnostic, Emitter, ErrorCode, JsonEmitter, Suggestion};
+
+    #[test]
+    fn emit() {
+        let mut source_map = SourceMap::new();
+        let file = source_map.insert(SourceFile::new("main.ri", "let x = 1\nfoo\n"));
+
+        let diagnostic = Diagnostic::error("expected `;`")
+            .with_code(ErrorCode::E0002)
+            .with_msg_span("found `foo`", Span::new(10, 13, file))
+            .with_msg(String::from("statements end with `;`"))
+            .with_help("add a `;`")
+            .with_suggestion(Suggestion::machine_applicable(
+                "insert `;`",
+                Span::new(9, 9, file),
+                ";",
+            ));
+
+        let mut out = Vec::new();
+        let mut emitter = JsonEmitter::new(&source_map, &mut out);
+        emitter.emit(diagnostic.clone());
+        emitter.emit(diagnostic);
+
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+
+        let mut json: Value = serde_json::from_str(lines[0]).unwrap();
+        assert!(json["rendered"]
+            .as_str()
+            .unwrap()
+            .starts_with("error[E0002]"));
+        json["rendered"] = Value::Null;
+
+        let expected = json!({
+            "level": "error",
+            "code": "E0002",
+            "message": "expected `;`",
+            "spans": [{
+                "file": "main.ri",
+                "byte_start": 10,
+                "byte_end": 13,
+                "line_start": 2,
+                "column_start": 1,
+                "line_end": 2,
+                "column_end": 4,
+                "is_primary": true,
+                "label": "found `foo`",
+            }],
+            "children": [
+                { "level": "note", "message": "statements end with `;`" },
+                { "level": "help", "message": "add a `;`" },
+            ],
+            "suggestions": [{
+                "message": "insert `;`",
+                "span": {
+                    "file": "main.ri",
+                    "byte_start": 9,
+                    "byte_end": 9,
+                    "line_start": 1,
+                    "column_start": 10,
+                    "line_end": 1,
+                    "column_end": 10,
+                },
+                "replacement": ";",
+                "applicability": "machine-applicable",
+            }],
+            "rendered": null,
+        });
+
+        assert_eq!(json, expected);
+    }
+}
@@ -1,7 +1,13 @@
+mod code;
 mod diagnostic;
 mod emitter;
+mod json;
+mod suggestion;
 mod writer;
 
+pub use code::*;
 pub use diagnostic::*;
 pub use emitter::*;
+pub use json::*;
+pub use suggestion::*;
 pub use writer::*;
@@ -0,0 +1,159 @@
+use ritec_core::Span;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Applicability {
+    /// The suggestion is definitely what the user intended and can be applied
+    /// automatically.
+    MachineApplicable,
+    /// The suggestion may be what the user intended, but should be reviewed.
+    MaybeIncorrect,
+}
+
+/// A fix for a [`Diagnostic`](crate::Diagnostic), replacing the text at `span`
+/// with `replacement`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    pub message: String,
+    pub span: Span,
+    pub replacement: String,
+    pub applicability: Applicability,
+}
+
+impl Suggestion {
+    pub fn new(
+        message: impl Into<String>,
+        span: Span,
+        replacement: impl Into<String>,
+        applicability: Applicability,
+    ) -> Self {
+        Self {
+            message: message.into(),
+            span,
+            replacement: replacement.into(),
+            applicability,
+        }
+    }
+
+    pub fn machine_applicable(
+        message: impl Into<String>,
+        span: Span,
+        replacement: impl Into<String>,
+    ) -> Self {
+        Self::new(message, span, replacement, Applicability::MachineApplicable)
+    }
+
+    pub fn maybe_incorrect(
+        message: impl Into<String>,
+        span: Span,
+        replacement: impl Into<String>,
+    ) -> Self {
+        Self::new(message, span, replacement, Applicability::MaybeIncorrect)
+    }
+
+    pub fn is_insertion(&self) -> bool {
+        self.span.lo == self.span.hi
+    }
+}
+
+/// Applies every machine applicable suggestion in `suggestions` to `text`.
+///
+/// Suggestions are applied in order of their spans, suggestions overlapping an
+/// already applied suggestion are skipped.
+pub fn apply_suggestions<'a>(
+    text: &str,
+    suggestions: impl IntoIterator<Item = &'a Suggestion>,
+) -> String {
+    let mut suggestions: Vec<_> = suggestions
+        .into_iter()
+        .filter(|suggestion| suggestion.applicability == Applicability::MachineApplicable)
+        .collect();
+    suggestions.sort_by_key(|suggestion| (suggestion.span.lo, suggestion.span.hi));
+
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+
+    for suggestion in suggestions {
+        let Span { lo, hi, .. } = suggestion.span;
+
+        if lo < last || hi > text.len() {
+            continue;
+        }
+
+        out.push_str(&text[last..lo]);
+        out.push_str(&suggestion.replacement);
+        last = hi;
+    }
+
+    out.push_str(&text[last..]);
+    out
+}
+
+/// Returns the number of single character edits needed to turn `a` into `b`.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+
+        for (j, &b) in b.iter().enumerate() {
+            let substitute = previous + (a != b) as usize;
+            previous = row[j + 1];
+            row[j + 1] = substitute.min(row[j] + 1).min(previous + 1);
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Finds the candidate closest to `name`, if any is close enough to be a
+/// plausible typo.
+pub fn find_best_match<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let max_distance = usize::max(name.chars().count(), 3) / 3;
+
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use ritec_core::{FileId, Span};
+
+    use super::*;
+
+    fn span(lo: usize, hi: usize) -> Span {
+        Span::new(lo, hi, FileId::from_raw_index(0))
+    }
+
+    #[test]
+    fn apply() {
+        let text = "let x = 1\nv.psh(x);\n";
+
+        let suggestions = [
+            Suggestion::machine_applicable("insert `;`", span(9, 9), ";"),
+            Suggestion::machine_applicable("did you mean `push`", span(12, 15), "push"),
+            Suggestion::maybe_incorrect("remove this", span(16, 17), ""),
+        ];
+
+        let fixed = apply_suggestions(text, &suggestions);
+        assert_eq!(fixed, "let x = 1;\nv.push(x);\n");
+    }
+
+    #[test]
+    fn best_match() {
+        let candidates = ["push", "pop", "get", "len"];
+
+        assert_eq!(find_best_match("psh", candidates), Some("push"));
+        assert_eq!(find_best_match("lne", candidates), None);
+        assert_eq!(find_best_match("foo", candidates), None);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+}
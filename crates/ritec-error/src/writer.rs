@@ -1,19 +1,552 @@
-use std::io::{self, prelude::*};
+use std::{
+    io::{self, prelude::*},
+    path::Path,
+};
 
-use ritec_core::SourceMap;
+use ritec_core::{SourceFile, SourceMap, Span};
+use termion::{color, style};
 
-use crate::Diagnostic;
+use crate::{Diagnostic, DiagnosticHint, DiagnosticLevel, Suggestion};
+
+const TAB: &str = "    ";
 
 pub struct ErrorWriter<'a> {
     pub source_map: &'a SourceMap,
+    pub color: bool,
 }
 
 impl<'a> ErrorWriter<'a> {
     pub fn new(source_map: &'a SourceMap) -> Self {
-        Self { source_map }
+        Self {
+            source_map,
+            color: true,
+        }
+    }
+
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
     }
 
     pub fn write(&self, writer: &mut dyn Write, error: &Diagnostic) -> io::Result<()> {
+        self.level_color(writer, error.level)?;
+        write!(writer, "{}", error.level.as_str())?;
+        if let Some(code) = error.code {
+            write!(writer, "[{}]", code)?;
+        }
+        self.reset(writer)?;
+        self.bold(writer)?;
+        writeln!(writer, ": {}", error.title)?;
+        self.reset(writer)?;
+
+        let snippets: Vec<_> = error
+            .messages
+            .iter()
+            .filter_map(|hint| self.snippet(hint))
+            .collect();
+
+        let suggestions: Vec<_> = error
+            .suggestions
+            .iter()
+            .map(|suggestion| (suggestion, self.patch(suggestion)))
+            .collect();
+
+        let snippet_lines = snippets
+            .iter()
+            .map(|snippet| snippet.line + snippet.lines.len() - 1);
+        let patch_lines = suggestions
+            .iter()
+            .filter_map(|(_, patch)| patch.as_ref().map(|patch| patch.line));
+        let width = snippet_lines
+            .chain(patch_lines)
+            .max()
+            .map_or(0, |line| line.to_string().len());
+
+        // the location shown first is that of the first primary hint
+        let primary = snippets.iter().find(|snippet| snippet.primary);
+        let mut previous: Option<&Snippet> = None;
+        for snippet in snippets.iter() {
+            let location = match previous {
+                None => Some(("-->", primary.unwrap_or(snippet))),
+                Some(previous) if previous.path != snippet.path => Some((":::", snippet)),
+                Some(_) => None,
+            };
+
+            if let Some((arrow, location)) = location {
+                write!(writer, "{:width$}", "")?;
+                self.gutter(writer)?;
+                write!(writer, "{} ", arrow)?;
+                self.reset(writer)?;
+                writeln!(
+                    writer,
+                    "{}:{}:{}",
+                    location.path.display(),
+                    location.line,
+                    location.column
+                )?;
+            }
+
+            self.write_empty_gutter(writer, width)?;
+            self.write_snippet(writer, error.level, snippet, width)?;
+
+            previous = Some(snippet);
+        }
+
+        if !snippets.is_empty() {
+            self.write_empty_gutter(writer, width)?;
+        }
+
+        for hint in error.messages.iter() {
+            if self.snippet(hint).is_some() {
+                continue;
+            }
+
+            let Some(ref message) = hint.message else {
+                continue;
+            };
+
+            self.write_child(writer, None, message, width)?;
+        }
+
+        for child in error.children.iter() {
+            self.write_child(writer, Some(child.level), &child.message, width)?;
+        }
+
+        for (suggestion, patch) in suggestions.iter() {
+            let Some(patch) = patch else {
+                let message = format!("{}: `{}`", suggestion.message, suggestion.replacement);
+                self.write_child(writer, Some(DiagnosticLevel::Help), &message, width)?;
+                continue;
+            };
+
+            self.level_color(writer, DiagnosticLevel::Help)?;
+            write!(writer, "help")?;
+            self.reset(writer)?;
+            writeln!(writer, ": {}", suggestion.message)?;
+
+            self.write_empty_gutter(writer, width)?;
+            self.write_patch(writer, patch, width)?;
+            self.write_empty_gutter(writer, width)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_child(
+        &self,
+        writer: &mut dyn Write,
+        level: Option<DiagnosticLevel>,
+        message: &str,
+        width: usize,
+    ) -> io::Result<()> {
+        write!(writer, "{:width$} ", "")?;
+        self.gutter(writer)?;
+        write!(writer, "=")?;
+        self.reset(writer)?;
+
+        if let Some(level) = level {
+            self.bold(writer)?;
+            write!(writer, " {}:", level.as_str())?;
+            self.reset(writer)?;
+        }
+
+        writeln!(writer, " {}", message)
+    }
+
+    fn write_snippet(
+        &self,
+        writer: &mut dyn Write,
+        level: DiagnosticLevel,
+        snippet: &Snippet,
+        width: usize,
+    ) -> io::Result<()> {
+        let last = snippet.lines.len() - 1;
+        let underline = if snippet.primary { "^" } else { "-" };
+
+        for (i, (text, lo, hi)) in snippet.lines.iter().enumerate() {
+            self.gutter(writer)?;
+            write!(writer, "{:>width$} |", snippet.line + i)?;
+            self.reset(writer)?;
+            writeln!(writer, " {}", expand_tabs(text))?;
+
+            write!(writer, "{:width$} ", "")?;
+            self.gutter(writer)?;
+            write!(writer, "|")?;
+            self.reset(writer)?;
+
+            let padding = expand_tabs(&text[..*lo]).chars().count();
+            let carets = expand_tabs(&text[*lo..*hi]).chars().count().max(1);
+
+            write!(writer, " {:padding$}", "")?;
+            if snippet.primary {
+                self.level_color(writer, level)?;
+            } else {
+                self.gutter(writer)?;
+            }
+            write!(writer, "{}", underline.repeat(carets))?;
+
+            match snippet.message {
+                Some(ref message) if i == last => write!(writer, " {}", message)?,
+                _ => {}
+            }
+
+            self.reset(writer)?;
+            writeln!(writer)?;
+        }
+
         Ok(())
     }
+
+    fn write_patch(&self, writer: &mut dyn Write, patch: &Patch, width: usize) -> io::Result<()> {
+        self.gutter(writer)?;
+        write!(writer, "{:>width$} |", patch.line)?;
+        self.reset(writer)?;
+        writeln!(writer, " {}", expand_tabs(&patch.text))?;
+
+        write!(writer, "{:width$} ", "")?;
+        self.gutter(writer)?;
+        write!(writer, "|")?;
+        self.reset(writer)?;
+
+        let padding = expand_tabs(&patch.text[..patch.lo]).chars().count();
+        let length = expand_tabs(&patch.text[patch.lo..patch.hi]).chars().count();
+        let marker = if patch.insertion { "+" } else { "~" };
+
+        write!(writer, " {:padding$}", "")?;
+        self.level_color(writer, DiagnosticLevel::Help)?;
+        write!(writer, "{}", marker.repeat(length.max(1)))?;
+        self.reset(writer)?;
+        writeln!(writer)
+    }
+
+    fn write_empty_gutter(&self, writer: &mut dyn Write, width: usize) -> io::Result<()> {
+        write!(writer, "{:width$} ", "")?;
+        self.gutter(writer)?;
+        write!(writer, "|")?;
+        self.reset(writer)?;
+        writeln!(writer)
+    }
+
+    fn snippet(&self, hint: &DiagnosticHint) -> Option<Snippet<'_>> {
+        let span = hint.span?;
+        let file = self.source_map.get(span.file?)?;
+
+        Some(Snippet::new(file, span, hint.message.clone(), hint.primary))
+    }
+
+    /// Applies `suggestion` to the line it's on, returns `None` if the
+    /// suggestion spans multiple lines.
+    fn patch(&self, suggestion: &Suggestion) -> Option<Patch> {
+        let span = suggestion.span;
+        let file = self.source_map.get(span.file?)?;
+
+        let (line, _) = file.position(span.lo);
+        if file.position(span.hi).0 != line || suggestion.replacement.contains('\n') {
+            return None;
+        }
+
+        let text = file.line_text(line)?;
+        let start = file.line_starts()[line - 1];
+        let lo = span.lo - start;
+        let hi = (span.hi - start).min(text.len());
+
+        let mut patched = String::from(&text[..lo]);
+        patched.push_str(&suggestion.replacement);
+        patched.push_str(&text[hi..]);
+
+        Some(Patch {
+            line,
+            text: patched,
+            lo,
+            hi: lo + suggestion.replacement.len(),
+            insertion: suggestion.is_insertion(),
+        })
+    }
+
+    fn level_color(&self, writer: &mut dyn Write, level: DiagnosticLevel) -> io::Result<()> {
+        if !self.color {
+            return Ok(());
+        }
+
+        match level {
+            DiagnosticLevel::Error => write!(writer, "{}{}", style::Bold, color::Fg(color::Red)),
+            DiagnosticLevel::Warning => {
+                write!(writer, "{}{}", style::Bold, color::Fg(color::Yellow))
+            }
+            DiagnosticLevel::Note => write!(writer, "{}{}", style::Bold, color::Fg(color::Green)),
+            DiagnosticLevel::Help => write!(writer, "{}{}", style::Bold, color::Fg(color::Cyan)),
+        }
+    }
+
+    fn gutter(&self, writer: &mut dyn Write) -> io::Result<()> {
+        if !self.color {
+            return Ok(());
+        }
+
+        write!(writer, "{}{}", style::Bold, color::Fg(color::Blue))
+    }
+
+    fn bold(&self, writer: &mut dyn Write) -> io::Result<()> {
+        if !self.color {
+            return Ok(());
+        }
+
+        write!(writer, "{}", style::Bold)
+    }
+
+    fn reset(&self, writer: &mut dyn Write) -> io::Result<()> {
+        if !self.color {
+            return Ok(());
+        }
+
+        write!(writer, "{}", style::Reset)
+    }
+}
+
+fn expand_tabs(text: &str) -> String {
+    text.replace('\t', TAB)
+}
+
+/// The source lines covered by a single [`DiagnosticHint`].
+struct Snippet<'a> {
+    path: &'a Path,
+    /// One-based line number of the first line.
+    line: usize,
+    /// One-based column of the start of the span.
+    column: usize,
+    /// Each covered line, with the byte range of the span within that line.
+    lines: Vec<(&'a str, usize, usize)>,
+    message: Option<String>,
+    primary: bool,
+}
+
+/// A source line with a [`Suggestion`] applied.
+struct Patch {
+    line: usize,
+    text: String,
+    /// Byte range of the replacement within `text`.
+    lo: usize,
+    hi: usize,
+    insertion: bool,
+}
+
+impl<'a> Snippet<'a> {
+    fn new(file: &'a SourceFile, span: Span, message: Option<String>, primary: bool) -> Self {
+        // spans may end inside a multibyte character, widen them to whole characters
+        let lo = file.text.floor_char_boundary(span.lo);
+        let hi = file.text.ceil_char_boundary(span.hi.max(lo));
+
+        let (line, column) = file.position(lo);
+        let last = file.text[..hi].char_indices().last();
+        let last_line = file.position(last.map_or(lo, |(i, _)| i.max(lo))).0;
+
+        let mut lines = Vec::new();
+        for line in line..=last_line {
+            let text = file.line_text(line).unwrap_or_default();
+            let start = file.line_starts()[line - 1];
+
+            let span_lo = lo.max(start) - start;
+            let span_hi = hi.min(start + text.len()).max(start + span_lo) - start;
+            lines.push((text, span_lo, span_hi));
+        }
+
+        Self {
+            path: &file.path,
+            line,
+            column,
+            lines,
+            message,
+            primary,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ritec_core::{SourceFile, SourceMap, Span};
+
+    use crate::{Diagnostic, ErrorCode, ErrorWriter, Suggestion};
+
+    fn render(text: &str, diagnostic: impl FnOnce(Span) -> Diagnostic) -> String {
+        let mut source_map = SourceMap::new();
+        let file = source_map.insert(SourceFile::new("main.ri", text));
+
+        let diagnostic = diagnostic(Span::new(0, 0, file));
+
+        let writer = ErrorWriter::new(&source_map).with_color(false);
+        let mut out = Vec::new();
+        writer.write(&mut out, &diagnostic).unwrap();
+
+        String::from_utf8(out).unwrap()
+    }
+
+    fn span(file: Span, lo: usize, hi: usize) -> Span {
+        Span { lo, hi, ..file }
+    }
+
+    #[test]
+    fn single_span() {
+        let out = render("fn main() {\n\tlet x = y;\n}\n", |file| {
+            Diagnostic::error("'y' not defined")
+                .with_msg_span("variable not found", span(file, 21, 22))
+        });
+
+        let expected = "\
+error: 'y' not defined
+ --> main.ri:2:10
+  |
+2 |     let x = y;
+  |             ^ variable not found
+  |
+";
+
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn multiple_spans_and_notes() {
+        let out = render("let a = 1;\nlet b = a + true;\n", |file| {
+            Diagnostic::warning("mismatched types")
+                .with_msg_span("this is an integer", span(file, 19, 20))
+                .with_msg_span("this is a bool", span(file, 23, 27))
+                .with_msg(String::from("operands must have the same type"))
+        });
+
+        let expected = "\
+warning: mismatched types
+ --> main.ri:2:9
+  |
+2 | let b = a + true;
+  |         ^ this is an integer
+  |
+2 | let b = a + true;
+  |             ^^^^ this is a bool
+  |
+  = operands must have the same type
+";
+
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn multi_line_span() {
+        let out = render("fn foo() {\n    bar\n}\n", |file| {
+            Diagnostic::error("missing return").with_msg_span("in this body", span(file, 9, 21))
+        });
+
+        let expected = "\
+error: missing return
+ --> main.ri:1:10
+  |
+1 | fn foo() {
+  |          ^
+2 |     bar
+  | ^^^^^^^
+3 | }
+  | ^ in this body
+  |
+";
+
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn non_ascii_span() {
+        // the span ends inside `é`
+        let out = render("let é = 1;\n", |file| {
+            Diagnostic::error("unexpected character").with_msg_span("here", span(file, 4, 5))
+        });
+
+        let expected = "\
+error: unexpected character
+ --> main.ri:1:5
+  |
+1 | let é = 1;
+  |     ^ here
+  |
+";
+
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn no_span() {
+        let out = render("", |_| {
+            Diagnostic::error("failed to read file")
+                .with_msg(String::from("failed to read main file"))
+        });
+
+        assert_eq!(
+            out,
+            "error: failed to read file\n = failed to read main file\n"
+        );
+    }
+
+    #[test]
+    fn code_labels_and_children() {
+        let out = render("fn foo() -> i32 {}\nlet x: bool = foo();\n", |file| {
+            Diagnostic::error("cannot unify types")
+                .with_code(ErrorCode::E0007)
+                .with_secondary("expected due to this", span(file, 26, 30))
+                .with_msg_span("expected `bool`, found `i32`", span(file, 33, 38))
+                .with_note("`foo` returns `i32`")
+                .with_help("try casting with `as`")
+        });
+
+        let expected = "\
+error[E0007]: cannot unify types
+ --> main.ri:2:15
+  |
+2 | let x: bool = foo();
+  |        ---- expected due to this
+  |
+2 | let x: bool = foo();
+  |               ^^^^^ expected `bool`, found `i32`
+  |
+  = note: `foo` returns `i32`
+  = help: try casting with `as`
+";
+
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn suggestions() {
+        let out = render("let x = 1\nv.psh(x);\n", |file| {
+            Diagnostic::error("expected `;`")
+                .with_code(ErrorCode::E0002)
+                .with_msg_span("found `v`", span(file, 10, 11))
+                .with_suggestion(Suggestion::machine_applicable(
+                    "insert `;`",
+                    span(file, 9, 9),
+                    ";",
+                ))
+                .with_suggestion(Suggestion::maybe_incorrect(
+                    "did you mean `push`",
+                    span(file, 12, 15),
+                    "push",
+                ))
+        });
+
+        let expected = "\
+error[E0002]: expected `;`
+ --> main.ri:2:1
+  |
+2 | v.psh(x);
+  | ^ found `v`
+  |
+help: insert `;`
+  |
+1 | let x = 1;
+  |          +
+  |
+help: did you mean `push`
+  |
+2 | v.push(x);
+  |   ~~~~
+  |
+";
+
+        assert_eq!(out, expected);
+    }
 }
@@ -3,11 +3,12 @@ use std::{
     ops::{Index, IndexMut},
 };
 
-use ritec_core::{Arena, Ident, Span};
+use ritec_core::{Arena, BinOp, Ident, Span};
 
 use crate::{
     AlignofExpr, BitcastExpr, Block, BlockId, BreakExpr, Expr, ExprId, ExprStmt, FreeExpr, IfExpr,
     Local, LocalExpr, LocalId, MallocExpr, MemcpyExpr, ReturnExpr, SizeofExpr, Stmt, Type,
+    WrappingExpr,
 };
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -129,6 +130,17 @@ impl Body {
         self.exprs.push(Expr::Memcpy(expr))
     }
 
+    pub fn wrapping_expr(&mut self, operator: BinOp, lhs: ExprId, rhs: ExprId) -> ExprId {
+        let expr = WrappingExpr {
+            operator,
+            lhs,
+            rhs,
+            id: self.next_id(),
+            span: Span::DUMMY,
+        };
+        self.exprs.push(Expr::Wrapping(expr))
+    }
+
     pub fn return_expr(&mut self, value: Option<ExprId>) -> ExprId {
         let expr = ReturnExpr {
             value,
@@ -183,6 +195,56 @@ impl Body {
     pub fn next_id(&mut self) -> HirId {
         self.next_id.increment()
     }
+
+    /// Calls `f` with every type in the body, see [`Type::visit`].
+    pub fn visit_types(&self, f: &mut impl FnMut(&Type)) {
+        for local in self.locals.values() {
+            local.ty.visit(f);
+        }
+
+        for expr in self.exprs.values() {
+            let types = match expr {
+                Expr::Function(expr) => &expr.instance.generics,
+                Expr::ClassInit(expr) => &expr.class.generics,
+                Expr::MethodCall(expr) => &expr.generics,
+                Expr::As(expr) => std::slice::from_ref(&expr.ty),
+                Expr::Bitcast(expr) => std::slice::from_ref(&expr.ty),
+                Expr::Sizeof(expr) => std::slice::from_ref(&expr.ty),
+                Expr::Alignof(expr) => std::slice::from_ref(&expr.ty),
+                Expr::Malloc(expr) => std::slice::from_ref(&expr.ty),
+                _ => continue,
+            };
+
+            for ty in types {
+                ty.visit(f);
+            }
+        }
+    }
+
+    /// Calls `f` with every type in the body, see [`Type::visit_mut`].
+    pub fn visit_types_mut(&mut self, f: &mut impl FnMut(&mut Type)) {
+        for local in self.locals.values_mut() {
+            local.ty.visit_mut(f);
+        }
+
+        for expr in self.exprs.values_mut() {
+            let types = match expr {
+                Expr::Function(expr) => &mut expr.instance.generics,
+                Expr::ClassInit(expr) => &mut expr.class.generics,
+                Expr::MethodCall(expr) => &mut expr.generics,
+                Expr::As(expr) => std::slice::from_mut(&mut expr.ty),
+                Expr::Bitcast(expr) => std::slice::from_mut(&mut expr.ty),
+                Expr::Sizeof(expr) => std::slice::from_mut(&mut expr.ty),
+                Expr::Alignof(expr) => std::slice::from_mut(&mut expr.ty),
+                Expr::Malloc(expr) => std::slice::from_mut(&mut expr.ty),
+                _ => continue,
+            };
+
+            for ty in types {
+                ty.visit_mut(f);
+            }
+        }
+    }
 }
 
 impl Index<LocalId> for Body {
@@ -0,0 +1,51 @@
+use std::fmt::{self, Display};
+
+use ritec_core::{Id, Ident, Span};
+
+use crate::{Body, ExprId, Type};
+
+pub type ConstId = Id<Const>;
+
+/// A constant evaluated at compile time, either a `const` item or the
+/// length of an array type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Const {
+    /// The name of a `const` item, array lengths have none.
+    pub ident: Option<Ident>,
+    pub ty: Type,
+    pub body: Body,
+    pub expr: ExprId,
+    /// The value of the constant, set by the const evaluator.
+    pub value: Option<ConstValue>,
+    pub span: Span,
+}
+
+impl Const {
+    /// Returns a description of the constant for diagnostics.
+    pub fn describe(&self) -> String {
+        match self.ident {
+            Some(ref ident) => format!("constant `{}`", ident),
+            None => String::from("array length"),
+        }
+    }
+}
+
+/// The value of a [`Const`], integers are stored sign extended.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConstValue {
+    Bool(bool),
+    Int(i128),
+    Float(f64),
+    Null,
+}
+
+impl Display for ConstValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{}", value),
+            Self::Int(value) => write!(f, "{}", value),
+            Self::Float(value) => write!(f, "{:?}", value),
+            Self::Null => write!(f, "null"),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use ritec_core::{BinOp, Id, Ident, Literal, Span, UnaryOp};
 
-use crate::{BlockId, ClassType, FieldId, FunctionInstance, HirId, LocalId, Type};
+use crate::{BlockId, ClassType, ConstId, FieldId, FunctionInstance, HirId, LocalId, Type};
 
 pub type ExprId = Id<Expr>;
 
@@ -9,6 +9,7 @@ pub enum Expr {
     Local(LocalExpr),
     Literal(LiteralExpr),
     Function(FunctionExpr),
+    Const(ConstExpr),
     ClassInit(ClassInitExpr),
     Field(FieldExpr),
     As(AsExpr),
@@ -18,6 +19,7 @@ pub enum Expr {
     Malloc(MallocExpr),
     Free(FreeExpr),
     Memcpy(MemcpyExpr),
+    Wrapping(WrappingExpr),
     Call(CallExpr),
     MethodCall(MethodCallExpr),
     Unary(UnaryExpr),
@@ -36,6 +38,7 @@ impl Expr {
             Expr::Local(expr) => expr.span,
             Expr::Literal(expr) => expr.span,
             Expr::Function(expr) => expr.span,
+            Expr::Const(expr) => expr.span,
             Expr::ClassInit(expr) => expr.span,
             Expr::Field(expr) => expr.span,
             Expr::As(expr) => expr.span,
@@ -45,6 +48,7 @@ impl Expr {
             Expr::Malloc(expr) => expr.span,
             Expr::Free(expr) => expr.span,
             Expr::Memcpy(expr) => expr.span,
+            Expr::Wrapping(expr) => expr.span,
             Expr::Call(expr) => expr.span,
             Expr::MethodCall(expr) => expr.span,
             Expr::Unary(expr) => expr.span,
@@ -63,6 +67,7 @@ impl Expr {
             Expr::Local(expr) => expr.id,
             Expr::Literal(expr) => expr.id,
             Expr::Function(expr) => expr.id,
+            Expr::Const(expr) => expr.id,
             Expr::ClassInit(expr) => expr.id,
             Expr::Field(expr) => expr.id,
             Expr::As(expr) => expr.id,
@@ -72,6 +77,7 @@ impl Expr {
             Expr::Malloc(expr) => expr.id,
             Expr::Free(expr) => expr.id,
             Expr::Memcpy(expr) => expr.id,
+            Expr::Wrapping(expr) => expr.id,
             Expr::Call(expr) => expr.id,
             Expr::MethodCall(expr) => expr.id,
             Expr::Unary(expr) => expr.id,
@@ -104,6 +110,12 @@ impl From<FunctionExpr> for Expr {
     }
 }
 
+impl From<ConstExpr> for Expr {
+    fn from(expr: ConstExpr) -> Self {
+        Self::Const(expr)
+    }
+}
+
 impl From<ClassInitExpr> for Expr {
     fn from(expr: ClassInitExpr) -> Self {
         Self::ClassInit(expr)
@@ -158,6 +170,12 @@ impl From<MemcpyExpr> for Expr {
     }
 }
 
+impl From<WrappingExpr> for Expr {
+    fn from(expr: WrappingExpr) -> Self {
+        Self::Wrapping(expr)
+    }
+}
+
 impl From<CallExpr> for Expr {
     fn from(expr: CallExpr) -> Self {
         Self::Call(expr)
@@ -233,6 +251,13 @@ pub struct FunctionExpr {
     pub span: Span,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConstExpr {
+    pub constant: ConstId,
+    pub id: HirId,
+    pub span: Span,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct ClassInitExpr {
     pub class: ClassType,
@@ -303,6 +328,17 @@ pub struct MemcpyExpr {
     pub span: Span,
 }
 
+/// Integer arithmetic that wraps around on overflow, even when runtime
+/// checks are enabled.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WrappingExpr {
+    pub operator: BinOp,
+    pub lhs: ExprId,
+    pub rhs: ExprId,
+    pub id: HirId,
+    pub span: Span,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct CallExpr {
     pub callee: ExprId,
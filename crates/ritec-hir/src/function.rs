@@ -1,4 +1,4 @@
-use ritec_core::{Id, Ident, Span};
+use ritec_core::{Id, Ident, Inline, Span};
 
 use crate::{Body, FunctionType, Generics, LocalId, Type};
 
@@ -18,6 +18,12 @@ pub struct Function {
     pub arguments: Vec<FunctionArgument>,
     pub return_type: Type,
     pub body: Body,
+    pub inline: Inline,
+    /// Whether the function has the `#[export]` attribute, which makes it
+    /// part of the program even if it is never called.
+    pub export: bool,
+    /// Whether the function is a `const fn`, which can be called in constants.
+    pub is_const: bool,
     pub span: Span,
 }
 
@@ -1,4 +1,4 @@
-use ritec_core::{Generic, Ident, Span};
+use ritec_core::{BinOp, Generic, Ident, Inline, Span};
 
 use crate::{Body, Function, FunctionArgument, Generics, IntType, PointerType, Type};
 
@@ -30,6 +30,9 @@ pub fn build_intrinsic_bitcast() -> Function {
         arguments,
         return_type: u.into(),
         body,
+        inline: Inline::Auto,
+        export: false,
+        is_const: false,
         span: Span::DUMMY,
     }
 }
@@ -58,6 +61,9 @@ pub fn build_intrinsic_sizeof() -> Function {
             span: Span::DUMMY,
         }),
         body,
+        inline: Inline::Auto,
+        export: false,
+        is_const: true,
         span: Span::DUMMY,
     }
 }
@@ -86,6 +92,9 @@ pub fn build_intrinsic_alignof() -> Function {
             span: Span::DUMMY,
         }),
         body,
+        inline: Inline::Auto,
+        export: false,
+        is_const: true,
         span: Span::DUMMY,
     }
 }
@@ -127,6 +136,9 @@ pub fn build_intrinsic_malloc() -> Function {
             span: Span::DUMMY,
         }),
         body,
+        inline: Inline::Auto,
+        export: false,
+        is_const: false,
         span: Span::DUMMY,
     }
 }
@@ -164,6 +176,9 @@ pub fn build_intrinsic_free() -> Function {
         arguments,
         return_type: Type::void(Span::DUMMY),
         body,
+        inline: Inline::Auto,
+        export: false,
+        is_const: false,
         span: Span::DUMMY,
     }
 }
@@ -232,6 +247,54 @@ pub fn build_intrinsic_memcpy() -> Function {
         arguments,
         return_type: Type::void(Span::DUMMY),
         body,
+        inline: Inline::Auto,
+        export: false,
+        is_const: false,
+        span: Span::DUMMY,
+    }
+}
+
+/// Builds `fn name<T>(lhs: T, rhs: T) -> T`, which applies `operator` and
+/// wraps around on overflow.
+pub fn build_intrinsic_wrapping(name: &str, operator: BinOp) -> Function {
+    let ident = Ident::from(name);
+    let t = Generic::new("T");
+
+    let generics = Generics::new(vec![t.clone()], Span::DUMMY);
+
+    let mut body = Body::new();
+
+    let lhs = body.local("lhs", t.clone());
+    let rhs = body.local("rhs", t.clone());
+
+    let arguments = vec![
+        FunctionArgument {
+            ident: Ident::new("lhs", Span::DUMMY),
+            local: lhs,
+            span: Span::DUMMY,
+        },
+        FunctionArgument {
+            ident: Ident::new("rhs", Span::DUMMY),
+            local: rhs,
+            span: Span::DUMMY,
+        },
+    ];
+
+    let lhs_local = body.local_expr(lhs);
+    let rhs_local = body.local_expr(rhs);
+    let wrapping = body.wrapping_expr(operator, lhs_local, rhs_local);
+    let ret = body.return_expr(Some(wrapping));
+    body.expr_stmt(ret);
+
+    Function {
+        ident,
+        generics,
+        arguments,
+        return_type: t.into(),
+        body,
+        inline: Inline::Auto,
+        export: false,
+        is_const: true,
         span: Span::DUMMY,
     }
 }
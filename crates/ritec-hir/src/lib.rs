@@ -1,12 +1,15 @@
 mod block;
 mod body;
 mod class;
+mod constant;
 mod expr;
 mod function;
 mod generic;
 mod intrinsic;
 mod local;
 mod module;
+mod path;
+mod print;
 mod program;
 mod stmt;
 mod ty;
@@ -14,12 +17,14 @@ mod ty;
 pub use block::*;
 pub use body::*;
 pub use class::*;
+pub use constant::*;
 pub use expr::*;
 pub use function::*;
 pub use generic::*;
 pub use intrinsic::*;
 pub use local::*;
 pub use module::*;
+pub use path::*;
 pub use program::*;
 pub use stmt::*;
 pub use ty::*;
@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 
 use ritec_core::{Id, Ident};
 
-use crate::{ClassId, FunctionId};
+use crate::{ClassId, ConstId, FunctionId};
 
 pub type ModuleId = Id<Module>;
 
@@ -11,6 +11,7 @@ pub struct Module {
     pub modules: BTreeMap<Ident, ModuleId>,
     pub classes: BTreeMap<Ident, ClassId>,
     pub functions: BTreeMap<Ident, FunctionId>,
+    pub consts: BTreeMap<Ident, ConstId>,
 }
 
 impl Module {
@@ -19,6 +20,7 @@ impl Module {
             modules: BTreeMap::new(),
             classes: BTreeMap::new(),
             functions: BTreeMap::new(),
+            consts: BTreeMap::new(),
         }
     }
 }
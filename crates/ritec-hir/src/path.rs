@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use ritec_core::Ident;
+
+use crate::{ClassId, FunctionId, ModuleId, Program};
+
+/// The paths classes and functions are declared in, the modules leading up to
+/// them, followed by the class for methods and field initializers.
+///
+/// Items that aren't reachable from the root module, like intrinsics, have no
+/// path.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ItemPaths {
+    pub classes: HashMap<ClassId, Vec<Ident>>,
+    pub functions: HashMap<FunctionId, Vec<Ident>>,
+    /// The class of methods and field initializers.
+    pub methods: HashMap<FunctionId, ClassId>,
+}
+
+impl ItemPaths {
+    pub fn class(&self, id: ClassId) -> &[Ident] {
+        self.classes.get(&id).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn function(&self, id: FunctionId) -> &[Ident] {
+        self.functions.get(&id).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn method_class(&self, id: FunctionId) -> Option<ClassId> {
+        self.methods.get(&id).copied()
+    }
+}
+
+impl Program {
+    pub fn item_paths(&self) -> ItemPaths {
+        let mut paths = ItemPaths::default();
+        self.collect_paths(self.root_module, &mut Vec::new(), &mut paths);
+        paths
+    }
+
+    fn collect_paths(&self, module: ModuleId, path: &mut Vec<Ident>, paths: &mut ItemPaths) {
+        let module = &self[module];
+
+        for &id in module.classes.values() {
+            paths.classes.insert(id, path.clone());
+
+            let class = &self[id];
+            let mut class_path = path.clone();
+            class_path.push(class.ident.clone());
+
+            for method in class.methods.values() {
+                paths.functions.insert(method.function, class_path.clone());
+                paths.methods.insert(method.function, id);
+            }
+
+            for field in class.fields.values() {
+                if let Some(init) = field.init {
+                    paths.functions.insert(init, class_path.clone());
+                    paths.methods.insert(init, id);
+                }
+            }
+        }
+
+        for &id in module.functions.values() {
+            paths.functions.insert(id, path.clone());
+        }
+
+        for (ident, &id) in module.modules.iter() {
+            path.push(ident.clone());
+            self.collect_paths(id, path, paths);
+            path.pop();
+        }
+    }
+}
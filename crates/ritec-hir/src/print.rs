@@ -0,0 +1,367 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::{BlockId, Body, Class, Const, Expr, ExprId, Function, Program, Stmt, Type};
+
+fn indent(f: &mut Formatter<'_>, depth: usize) -> fmt::Result {
+    for _ in 0..depth {
+        write!(f, "\t")?;
+    }
+
+    Ok(())
+}
+
+fn generics(types: &[Type]) -> String {
+    if types.is_empty() {
+        return String::new();
+    }
+
+    let types: Vec<_> = types.iter().map(Type::to_string).collect();
+    format!("<{}>", types.join(", "))
+}
+
+impl Display for Class {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "class {}", self.ident)?;
+        write!(f, "{}", generics(&self.generics.instance()))?;
+        writeln!(f, " {{")?;
+
+        for field in self.fields.values() {
+            write!(f, "\t{}: {}", field.ident, field.ty)?;
+
+            if let Some(init) = field.init {
+                write!(f, " = fn[{}]", init.as_raw_index())?;
+            }
+
+            writeln!(f, ",")?;
+        }
+
+        for method in self.methods.values() {
+            writeln!(
+                f,
+                "\tfn {} = fn[{}],",
+                method.ident,
+                method.function.as_raw_index()
+            )?;
+        }
+
+        write!(f, "}}")
+    }
+}
+
+/// Prints a [`Body`] together with the [`Program`] it belongs to, which is
+/// needed to name the fields of classes used in it.
+struct BodyPrinter<'a> {
+    program: &'a Program,
+    body: &'a Body,
+}
+
+impl<'a> BodyPrinter<'a> {
+    fn body(&self) -> &'a Body {
+        self.body
+    }
+
+    fn block(&self, f: &mut Formatter<'_>, block: BlockId, depth: usize) -> fmt::Result {
+        writeln!(f, "{{")?;
+
+        for stmt in self.body()[block].stmts.iter() {
+            indent(f, depth + 1)?;
+            self.stmt(f, stmt, depth + 1)?;
+            writeln!(f)?;
+        }
+
+        indent(f, depth)?;
+        write!(f, "}}")
+    }
+
+    fn stmt(&self, f: &mut Formatter<'_>, stmt: &Stmt, depth: usize) -> fmt::Result {
+        match stmt {
+            Stmt::Let(stmt) => {
+                write!(f, "let _{}", stmt.local.as_raw_index())?;
+
+                if let Some(init) = stmt.init {
+                    write!(f, " = ")?;
+                    self.expr(f, init, depth)?;
+                }
+            }
+            Stmt::Expr(stmt) => self.expr(f, stmt.expr, depth)?,
+        }
+
+        write!(f, ";")
+    }
+
+    fn exprs(&self, f: &mut Formatter<'_>, exprs: &[ExprId], depth: usize) -> fmt::Result {
+        for (i, &expr) in exprs.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+
+            self.expr(f, expr, depth)?;
+        }
+
+        Ok(())
+    }
+
+    /// Prints the receiver of a field access or method call, parenthesizing
+    /// unary expressions so `(*a).b` isn't printed as `*a.b`.
+    fn receiver(&self, f: &mut Formatter<'_>, expr: ExprId, depth: usize) -> fmt::Result {
+        if let Expr::Unary(_) = self.body()[expr] {
+            write!(f, "(")?;
+            self.expr(f, expr, depth)?;
+            write!(f, ")")
+        } else {
+            self.expr(f, expr, depth)
+        }
+    }
+
+    fn expr(&self, f: &mut Formatter<'_>, expr: ExprId, depth: usize) -> fmt::Result {
+        match &self.body()[expr] {
+            Expr::Local(expr) => write!(f, "_{}", expr.local.as_raw_index()),
+            Expr::Literal(expr) => write!(f, "{}", expr.literal),
+            Expr::Function(expr) => write!(
+                f,
+                "fn[{}]{}",
+                expr.instance.function.as_raw_index(),
+                generics(&expr.instance.generics)
+            ),
+            Expr::Const(expr) => write!(f, "const[{}]", expr.constant.as_raw_index()),
+            Expr::ClassInit(expr) => {
+                let class = &self.program[expr.class.class];
+                write!(f, "{} {{", expr.class)?;
+
+                for (i, (field, init)) in expr.fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+
+                    write!(f, " {}: ", class[*field].ident)?;
+                    self.expr(f, *init, depth)?;
+                }
+
+                if !expr.fields.is_empty() {
+                    write!(f, " ")?;
+                }
+
+                write!(f, "}}")
+            }
+            Expr::Field(expr) => {
+                self.receiver(f, expr.class, depth)?;
+                write!(f, ".{}", expr.field)
+            }
+            Expr::As(expr) => {
+                write!(f, "(")?;
+                self.expr(f, expr.expr, depth)?;
+                write!(f, " as {})", expr.ty)
+            }
+            Expr::Bitcast(expr) => {
+                write!(f, "bitcast(")?;
+                self.expr(f, expr.expr, depth)?;
+                write!(f, " as {})", expr.ty)
+            }
+            Expr::Sizeof(expr) => write!(f, "sizeof({})", expr.ty),
+            Expr::Alignof(expr) => write!(f, "alignof({})", expr.ty),
+            Expr::Malloc(expr) => {
+                write!(f, "malloc(")?;
+                self.expr(f, expr.count, depth)?;
+                write!(f, ", {})", expr.ty)
+            }
+            Expr::Free(expr) => {
+                write!(f, "free(")?;
+                self.expr(f, expr.expr, depth)?;
+                write!(f, ")")
+            }
+            Expr::Memcpy(expr) => {
+                write!(f, "memcpy(")?;
+                self.exprs(f, &[expr.dst, expr.src, expr.size], depth)?;
+                write!(f, ")")
+            }
+            Expr::Wrapping(expr) => {
+                write!(f, "wrapping(")?;
+                self.expr(f, expr.lhs, depth)?;
+                write!(f, " {} ", expr.operator)?;
+                self.expr(f, expr.rhs, depth)?;
+                write!(f, ")")
+            }
+            Expr::Call(expr) => {
+                self.expr(f, expr.callee, depth)?;
+                write!(f, "(")?;
+                self.exprs(f, &expr.arguments, depth)?;
+                write!(f, ")")
+            }
+            Expr::MethodCall(expr) => {
+                self.receiver(f, expr.callee, depth)?;
+                write!(f, ".{}{}(", expr.method, generics(&expr.generics))?;
+                self.exprs(f, &expr.arguments, depth)?;
+                write!(f, ")")
+            }
+            Expr::Unary(expr) => {
+                write!(f, "{}", expr.operator)?;
+                self.expr(f, expr.operand, depth)
+            }
+            Expr::Binary(expr) => {
+                write!(f, "(")?;
+                self.expr(f, expr.lhs, depth)?;
+                write!(f, " {} ", expr.operator)?;
+                self.expr(f, expr.rhs, depth)?;
+                write!(f, ")")
+            }
+            Expr::Assign(expr) => {
+                self.expr(f, expr.lhs, depth)?;
+                write!(f, " = ")?;
+                self.expr(f, expr.rhs, depth)
+            }
+            Expr::Return(expr) => {
+                write!(f, "return")?;
+
+                if let Some(value) = expr.value {
+                    write!(f, " ")?;
+                    self.expr(f, value, depth)?;
+                }
+
+                Ok(())
+            }
+            Expr::Break(_) => write!(f, "break"),
+            Expr::Block(expr) => self.block(f, expr.block, depth),
+            Expr::If(expr) => {
+                write!(f, "if ")?;
+                self.expr(f, expr.condition, depth)?;
+                write!(f, " ")?;
+                self.expr(f, expr.then_expr, depth)?;
+
+                if let Some(else_expr) = expr.else_expr {
+                    write!(f, " else ")?;
+                    self.expr(f, else_expr, depth)?;
+                }
+
+                Ok(())
+            }
+            Expr::Loop(expr) => {
+                write!(f, "loop ")?;
+                self.block(f, expr.block, depth)
+            }
+        }
+    }
+}
+
+struct FunctionPrinter<'a> {
+    program: &'a Program,
+    function: &'a Function,
+}
+
+impl Display for FunctionPrinter<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let function = self.function;
+        let printer = BodyPrinter {
+            program: self.program,
+            body: &function.body,
+        };
+
+        if function.export {
+            writeln!(f, "#[export]")?;
+        }
+
+        if let Some(attribute) = function.inline.attribute() {
+            writeln!(f, "{}", attribute)?;
+        }
+
+        if function.is_const {
+            write!(f, "const ")?;
+        }
+
+        write!(f, "fn {}", function.ident)?;
+        write!(f, "{}(", generics(&function.generics.instance()))?;
+
+        for (i, argument) in function.arguments.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+
+            let ty = &function.body[argument.local].ty;
+            write!(f, "_{}: {}", argument.local.as_raw_index(), ty)?;
+        }
+
+        writeln!(f, ") -> {} {{", function.return_type)?;
+
+        for (id, local) in function.body.locals.iter() {
+            writeln!(
+                f,
+                "\tlet _{}: {}; // {}",
+                id.as_raw_index(),
+                local.ty,
+                local.ident
+            )?;
+        }
+
+        if let Some(block) = function.body.blocks.keys().next() {
+            if !function.body.locals.is_empty() {
+                writeln!(f)?;
+            }
+
+            indent(f, 1)?;
+            printer.block(f, block, 1)?;
+            writeln!(f)?;
+        }
+
+        write!(f, "}}")
+    }
+}
+
+struct ConstPrinter<'a> {
+    program: &'a Program,
+    constant: &'a Const,
+}
+
+impl Display for ConstPrinter<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let constant = self.constant;
+        let printer = BodyPrinter {
+            program: self.program,
+            body: &constant.body,
+        };
+
+        match constant.ident {
+            Some(ref ident) => write!(f, "const {}", ident)?,
+            None => write!(f, "const _")?,
+        }
+
+        write!(f, ": {} = ", constant.ty)?;
+        printer.expr(f, constant.expr, 0)?;
+        write!(f, ";")?;
+
+        if let Some(value) = constant.value {
+            write!(f, " // {}", value)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Program {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (id, class) in self.classes.iter() {
+            writeln!(f, "// class[{}]", id.as_raw_index())?;
+            writeln!(f, "{}\n", class)?;
+        }
+
+        for (id, function) in self.functions.iter() {
+            let printer = FunctionPrinter {
+                program: self,
+                function,
+            };
+
+            writeln!(f, "// fn[{}]", id.as_raw_index())?;
+            writeln!(f, "{}\n", printer)?;
+        }
+
+        for (id, constant) in self.consts.iter() {
+            let printer = ConstPrinter {
+                program: self,
+                constant,
+            };
+
+            writeln!(f, "// const[{}]", id.as_raw_index())?;
+            writeln!(f, "{}\n", printer)?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,11 +1,11 @@
 use std::ops::{Index, IndexMut};
 
-use ritec_core::Arena;
+use ritec_core::{Arena, BinOp};
 
 use crate::{
     build_intrinsic_alignof, build_intrinsic_bitcast, build_intrinsic_free, build_intrinsic_malloc,
-    build_intrinsic_memcpy, build_intrinsic_sizeof, Class, ClassId, Function, FunctionId, Module,
-    ModuleId,
+    build_intrinsic_memcpy, build_intrinsic_sizeof, build_intrinsic_wrapping, Class, ClassId,
+    Const, ConstId, Function, FunctionId, Module, ModuleId, Type,
 };
 
 #[derive(Clone, Debug, PartialEq)]
@@ -15,6 +15,7 @@ pub struct Program {
     pub modules: Arena<Module>,
     pub classes: Arena<Class>,
     pub functions: Arena<Function>,
+    pub consts: Arena<Const>,
 }
 
 impl Program {
@@ -22,6 +23,7 @@ impl Program {
         let mut modules = Arena::new();
         let classes = Arena::new();
         let functions = Arena::new();
+        let consts = Arena::new();
 
         let root_module = modules.push(Module::new());
         let auto_include = modules.push(Module::new());
@@ -32,6 +34,7 @@ impl Program {
             modules,
             classes,
             functions,
+            consts,
         }
     }
 
@@ -49,6 +52,28 @@ impl Program {
         self.add_function(build_intrinsic_malloc());
         self.add_function(build_intrinsic_free());
         self.add_function(build_intrinsic_memcpy());
+        self.add_function(build_intrinsic_wrapping("wrapping_add", BinOp::Add));
+        self.add_function(build_intrinsic_wrapping("wrapping_sub", BinOp::Sub));
+        self.add_function(build_intrinsic_wrapping("wrapping_mul", BinOp::Mul));
+    }
+
+    /// Calls `f` with every type in the program, see [`Type::visit_mut`].
+    pub fn visit_types_mut(&mut self, f: &mut impl FnMut(&mut Type)) {
+        for class in self.classes.values_mut() {
+            for field in class.fields.values_mut() {
+                field.ty.visit_mut(f);
+            }
+        }
+
+        for function in self.functions.values_mut() {
+            function.return_type.visit_mut(f);
+            function.body.visit_types_mut(f);
+        }
+
+        for constant in self.consts.values_mut() {
+            constant.ty.visit_mut(f);
+            constant.body.visit_types_mut(f);
+        }
     }
 }
 
@@ -93,3 +118,17 @@ impl IndexMut<FunctionId> for Program {
         &mut self.functions[index]
     }
 }
+
+impl Index<ConstId> for Program {
+    type Output = Const;
+
+    fn index(&self, index: ConstId) -> &Self::Output {
+        &self.consts[index]
+    }
+}
+
+impl IndexMut<ConstId> for Program {
+    fn index_mut(&mut self, index: ConstId) -> &mut Self::Output {
+        &mut self.consts[index]
+    }
+}
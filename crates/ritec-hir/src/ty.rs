@@ -2,7 +2,7 @@ use std::fmt::{self, Display};
 
 use ritec_core::{FloatSize, Generic, Ident, IntSize, Span};
 
-use crate::{ClassId, GenericMap};
+use crate::{ClassId, ConstId, GenericMap};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct InferredType {
@@ -101,6 +101,9 @@ impl Display for PointerType {
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct ArrayType {
     pub element: Box<Type>,
+    /// The constant the length of the array is evaluated from.
+    pub length: ConstId,
+    /// The length of the array, set once `length` has been evaluated.
     pub size: usize,
     pub span: Span,
 }
@@ -303,6 +306,74 @@ impl Type {
             Type::Inferred(_) | Type::Void(_) | Type::Bool(_) | Type::Int(_) | Type::Float(_) => {}
         }
     }
+
+    /// Calls `f` with `self` and every type nested in it.
+    pub fn visit(&self, f: &mut impl FnMut(&Type)) {
+        f(self);
+
+        match self {
+            Type::Pointer(t) => t.pointee.visit(f),
+            Type::Array(t) => t.element.visit(f),
+            Type::Slice(t) => t.element.visit(f),
+            Type::Function(t) => {
+                for argument in &t.arguments {
+                    argument.visit(f);
+                }
+
+                t.return_type.visit(f);
+            }
+            Type::Tuple(t) => {
+                for field in &t.fields {
+                    field.visit(f);
+                }
+            }
+            Type::Class(t) => {
+                for generic in &t.generics {
+                    generic.visit(f);
+                }
+            }
+            Type::Inferred(_)
+            | Type::Void(_)
+            | Type::Bool(_)
+            | Type::Int(_)
+            | Type::Float(_)
+            | Type::Generic(_) => {}
+        }
+    }
+
+    /// Calls `f` with `self` and every type nested in it.
+    pub fn visit_mut(&mut self, f: &mut impl FnMut(&mut Type)) {
+        f(self);
+
+        match self {
+            Type::Pointer(t) => t.pointee.visit_mut(f),
+            Type::Array(t) => t.element.visit_mut(f),
+            Type::Slice(t) => t.element.visit_mut(f),
+            Type::Function(t) => {
+                for argument in &mut t.arguments {
+                    argument.visit_mut(f);
+                }
+
+                t.return_type.visit_mut(f);
+            }
+            Type::Tuple(t) => {
+                for field in &mut t.fields {
+                    field.visit_mut(f);
+                }
+            }
+            Type::Class(t) => {
+                for generic in &mut t.generics {
+                    generic.visit_mut(f);
+                }
+            }
+            Type::Inferred(_)
+            | Type::Void(_)
+            | Type::Bool(_)
+            | Type::Int(_)
+            | Type::Float(_)
+            | Type::Generic(_) => {}
+        }
+    }
 }
 
 impl From<InferredType> for Type {
@@ -13,7 +13,9 @@ impl<'a> Solver<'a> {
             self.table_mut().register_type(local.id, ty);
         }
 
-        for block in body.blocks.values() {
+        // nested blocks are solved through the expressions containing them,
+        // solving them here too would register their generics twice
+        if let Some(block) = body.blocks.values().next() {
             self.solve_block(body, block)?;
         }
 
@@ -66,6 +68,7 @@ impl<'a> Solver<'a> {
             hir::Expr::Local(expr) => self.solve_local_expr(body, expr)?,
             hir::Expr::Literal(expr) => self.solve_literal_expr(body, expr)?,
             hir::Expr::Function(expr) => self.solve_function_expr(body, expr)?,
+            hir::Expr::Const(expr) => self.solve_const_expr(body, expr)?,
             hir::Expr::ClassInit(expr) => self.solve_init_expr(body, expr)?,
             hir::Expr::Field(expr) => self.solve_field_expr(body, expr)?,
             hir::Expr::As(expr) => self.solve_as_expr(body, expr)?,
@@ -75,6 +78,7 @@ impl<'a> Solver<'a> {
             hir::Expr::Malloc(expr) => self.solve_malloc_expr(body, expr)?,
             hir::Expr::Free(expr) => self.solve_free_expr(body, expr)?,
             hir::Expr::Memcpy(expr) => self.solve_memcpy_expr(body, expr)?,
+            hir::Expr::Wrapping(expr) => self.solve_wrapping_expr(body, expr)?,
             hir::Expr::Call(expr) => self.solve_call_expr(body, expr)?,
             hir::Expr::MethodCall(expr) => self.solve_method_call_expr(body, expr)?,
             hir::Expr::Unary(expr) => self.solve_unary_expr(body, expr)?,
@@ -148,6 +152,15 @@ impl<'a> Solver<'a> {
         Ok(self.table_mut().infer_hir(&ty, &instance))
     }
 
+    pub fn solve_const_expr(
+        &mut self,
+        _body: &hir::Body,
+        expr: &hir::ConstExpr,
+    ) -> Result<InferType, Diagnostic> {
+        let constant = &self.program()[expr.constant];
+        Ok(self.table_mut().infer_hir(&constant.ty, &Instance::empty()))
+    }
+
     pub fn solve_init_expr(
         &mut self,
         body: &hir::Body,
@@ -280,6 +293,18 @@ impl<'a> Solver<'a> {
         Ok(InferType::void(expr.span))
     }
 
+    pub fn solve_wrapping_expr(
+        &mut self,
+        body: &hir::Body,
+        expr: &hir::WrappingExpr,
+    ) -> Result<InferType, Diagnostic> {
+        let lhs = self.solve_expr(body, &body.exprs[expr.lhs])?;
+        let rhs = self.solve_expr(body, &body.exprs[expr.rhs])?;
+        self.unify(lhs.clone(), rhs)?;
+
+        Ok(lhs)
+    }
+
     pub fn solve_call_expr(
         &mut self,
         body: &hir::Body,
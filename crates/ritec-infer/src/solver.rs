@@ -1,7 +1,7 @@
 use std::collections::VecDeque;
 
 use ritec_core::{trace, Ident, Span};
-use ritec_error::Diagnostic;
+use ritec_error::{find_best_match, Diagnostic, ErrorCode, Suggestion};
 use ritec_hir as hir;
 use ritec_mir as mir;
 
@@ -98,6 +98,7 @@ impl<'a> Solver<'a> {
         // if base isn't a class, it can't have fields
         let ItemId::Class(class_id, _) = apply.item else {
             let err = Diagnostic::error("expected a class")
+                .with_code(ErrorCode::E0011)
                 .with_msg_span("found this type", apply.span);
 
             return Err(err);
@@ -108,9 +109,22 @@ impl<'a> Solver<'a> {
 
         // find the field in the class
         let Some(field) = class.find_field(&field) else {
-            let err = Diagnostic::error("field not found")
-                .with_msg_span("found this class", class.span)
-                .with_msg(format!("class has no field `{}`", field));
+            let mut err = Diagnostic::error("field not found")
+                .with_code(ErrorCode::E0008)
+                .with_msg_span(
+                    format!("class `{}` has no field `{}`", class.ident, field),
+                    field.span(),
+                )
+                .with_secondary("class defined here", class.ident.span());
+
+            let fields = class.fields.values().map(|field| field.ident.value());
+            if let Some(candidate) = find_best_match(field.value(), fields) {
+                err.add_suggestion(Suggestion::maybe_incorrect(
+                    format!("did you mean `{}`", candidate),
+                    field.span(),
+                    candidate,
+                ));
+            }
 
             return Err(err);
         };
@@ -146,6 +160,7 @@ impl<'a> Solver<'a> {
         // if base isn't a class, it can't have methods
         let ItemId::Class(class_id, _) = apply.item else {
             let err = Diagnostic::error("invalid method access")
+                .with_code(ErrorCode::E0009)
                 .with_msg_span("method access on non-class type", apply.span);
 
             return Err(err);
@@ -156,8 +171,22 @@ impl<'a> Solver<'a> {
 
         // find the method in the class
         let Some(method) = class.find_method(&method) else {
-            let err = Diagnostic::error("invalid method access")
-                .with_msg_span("method not found in class", apply.span);
+            let mut err = Diagnostic::error("invalid method access")
+                .with_code(ErrorCode::E0009)
+                .with_msg_span(
+                    format!("class `{}` has no method `{}`", class.ident, method),
+                    method.span(),
+                )
+                .with_secondary("class defined here", class.ident.span());
+
+            let methods = class.methods.values().map(|method| method.ident.value());
+            if let Some(candidate) = find_best_match(method.value(), methods) {
+                err.add_suggestion(Suggestion::maybe_incorrect(
+                    format!("did you mean `{}`", candidate),
+                    method.span(),
+                    candidate,
+                ));
+            }
 
             return Err(err);
         };
@@ -189,14 +218,16 @@ impl<'a> Solver<'a> {
         }
 
         if fn_generics.len() != function.generics.params.len() {
-            let err = Diagnostic::error("invalid method access").with_msg_span(
-                format!(
-                    "wrong number of generic arguments, expected {}, got {}",
-                    function.generics.params.len() - class.generics.params.len(),
-                    fn_generics.len() - class.generics.params.len()
-                ),
-                apply.span,
-            );
+            let err = Diagnostic::error("invalid method access")
+                .with_code(ErrorCode::E0009)
+                .with_msg_span(
+                    format!(
+                        "wrong number of generic arguments, expected {}, got {}",
+                        function.generics.params.len() - class.generics.params.len(),
+                        fn_generics.len() - class.generics.params.len()
+                    ),
+                    apply.span,
+                );
 
             return Err(err);
         }
@@ -302,6 +333,7 @@ impl<'a> Solver<'a> {
             (ItemId::Float(_), ItemId::Float(_)) => {}
             _ => {
                 let err = Diagnostic::error("invalid type cast")
+                    .with_code(ErrorCode::E0010)
                     .with_msg_span("type cast on non-class type", Span::DUMMY);
 
                 return Err(err);
@@ -1,5 +1,5 @@
 use ritec_core::trace;
-use ritec_error::Diagnostic;
+use ritec_error::{Diagnostic, ErrorCode};
 
 use crate::{
     Constraint, InferType, InferenceTable, Normalize, TypeApplication, TypeProjection, TypeVariable,
@@ -57,7 +57,7 @@ impl<'a> Unifier<'a> {
         }
 
         if !a.can_unify_with_var(&b) {
-            let err = Diagnostic::error("cannot unify types");
+            let err = Diagnostic::error("cannot unify types").with_code(ErrorCode::E0007);
             return Err(err);
         }
 
@@ -95,12 +95,12 @@ impl<'a> Unifier<'a> {
         b: &TypeApplication,
     ) -> Result<(), Diagnostic> {
         if a.item != b.item {
-            let err = Diagnostic::error("cannot unify types");
+            let err = Diagnostic::error("cannot unify types").with_code(ErrorCode::E0007);
             return Err(err);
         }
 
         if a.arguments.len() != b.arguments.len() {
-            let err = Diagnostic::error("wrong number of arguments");
+            let err = Diagnostic::error("wrong number of arguments").with_code(ErrorCode::E0007);
             return Err(err);
         }
 
@@ -141,7 +141,7 @@ impl<'a> Unifier<'a> {
         }
 
         if !b.can_unify_with_apply(&a) {
-            let err = Diagnostic::error("cannot unify types");
+            let err = Diagnostic::error("cannot unify types").with_code(ErrorCode::E0007);
             return Err(err);
         }
 
@@ -152,7 +152,7 @@ impl<'a> Unifier<'a> {
 
     pub fn unify_var_ty(&mut self, a: &TypeVariable, b: &InferType) -> Result<(), Diagnostic> {
         if !a.can_unify_with(&b) {
-            let err = Diagnostic::error("cannot unify types");
+            let err = Diagnostic::error("cannot unify types").with_code(ErrorCode::E0007);
             return Err(err);
         }
 
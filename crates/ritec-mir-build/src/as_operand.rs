@@ -1,4 +1,5 @@
 use ritec_core::Literal;
+use ritec_hir as hir;
 use ritec_mir as mir;
 
 use crate::{thir, unpack, BlockAnd, FunctionBuilder};
@@ -49,6 +50,23 @@ impl<'a> FunctionBuilder<'a> {
                     BlockAnd::new(block, constant)
                 }
             },
+            thir::Expr::Const(expr) => {
+                let constant = match (expr.value, &expr.ty) {
+                    (hir::ConstValue::Bool(value), _) => mir::Constant::Bool(value),
+                    (hir::ConstValue::Int(value), mir::Type::Int(ty)) => {
+                        mir::Constant::Integer(value as i64, ty.clone())
+                    }
+                    (hir::ConstValue::Float(value), mir::Type::Float(ty)) => {
+                        mir::Constant::Float(value, ty.clone())
+                    }
+                    (hir::ConstValue::Null, mir::Type::Pointer(ty)) => {
+                        mir::Constant::Null(ty.pointee().clone())
+                    }
+                    (value, ty) => unreachable!("constant {} of type {}", value, ty),
+                };
+
+                BlockAnd::new(block, mir::Operand::Constant(constant))
+            }
             thir::Expr::Function(expr) => {
                 let constant = mir::Operand::Constant(mir::Constant::Function(
                     expr.function.cast(),
@@ -64,13 +82,13 @@ impl<'a> FunctionBuilder<'a> {
                     mir::Operand::Constant(mir::Constant::Void)
                 };
 
-                self[block].terminate_return(value);
+                self[block].terminate_return(value, expr.span);
 
                 BlockAnd::new(block, mir::Operand::VOID)
             }
-            thir::Expr::Break(_) => {
+            thir::Expr::Break(expr) => {
                 let break_block = self.break_block.expect("break outside of loop");
-                self[block].terminate_goto(break_block);
+                self[block].terminate_goto(break_block, expr.span);
 
                 BlockAnd::new(block, mir::Operand::VOID)
             }
@@ -83,13 +101,13 @@ impl<'a> FunctionBuilder<'a> {
                 let loop_block = self.new_block();
                 let exit_block = self.new_block();
 
-                self[block].terminate_goto(loop_block);
+                self[block].terminate_goto(loop_block, expr.span);
 
                 let break_block = self.break_block.take();
                 self.break_block = Some(exit_block);
 
                 let block = self.build_block(loop_block, &self.thir[expr.block]);
-                self[block].terminate_goto(loop_block);
+                self[block].terminate_goto(loop_block, expr.span);
 
                 self.break_block = break_block;
 
@@ -105,6 +123,7 @@ impl<'a> FunctionBuilder<'a> {
             | thir::Expr::Malloc(_)
             | thir::Expr::Free(_)
             | thir::Expr::Memcpy(_)
+            | thir::Expr::Wrapping(_)
             | thir::Expr::Call(_)
             | thir::Expr::StaticCall(_)
             | thir::Expr::Unary(_)
@@ -128,21 +147,21 @@ impl<'a> FunctionBuilder<'a> {
         }
 
         if let Some(else_expr) = expr.else_expr {
-            return self.build_if_else_expr(block, condition, expr.then_expr, else_expr);
+            return self.build_if_else_expr(block, condition, expr, else_expr);
         }
 
         let then_block = self.new_block();
-        self.as_value(then_block, &self.thir[expr.then_expr]);
+        let then_end = self.as_value(then_block, &self.thir[expr.then_expr]).block;
 
         let end_block = self.new_block();
-        self[then_block].terminate_goto(end_block);
+        self[then_end].terminate_goto(end_block, expr.span);
 
         let targets = mir::SwitchTargets {
             targets: vec![(1, then_block)],
             default: end_block,
         };
 
-        self[block].terminate_switch(condition, targets);
+        self[block].terminate_switch(condition, targets, expr.span);
 
         BlockAnd::new(end_block, mir::Operand::VOID)
     }
@@ -151,29 +170,29 @@ impl<'a> FunctionBuilder<'a> {
         &mut self,
         block: mir::BlockId,
         condition: mir::Operand,
-        then_expr: thir::ExprId,
+        expr: &thir::IfExpr,
         else_expr: thir::ExprId,
     ) -> BlockAnd<mir::Operand> {
         let then_block = self.new_block();
-        self.as_value(then_block, &self.thir[then_expr]);
+        let then_end = self.as_value(then_block, &self.thir[expr.then_expr]).block;
 
         let else_block = self.new_block();
-        self.as_value(else_block, &self.thir[else_expr]);
+        let else_end = self.as_value(else_block, &self.thir[else_expr]).block;
 
         let targets = mir::SwitchTargets {
             targets: vec![(1, then_block)],
             default: else_block,
         };
 
-        self[block].terminate_switch(condition, targets);
+        self[block].terminate_switch(condition, targets, expr.span);
 
-        if self[then_block].is_terminated() && self[else_block].is_terminated() {
+        if self[then_end].is_terminated() && self[else_end].is_terminated() {
             return BlockAnd::new(block, mir::Operand::VOID);
         }
 
         let end_block = self.new_block();
-        self[then_block].terminate_goto(end_block);
-        self[else_block].terminate_goto(end_block);
+        self[then_end].terminate_goto(end_block, expr.span);
+        self[else_end].terminate_goto(end_block, expr.span);
 
         BlockAnd::new(end_block, mir::Operand::VOID)
     }
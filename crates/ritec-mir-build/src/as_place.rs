@@ -17,7 +17,7 @@ impl<'a> FunctionBuilder<'a> {
             thir::Expr::ClassInit(expr) => {
                 let class = &self.classes[expr.class.class.cast()];
 
-                let place = self.push_temp(expr.ty.clone());
+                let place = self.push_temp(expr.ty.clone(), expr.span);
 
                 let mut fields = Vec::new();
 
@@ -29,8 +29,9 @@ impl<'a> FunctionBuilder<'a> {
                     let mut place = place.clone();
                     place.proj.push(mir::Projection::Field(*field));
 
-                    let value = unpack!(block = self.as_value(block, &self.thir[*init]));
-                    self[block].push_assign(place, value);
+                    let init = &self.thir[*init];
+                    let value = unpack!(block = self.as_value(block, init));
+                    self[block].push_assign(place, value, init.span());
 
                     fields.push(*field);
                 }
@@ -53,7 +54,7 @@ impl<'a> FunctionBuilder<'a> {
                     ));
                     let value = mir::Value::Call(init, vec![]);
 
-                    self[block].push_assign(place, value);
+                    self[block].push_assign(place, value, expr.span);
                 }
 
                 BlockAnd::new(block, place)
@@ -65,22 +66,26 @@ impl<'a> FunctionBuilder<'a> {
                 BlockAnd::new(block, place)
             }
             thir::Expr::Unary(expr) if expr.operator == UnaryOp::Deref => {
-                let mut place = unpack!(block = self.as_place(block, &self.thir[expr.operand]));
+                let operand = &self.thir[expr.operand];
+                let mut place = unpack!(block = self.as_place(block, operand));
+                block = self.check_deref(block, &place, operand.ty(), expr.span);
                 place.proj.push(mir::Projection::Deref);
                 BlockAnd::new(block, place)
             }
             thir::Expr::Assign(expr) => {
-                let temp = self.push_temp(expr.ty.clone());
+                let temp = self.push_temp(expr.ty.clone(), expr.span);
                 let place = unpack!(block = self.as_place(block, &self.thir[expr.lhs]));
                 let value = unpack!(block = self.as_operand(block, &self.thir[expr.rhs]));
 
-                self[block].push_assign(temp.clone(), mir::Operand::Move(place.clone()));
-                self[block].push_assign(place, value);
+                let moved = mir::Operand::Move(place.clone());
+                self[block].push_assign(temp.clone(), moved, expr.span);
+                self[block].push_assign(place, value, expr.span);
 
                 BlockAnd::new(block, temp)
             }
             thir::Expr::Literal(_)
             | thir::Expr::Function(_)
+            | thir::Expr::Const(_)
             | thir::Expr::As(_)
             | thir::Expr::Bitcast(_)
             | thir::Expr::Sizeof(_)
@@ -88,6 +93,7 @@ impl<'a> FunctionBuilder<'a> {
             | thir::Expr::Malloc(_)
             | thir::Expr::Free(_)
             | thir::Expr::Memcpy(_)
+            | thir::Expr::Wrapping(_)
             | thir::Expr::Call(_)
             | thir::Expr::StaticCall(_)
             | thir::Expr::Unary(_)
@@ -98,8 +104,8 @@ impl<'a> FunctionBuilder<'a> {
             | thir::Expr::If(_)
             | thir::Expr::Loop(_) => {
                 let value = unpack!(block = self.as_value(block, expr));
-                let temp = self.push_temp(expr.ty().clone());
-                self[block].push_assign(temp.clone(), value);
+                let temp = self.push_temp(expr.ty().clone(), expr.span());
+                self[block].push_assign(temp.clone(), value, expr.span());
 
                 BlockAnd::new(block, temp)
             }
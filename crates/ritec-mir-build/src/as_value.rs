@@ -46,11 +46,11 @@ impl<'a> FunctionBuilder<'a> {
                 BlockAnd::new(block, mir::Value::Intrinsic(bitcast))
             }
             thir::Expr::Sizeof(expr) => {
-                let size = mir::Intrinsic::Sizeof(expr.ty.clone());
+                let size = mir::Intrinsic::Sizeof(expr.item.clone());
                 BlockAnd::new(block, mir::Value::Intrinsic(size))
             }
             thir::Expr::Alignof(expr) => {
-                let align = mir::Intrinsic::Alignof(expr.ty.clone());
+                let align = mir::Intrinsic::Alignof(expr.item.clone());
                 BlockAnd::new(block, mir::Value::Intrinsic(align))
             }
             thir::Expr::Malloc(expr) => {
@@ -70,6 +70,20 @@ impl<'a> FunctionBuilder<'a> {
                 let memcpy = mir::Intrinsic::Memcpy(dst, src, size);
                 BlockAnd::new(block, mir::Value::Intrinsic(memcpy))
             }
+            thir::Expr::Wrapping(expr) => {
+                let lhs = unpack!(block = self.as_operand(block, &self.thir[expr.lhs]));
+                let rhs = unpack!(block = self.as_operand(block, &self.thir[expr.rhs]));
+
+                let op = match expr.operator {
+                    BinOp::Add => mir::BinOp::IntAdd,
+                    BinOp::Sub => mir::BinOp::IntSub,
+                    BinOp::Mul => mir::BinOp::IntMul,
+                    _ => unreachable!("wrapping {}", expr.operator),
+                };
+
+                let wrapping = mir::Intrinsic::Wrapping(op, lhs, rhs);
+                BlockAnd::new(block, mir::Value::Intrinsic(wrapping))
+            }
             thir::Expr::Unary(expr) if expr.operator == UnaryOp::Ref => {
                 let place = unpack!(block = self.as_place(block, &self.thir[expr.operand]));
                 BlockAnd::new(block, mir::Value::Address(place))
@@ -82,6 +96,7 @@ impl<'a> FunctionBuilder<'a> {
                 };
 
                 let value = unpack!(block = self.as_operand(block, &self.thir[expr.operand]));
+                block = self.check_neg(block, &value, &expr.ty, expr.span);
                 BlockAnd::new(block, mir::Value::UnaryOp(op, value))
             }
             thir::Expr::Unary(expr) if expr.operator == UnaryOp::Not => {
@@ -92,7 +107,8 @@ impl<'a> FunctionBuilder<'a> {
                 let lhs = unpack!(block = self.as_operand(block, &self.thir[expr.lhs]));
                 let rhs = unpack!(block = self.as_operand(block, &self.thir[expr.rhs]));
 
-                let op = match self.thir[expr.lhs].ty() {
+                let ty = self.thir[expr.lhs].ty().clone();
+                let op = match ty {
                     mir::Type::Int(ref t) => match expr.operator {
                         BinOp::Add => mir::BinOp::IntAdd,
                         BinOp::Sub => mir::BinOp::IntSub,
@@ -125,6 +141,7 @@ impl<'a> FunctionBuilder<'a> {
                     _ => unreachable!("{}", expr.ty),
                 };
 
+                block = self.check_binary_op(block, op, &lhs, &rhs, &ty, expr.span);
                 BlockAnd::new(block, mir::Value::BinaryOp(op, lhs, rhs))
             }
             thir::Expr::Call(expr) => {
@@ -153,6 +170,7 @@ impl<'a> FunctionBuilder<'a> {
             thir::Expr::Local(_)
             | thir::Expr::Literal(_)
             | thir::Expr::Function(_)
+            | thir::Expr::Const(_)
             | thir::Expr::ClassInit(_)
             | thir::Expr::Field(_)
             | thir::Expr::Unary(_)
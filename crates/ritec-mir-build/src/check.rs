@@ -0,0 +1,144 @@
+use ritec_core::Span;
+use ritec_mir as mir;
+
+use crate::FunctionBuilder;
+
+impl<'a> FunctionBuilder<'a> {
+    /// Ends `block` with an assert that `cond` is `expected`, returns the
+    /// block execution continues in.
+    pub fn push_assert(
+        &mut self,
+        block: mir::BlockId,
+        cond: mir::Operand,
+        expected: bool,
+        message: mir::AssertMessage,
+        span: Span,
+    ) -> mir::BlockId {
+        let target = self.new_block();
+        self[block].terminate_assert(cond, expected, message, target, span);
+        target
+    }
+
+    /// Checks that `op` of `lhs` and `rhs` neither overflows nor divides by
+    /// zero, if checks are enabled and the operands are integers.
+    pub fn check_binary_op(
+        &mut self,
+        mut block: mir::BlockId,
+        op: mir::BinOp,
+        lhs: &mir::Operand,
+        rhs: &mir::Operand,
+        ty: &mir::Type,
+        span: Span,
+    ) -> mir::BlockId {
+        let mir::Type::Int(ref int) = *ty else {
+            return block;
+        };
+
+        let checked = matches!(
+            op,
+            mir::BinOp::IntAdd
+                | mir::BinOp::IntSub
+                | mir::BinOp::IntMul
+                | mir::BinOp::IntDivSigned
+                | mir::BinOp::IntDivUnsigned
+        );
+
+        if !self.checks || !checked {
+            return block;
+        }
+
+        let lhs = copy(lhs);
+        let rhs = copy(rhs);
+
+        if let mir::BinOp::IntDivSigned | mir::BinOp::IntDivUnsigned = op {
+            let zero = mir::Operand::Constant(mir::Constant::Integer(0, int.clone()));
+            let is_zero = mir::Value::BinaryOp(mir::BinOp::IntEq, rhs.clone(), zero);
+
+            let cond = self.push_temp(mir::Type::Bool, span);
+            self[block].push_assign(cond.clone(), is_zero, span);
+
+            let message = mir::AssertMessage::DivisionByZero;
+            block = self.push_assert(block, mir::Operand::Move(cond), false, message, span);
+        }
+
+        // unsigned division can't overflow
+        if let mir::BinOp::IntDivUnsigned = op {
+            return block;
+        }
+
+        let overflows = mir::Intrinsic::Overflows(op, lhs, rhs);
+        let cond = self.push_temp(mir::Type::Bool, span);
+        self[block].push_assign(cond.clone(), mir::Value::Intrinsic(overflows), span);
+
+        let message = mir::AssertMessage::Overflow(op);
+        self.push_assert(block, mir::Operand::Move(cond), false, message, span)
+    }
+
+    /// Checks that negating `operand` doesn't overflow, which only the
+    /// minimum of a signed integer does, if checks are enabled.
+    pub fn check_neg(
+        &mut self,
+        block: mir::BlockId,
+        operand: &mir::Operand,
+        ty: &mir::Type,
+        span: Span,
+    ) -> mir::BlockId {
+        let mir::Type::Int(ref int) = *ty else {
+            return block;
+        };
+
+        if !self.checks || !int.signed {
+            return block;
+        }
+
+        // `-x` overflows exactly when `0 - x` does
+        let zero = mir::Operand::Constant(mir::Constant::Integer(0, int.clone()));
+        let overflows = mir::Intrinsic::Overflows(mir::BinOp::IntSub, zero, copy(operand));
+        let cond = self.push_temp(mir::Type::Bool, span);
+        self[block].push_assign(cond.clone(), mir::Value::Intrinsic(overflows), span);
+
+        let message = mir::AssertMessage::NegOverflow;
+        self.push_assert(block, mir::Operand::Move(cond), false, message, span)
+    }
+
+    /// Checks that the pointer stored in `place` isn't null before it is
+    /// dereferenced, if checks are enabled.
+    pub fn check_deref(
+        &mut self,
+        block: mir::BlockId,
+        place: &mir::Place,
+        ty: &mir::Type,
+        span: Span,
+    ) -> mir::BlockId {
+        let mir::Type::Pointer(ref pointer_type) = *ty else {
+            return block;
+        };
+
+        if !self.checks {
+            return block;
+        }
+
+        let pointer = mir::Operand::Copy(place.clone());
+        let ptrtoint = mir::Intrinsic::PtrToInt(pointer, pointer_type.clone(), mir::IntType::USIZE);
+
+        let address = self.push_temp(mir::Type::USIZE, span);
+        self[block].push_assign(address.clone(), mir::Value::Intrinsic(ptrtoint), span);
+
+        let null = mir::Operand::Constant(mir::Constant::Integer(0, mir::IntType::USIZE));
+        let is_null = mir::Value::BinaryOp(mir::BinOp::IntEq, mir::Operand::Move(address), null);
+
+        let cond = self.push_temp(mir::Type::Bool, span);
+        self[block].push_assign(cond.clone(), is_null, span);
+
+        let message = mir::AssertMessage::NullDeref;
+        self.push_assert(block, mir::Operand::Move(cond), false, message, span)
+    }
+}
+
+/// Returns `operand` with moves turned into copies, so it can be used again.
+fn copy(operand: &mir::Operand) -> mir::Operand {
+    match operand {
+        mir::Operand::Move(place) => mir::Operand::Copy(place.clone()),
+        operand => operand.clone(),
+    }
+}
@@ -1,6 +1,10 @@
-use std::ops::{Index, IndexMut};
+use std::{
+    collections::HashSet,
+    ops::{Index, IndexMut},
+};
 
-use ritec_core::Arena;
+use ritec_core::{Arena, Span};
+use ritec_error::{Diagnostic, Emitter, ErrorCode};
 use ritec_hir as hir;
 use ritec_mir as mir;
 
@@ -30,49 +34,112 @@ pub struct FunctionBuilder<'a> {
     pub thir: &'a thir::Body,
     pub mir: mir::Body,
     pub classes: &'a Arena<hir::Class>,
+    pub return_type: &'a mir::Type,
+    /// Whether runtime checks for overflow, division by zero and null
+    /// dereferences are inserted.
+    pub checks: bool,
     pub break_block: Option<mir::BlockId>,
+    /// The block every statement starts in, and its span.
+    pub stmts: Vec<(mir::BlockId, Span)>,
+    pub span: Span,
 }
 
 impl<'a> FunctionBuilder<'a> {
-    pub fn new(thir: &'a thir::Body, classes: &'a Arena<hir::Class>) -> Self {
+    pub fn new(
+        thir: &'a thir::Body,
+        classes: &'a Arena<hir::Class>,
+        return_type: &'a mir::Type,
+        span: Span,
+    ) -> Self {
         Self {
             thir,
             mir: mir::Body::new(),
             classes,
+            return_type,
+            checks: false,
             break_block: None,
+            stmts: Vec::new(),
+            span,
         }
     }
 
-    pub fn build(mut self) -> mir::Body {
+    /// Builds the body, warning about unreachable statements, returns an
+    /// error if a function returning a value can reach its end.
+    pub fn build(mut self, emitter: &mut dyn Emitter) -> Result<mir::Body, Diagnostic> {
         self.mir.locals = self.thir.locals.clone();
 
         let entry_block = self.thir.blocks.values().next().unwrap();
         let mut block = self.mir.blocks.push(mir::Block::new());
         block = self.build_block(block, entry_block);
 
-        if !self[block].is_terminated() {
-            self.drop_stack(block);
-            self[block].terminate(mir::Terminator::Return(mir::Operand::VOID));
+        let reachable: HashSet<_> = self.mir.reverse_postorder().into_iter().collect();
+
+        // only the first of a run of unreachable statements is reported
+        let mut previous_reachable = true;
+        for &(stmt_block, span) in &self.stmts {
+            let is_reachable = reachable.contains(&stmt_block);
+
+            if previous_reachable && !is_reachable {
+                let warning = Diagnostic::warning("unreachable statement")
+                    .with_msg_span("this statement can never be executed", span);
+                emitter.emit(warning);
+            }
+
+            previous_reachable = is_reachable;
         }
 
-        self.mir.clone()
-    }
+        if reachable.contains(&block) && !self[block].is_terminated() {
+            let span = self.span.shrink_to_hi();
+
+            if !self.return_type.is_void() {
+                // point at the closing brace of the function
+                let brace = if span.is_dummy() {
+                    span
+                } else {
+                    span.expand_lo(1)
+                };
+
+                let err = Diagnostic::error("not all paths return a value")
+                    .with_code(ErrorCode::E0017)
+                    .with_msg_span(
+                        format!("expected a return of `{}` before the end", self.return_type),
+                        brace,
+                    );
+
+                return Err(err);
+            }
+
+            self.drop_stack(block, span);
+            self[block].terminate_return(mir::Operand::VOID, span);
+        }
 
-    pub fn build_block(&mut self, mut block_id: mir::BlockId, block: &thir::Block) -> mir::BlockId {
-        if !self[block_id].is_empty() {
-            block_id = self.mir.blocks.push(mir::Block::new());
+        for block in self.mir.blocks.keys().collect::<Vec<_>>() {
+            if !reachable.contains(&block) {
+                self.mir.blocks.remove(block);
+            }
         }
 
+        Ok(self.mir)
+    }
+
+    pub fn build_block(&mut self, mut block_id: mir::BlockId, block: &thir::Block) -> mir::BlockId {
         for stmt in block.stmts.iter() {
+            // statements after a `return` or `break` start a block of their
+            // own, which is unreachable
+            if self[block_id].is_terminated() {
+                block_id = self.new_block();
+            }
+
+            self.stmts.push((block_id, stmt.span()));
             block_id = self.build_stmt(block_id, stmt);
         }
 
         block_id
     }
 
-    pub fn drop_stack(&mut self, block: mir::BlockId) {
+    pub fn drop_stack(&mut self, block: mir::BlockId, span: Span) {
         for id in self.thir.locals.keys() {
-            self[block].push_drop(mir::Operand::Move(mir::Place::local(id)));
+            self[block].push_drop(mir::Operand::Move(mir::Place::local(id)), span);
         }
     }
 
@@ -80,8 +147,8 @@ impl<'a> FunctionBuilder<'a> {
         self.mir.blocks.push(mir::Block::new())
     }
 
-    pub fn push_temp(&mut self, ty: mir::Type) -> mir::Place {
-        let local = self.mir.locals.push(mir::Local { ident: None, ty });
+    pub fn push_temp(&mut self, ty: mir::Type, span: Span) -> mir::Place {
+        let local = self.mir.locals.push(mir::Local::new(ty, span.into()));
         mir::Place {
             local: local.cast(),
             proj: vec![],
@@ -1,6 +1,7 @@
 mod as_operand;
 mod as_place;
 mod as_value;
+mod check;
 mod error;
 mod function_builder;
 mod program_builder;
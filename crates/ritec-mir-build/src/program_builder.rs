@@ -1,4 +1,4 @@
-use ritec_error::Diagnostic;
+use ritec_error::{Diagnostic, Emitter};
 use ritec_hir as hir;
 use ritec_infer::Solver;
 use ritec_mir as mir;
@@ -7,24 +7,49 @@ use crate::{build_type, thir, FunctionBuilder};
 
 pub struct ProgramBuilder<'a> {
     pub hir: &'a hir::Program,
+    pub paths: hir::ItemPaths,
     pub mir: mir::Program,
+    /// Whether runtime checks are inserted, see [`FunctionBuilder::checks`].
+    pub checks: bool,
 }
 
 impl<'a> ProgramBuilder<'a> {
     pub fn new(program: &'a hir::Program) -> Self {
         Self {
             hir: program,
+            paths: program.item_paths(),
             mir: mir::Program::new(),
+            checks: false,
         }
     }
 
-    pub fn build(mut self) -> Result<mir::Program, Diagnostic> {
+    /// Builds the MIR of every class and function, warnings are written to
+    /// `emitter`.
+    pub fn build(mut self, emitter: &mut dyn Emitter) -> Result<mir::Program, Diagnostic> {
         for (id, class) in self.hir.classes.iter() {
             self.build_class(id, class)?;
         }
 
+        // build every function, so each one missing a return is reported
+        let mut errors = Vec::new();
         for (id, function) in self.hir.functions.iter() {
-            self.build_function(id, function)?;
+            if let Err(err) = self.build_function(id, function, emitter) {
+                errors.push(err);
+            }
+        }
+
+        if let Some(err) = errors.pop() {
+            for err in errors {
+                emitter.emit(err);
+            }
+
+            return Err(err);
+        }
+
+        self.mir.set_class_paths();
+
+        if cfg!(debug_assertions) {
+            mir::validate(&self.mir)?;
         }
 
         Ok(self.mir)
@@ -51,6 +76,7 @@ impl<'a> ProgramBuilder<'a> {
 
         let class = mir::Class {
             ident: class.ident.clone(),
+            path: self.paths.class(id).to_vec(),
             generics,
             fields,
         };
@@ -60,11 +86,12 @@ impl<'a> ProgramBuilder<'a> {
         Ok(())
     }
 
-    pub fn build_function(
-        &mut self,
-        id: hir::FunctionId,
+    /// Infers the types of `function` and builds its THIR, returns the body
+    /// and the resolved return type.
+    pub fn build_thir(
+        &self,
         function: &hir::Function,
-    ) -> Result<(), Diagnostic> {
+    ) -> Result<(thir::Body, mir::Type), Diagnostic> {
         let mut solver = Solver::new(self.hir);
         solver.set_return_type(function.return_type.clone());
         solver.solve_body(&function.body)?;
@@ -74,8 +101,21 @@ impl<'a> ProgramBuilder<'a> {
         let mut thir_builder = thir::ThirBuilder::new(&self.hir, &function.body, solver.finish()?)?;
         let thir = thir_builder.build()?;
 
-        let function_builder = FunctionBuilder::new(&thir, &self.hir.classes);
-        let mir = function_builder.build();
+        Ok((thir, return_type))
+    }
+
+    pub fn build_function(
+        &mut self,
+        id: hir::FunctionId,
+        function: &hir::Function,
+        emitter: &mut dyn Emitter,
+    ) -> Result<(), Diagnostic> {
+        let (thir, return_type) = self.build_thir(function)?;
+
+        let mut function_builder =
+            FunctionBuilder::new(&thir, &self.hir.classes, &return_type, function.span);
+        function_builder.checks = self.checks;
+        let mir = function_builder.build(emitter)?;
 
         let mut params = Vec::new();
         for param in &function.generics.params {
@@ -97,10 +137,14 @@ impl<'a> ProgramBuilder<'a> {
 
         let function = mir::Function {
             ident: function.ident.clone(),
+            path: self.paths.function(id).to_vec(),
+            class: self.paths.method_class(id).map(|class| class.cast()),
             generics: params,
             arguments,
             return_type,
             body: mir,
+            inline: function.inline,
+            export: function.export,
         };
 
         self.mir.functions.insert(id.cast(), function);
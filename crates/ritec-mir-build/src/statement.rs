@@ -17,7 +17,7 @@ impl<'a> FunctionBuilder<'a> {
     ) -> mir::BlockId {
         if let Some(init) = stmt.init {
             let init = unpack!(block = self.as_value(block, &self.thir.exprs[init]));
-            self[block].push_assign(stmt.local, init);
+            self[block].push_assign(stmt.local, init, stmt.span);
         }
 
         block
@@ -32,7 +32,7 @@ impl<'a> FunctionBuilder<'a> {
         let value = unpack!(block = self.as_value(block, expr));
 
         if !expr.ty().is_void() || matches!(expr, thir::Expr::Call(_) | thir::Expr::StaticCall(_)) {
-            self[block].push_drop(value);
+            self[block].push_drop(value, stmt.span);
         }
 
         block
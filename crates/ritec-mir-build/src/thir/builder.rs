@@ -30,10 +30,8 @@ impl<'a> ThirBuilder<'a> {
 
     pub fn build(&mut self) -> Result<thir::Body, Diagnostic> {
         for (local_id, local) in self.hir.locals.iter() {
-            let local = mir::Local {
-                ident: Some(local.ident.clone()),
-                ty: self.table.resolve_mir(local.id)?,
-            };
+            let ty = self.table.resolve_mir(local.id)?;
+            let local = mir::Local::with_ident(local.ident.clone(), ty);
 
             self.thir.locals.insert(local_id.cast(), local);
         }
@@ -145,6 +143,7 @@ impl<'a> ThirBuilder<'a> {
             hir::Expr::Local(expr) => self.build_local_expr(expr)?,
             hir::Expr::Literal(expr) => self.build_literal_expr(expr)?,
             hir::Expr::Function(expr) => self.build_function_expr(expr)?,
+            hir::Expr::Const(expr) => self.build_const_expr(expr)?,
             hir::Expr::ClassInit(expr) => self.build_init_expr(expr)?,
             hir::Expr::Field(expr) => self.build_field_expr(expr)?,
             hir::Expr::As(expr) => self.build_as_expr(expr)?,
@@ -154,6 +153,7 @@ impl<'a> ThirBuilder<'a> {
             hir::Expr::Malloc(expr) => self.build_malloc_expr(expr)?,
             hir::Expr::Free(expr) => self.build_free_expr(expr)?,
             hir::Expr::Memcpy(expr) => self.build_memcpy_expr(expr)?,
+            hir::Expr::Wrapping(expr) => self.build_wrapping_expr(expr)?,
             hir::Expr::Call(expr) => self.build_call_expr(expr)?,
             hir::Expr::MethodCall(expr) => self.build_method_call_expr(expr)?,
             hir::Expr::Unary(expr) => self.build_unary_expr(expr)?,
@@ -212,6 +212,16 @@ impl<'a> ThirBuilder<'a> {
         Ok(thir::Expr::Function(expr))
     }
 
+    pub fn build_const_expr(&mut self, expr: &hir::ConstExpr) -> Result<thir::Expr, Diagnostic> {
+        let constant = &self.program[expr.constant];
+
+        Ok(thir::Expr::Const(thir::ConstExpr {
+            value: constant.value.expect("constant not evaluated"),
+            ty: self.table.resolve_mir(expr.id)?,
+            span: expr.span,
+        }))
+    }
+
     pub fn build_init_expr(&mut self, expr: &hir::ClassInitExpr) -> Result<thir::Expr, Diagnostic> {
         let ty = self.table.resolve_mir(expr.id)?;
 
@@ -332,6 +342,21 @@ impl<'a> ThirBuilder<'a> {
         Ok(thir::Expr::Memcpy(expr))
     }
 
+    pub fn build_wrapping_expr(
+        &mut self,
+        expr: &hir::WrappingExpr,
+    ) -> Result<thir::Expr, Diagnostic> {
+        let expr = thir::WrappingExpr {
+            operator: expr.operator,
+            lhs: self.build_expr(&self.hir[expr.lhs])?,
+            rhs: self.build_expr(&self.hir[expr.rhs])?,
+            ty: self.table.resolve_mir(expr.id)?,
+            span: expr.span,
+        };
+
+        Ok(thir::Expr::Wrapping(expr))
+    }
+
     pub fn build_call_expr(&mut self, expr: &hir::CallExpr) -> Result<thir::Expr, Diagnostic> {
         let callee = self.build_expr(&self.hir[expr.callee])?;
 
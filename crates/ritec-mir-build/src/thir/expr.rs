@@ -1,5 +1,5 @@
 use ritec_core::{BinOp, Id, Literal, Span, UnaryOp};
-use ritec_hir::FunctionId;
+use ritec_hir::{ConstValue, FunctionId};
 use ritec_mir::{ClassType, FieldId, LocalId, Type};
 
 use super::BlockId;
@@ -11,6 +11,7 @@ pub enum Expr {
     Local(LocalExpr),
     Literal(LiteralExpr),
     Function(FunctionExpr),
+    Const(ConstExpr),
     ClassInit(ClassInitExpr),
     Field(FieldExpr),
     As(AsExpr),
@@ -20,6 +21,7 @@ pub enum Expr {
     Malloc(MallocExpr),
     Free(FreeExpr),
     Memcpy(MemcpyExpr),
+    Wrapping(WrappingExpr),
     Call(CallExpr),
     StaticCall(StaticCallExpr),
     Unary(UnaryExpr),
@@ -38,6 +40,7 @@ impl Expr {
             Expr::Local(expr) => &expr.ty,
             Expr::Literal(expr) => &expr.ty,
             Expr::Function(expr) => &expr.ty,
+            Expr::Const(expr) => &expr.ty,
             Expr::ClassInit(expr) => &expr.ty,
             Expr::Field(expr) => &expr.ty,
             Expr::As(expr) => &expr.ty,
@@ -47,6 +50,7 @@ impl Expr {
             Expr::Malloc(expr) => &expr.ty,
             Expr::Free(expr) => &expr.ty,
             Expr::Memcpy(expr) => &expr.ty,
+            Expr::Wrapping(expr) => &expr.ty,
             Expr::Call(expr) => &expr.ty,
             Expr::StaticCall(expr) => &expr.ty,
             Expr::Unary(expr) => &expr.ty,
@@ -65,6 +69,7 @@ impl Expr {
             Expr::Local(expr) => expr.span,
             Expr::Literal(expr) => expr.span,
             Expr::Function(expr) => expr.span,
+            Expr::Const(expr) => expr.span,
             Expr::ClassInit(expr) => expr.span,
             Expr::Field(expr) => expr.span,
             Expr::As(expr) => expr.span,
@@ -74,6 +79,7 @@ impl Expr {
             Expr::Malloc(expr) => expr.span,
             Expr::Free(expr) => expr.span,
             Expr::Memcpy(expr) => expr.span,
+            Expr::Wrapping(expr) => expr.span,
             Expr::Call(expr) => expr.span,
             Expr::StaticCall(expr) => expr.span,
             Expr::Unary(expr) => expr.span,
@@ -110,6 +116,13 @@ pub struct FunctionExpr {
     pub span: Span,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConstExpr {
+    pub value: ConstValue,
+    pub ty: Type,
+    pub span: Span,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct ClassInitExpr {
     pub class: ClassType,
@@ -179,6 +192,15 @@ pub struct MemcpyExpr {
     pub span: Span,
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct WrappingExpr {
+    pub operator: BinOp,
+    pub lhs: ExprId,
+    pub rhs: ExprId,
+    pub ty: Type,
+    pub span: Span,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct CallExpr {
     pub callee: ExprId,
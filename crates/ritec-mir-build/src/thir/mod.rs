@@ -1,9 +1,11 @@
 mod builder;
 mod expr;
+mod print;
 mod stmt;
 
 pub use builder::*;
 pub use expr::*;
+pub use print::*;
 pub use stmt::*;
 
 use std::ops::Index;
@@ -0,0 +1,289 @@
+use std::fmt::{self, Display, Formatter};
+
+use ritec_hir as hir;
+use ritec_mir as mir;
+
+use super::{BlockId, Body, Expr, ExprId, Stmt};
+
+fn indent(f: &mut Formatter<'_>, depth: usize) -> fmt::Result {
+    for _ in 0..depth {
+        write!(f, "\t")?;
+    }
+
+    Ok(())
+}
+
+fn generics(types: &[mir::Type]) -> String {
+    if types.is_empty() {
+        return String::new();
+    }
+
+    let types: Vec<_> = types.iter().map(mir::Type::to_string).collect();
+    format!("<{}>", types.join(", "))
+}
+
+/// Prints the THIR [`Body`] of a function, using the HIR [`Program`](hir::Program)
+/// it was built from to name functions and fields.
+pub struct ThirPrinter<'a> {
+    pub program: &'a hir::Program,
+    pub function: &'a hir::Function,
+    pub body: &'a Body,
+    pub return_type: &'a mir::Type,
+}
+
+impl<'a> ThirPrinter<'a> {
+    pub fn new(
+        program: &'a hir::Program,
+        function: &'a hir::Function,
+        body: &'a Body,
+        return_type: &'a mir::Type,
+    ) -> Self {
+        Self {
+            program,
+            function,
+            body,
+            return_type,
+        }
+    }
+
+    fn field_name(&self, class: ExprId, field: mir::FieldId) -> String {
+        let mir::Type::Class(ref class) = self.body[class].ty() else {
+            return field.as_raw_index().to_string();
+        };
+
+        let class = &self.program[class.class.cast::<hir::Class>()];
+        class[field.cast::<hir::Field>()].ident.to_string()
+    }
+
+    fn block(&self, f: &mut Formatter<'_>, block: BlockId, depth: usize) -> fmt::Result {
+        writeln!(f, "{{")?;
+
+        for stmt in self.body[block].stmts.iter() {
+            indent(f, depth + 1)?;
+            self.stmt(f, stmt, depth + 1)?;
+            writeln!(f)?;
+        }
+
+        indent(f, depth)?;
+        write!(f, "}}")
+    }
+
+    fn stmt(&self, f: &mut Formatter<'_>, stmt: &Stmt, depth: usize) -> fmt::Result {
+        match stmt {
+            Stmt::Let(stmt) => {
+                write!(f, "let _{}", stmt.local.as_raw_index())?;
+
+                if let Some(init) = stmt.init {
+                    write!(f, " = ")?;
+                    self.expr(f, init, depth)?;
+                }
+            }
+            Stmt::Expr(stmt) => self.expr(f, stmt.expr, depth)?,
+        }
+
+        write!(f, ";")
+    }
+
+    fn exprs(&self, f: &mut Formatter<'_>, exprs: &[ExprId], depth: usize) -> fmt::Result {
+        for (i, &expr) in exprs.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+
+            self.expr(f, expr, depth)?;
+        }
+
+        Ok(())
+    }
+
+    /// Prints the receiver of a field access or method call, parenthesizing
+    /// unary expressions so `(*a).b` isn't printed as `*a.b`.
+    fn receiver(&self, f: &mut Formatter<'_>, expr: ExprId, depth: usize) -> fmt::Result {
+        if let Expr::Unary(_) = self.body[expr] {
+            write!(f, "(")?;
+            self.expr(f, expr, depth)?;
+            write!(f, ")")
+        } else {
+            self.expr(f, expr, depth)
+        }
+    }
+
+    fn expr(&self, f: &mut Formatter<'_>, expr: ExprId, depth: usize) -> fmt::Result {
+        match &self.body[expr] {
+            Expr::Local(expr) => write!(f, "_{}", expr.local.as_raw_index()),
+            Expr::Literal(expr) => write!(f, "{}", expr.literal),
+            Expr::Function(expr) => write!(
+                f,
+                "fn[{}]{}",
+                expr.function.as_raw_index(),
+                generics(&expr.generics)
+            ),
+            Expr::Const(expr) => write!(f, "const {}", expr.value),
+            Expr::ClassInit(expr) => {
+                let class = &self.program[expr.class.class.cast::<hir::Class>()];
+                write!(f, "{} {{", expr.class)?;
+
+                for (i, (field, init)) in expr.fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+
+                    write!(f, " {}: ", class[field.cast::<hir::Field>()].ident)?;
+                    self.expr(f, *init, depth)?;
+                }
+
+                if !expr.fields.is_empty() {
+                    write!(f, " ")?;
+                }
+
+                write!(f, "}}")
+            }
+            Expr::Field(expr) => {
+                self.receiver(f, expr.class, depth)?;
+                write!(f, ".{}", self.field_name(expr.class, expr.field))
+            }
+            Expr::As(expr) => {
+                write!(f, "(")?;
+                self.expr(f, expr.expr, depth)?;
+                write!(f, " as {})", expr.into)
+            }
+            Expr::Bitcast(expr) => {
+                write!(f, "bitcast(")?;
+                self.expr(f, expr.expr, depth)?;
+                write!(f, " as {})", expr.ty)
+            }
+            Expr::Sizeof(expr) => write!(f, "sizeof({})", expr.item),
+            Expr::Alignof(expr) => write!(f, "alignof({})", expr.item),
+            Expr::Malloc(expr) => {
+                write!(f, "malloc(")?;
+                self.expr(f, expr.count, depth)?;
+                write!(f, ", {})", expr.item)
+            }
+            Expr::Free(expr) => {
+                write!(f, "free(")?;
+                self.expr(f, expr.expr, depth)?;
+                write!(f, ")")
+            }
+            Expr::Memcpy(expr) => {
+                write!(f, "memcpy(")?;
+                self.exprs(f, &[expr.dst, expr.src, expr.size], depth)?;
+                write!(f, ")")
+            }
+            Expr::Wrapping(expr) => {
+                write!(f, "wrapping(")?;
+                self.expr(f, expr.lhs, depth)?;
+                write!(f, " {} ", expr.operator)?;
+                self.expr(f, expr.rhs, depth)?;
+                write!(f, ")")
+            }
+            Expr::Call(expr) => {
+                self.expr(f, expr.callee, depth)?;
+                write!(f, "(")?;
+                self.exprs(f, &expr.arguments, depth)?;
+                write!(f, ")")
+            }
+            Expr::StaticCall(expr) => {
+                let callee = expr.callee.as_raw_index();
+                write!(f, "fn[{}]{}(", callee, generics(&expr.generics))?;
+                self.exprs(f, &expr.arguments, depth)?;
+                write!(f, ")")
+            }
+            Expr::Unary(expr) => {
+                write!(f, "{}", expr.operator)?;
+                self.expr(f, expr.operand, depth)
+            }
+            Expr::Binary(expr) => {
+                write!(f, "(")?;
+                self.expr(f, expr.lhs, depth)?;
+                write!(f, " {} ", expr.operator)?;
+                self.expr(f, expr.rhs, depth)?;
+                write!(f, ")")
+            }
+            Expr::Assign(expr) => {
+                self.expr(f, expr.lhs, depth)?;
+                write!(f, " = ")?;
+                self.expr(f, expr.rhs, depth)
+            }
+            Expr::Return(expr) => {
+                write!(f, "return")?;
+
+                if let Some(value) = expr.value {
+                    write!(f, " ")?;
+                    self.expr(f, value, depth)?;
+                }
+
+                Ok(())
+            }
+            Expr::Break(_) => write!(f, "break"),
+            Expr::Block(expr) => self.block(f, expr.block, depth),
+            Expr::If(expr) => {
+                write!(f, "if ")?;
+                self.expr(f, expr.condition, depth)?;
+                write!(f, " ")?;
+                self.expr(f, expr.then_expr, depth)?;
+
+                if let Some(else_expr) = expr.else_expr {
+                    write!(f, " else ")?;
+                    self.expr(f, else_expr, depth)?;
+                }
+
+                Ok(())
+            }
+            Expr::Loop(expr) => {
+                write!(f, "loop ")?;
+                self.block(f, expr.block, depth)
+            }
+        }
+    }
+}
+
+impl Display for ThirPrinter<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let function = self.function;
+
+        let params: Vec<_> = function
+            .generics
+            .params
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        write!(f, "fn {}", function.ident)?;
+        if !params.is_empty() {
+            write!(f, "<{}>", params.join(", "))?;
+        }
+
+        write!(f, "(")?;
+        for (i, argument) in function.arguments.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+
+            let local = argument.local.cast::<mir::Local>();
+            write!(f, "_{}: {}", local.as_raw_index(), self.body[local].ty)?;
+        }
+
+        writeln!(f, ") -> {} {{", self.return_type)?;
+
+        for (id, local) in self.body.locals.iter() {
+            writeln!(
+                f,
+                "\tlet _{}: {}; {}",
+                id.as_raw_index(),
+                local.ty,
+                local.comment()
+            )?;
+        }
+
+        if let Some(block) = self.body.blocks.keys().next() {
+            if !self.body.locals.is_empty() {
+                writeln!(f)?;
+            }
+
+            indent(f, 1)?;
+            self.block(f, block, 1)?;
+            writeln!(f)?;
+        }
+
+        write!(f, "}}")
+    }
+}
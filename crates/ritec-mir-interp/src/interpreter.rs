@@ -0,0 +1,835 @@
+use std::{cmp::Ordering, collections::HashMap, fmt::Display};
+
+use ritec_core::{FloatSize, Layout, Span, TargetLayout};
+use ritec_error::{Diagnostic, ErrorCode};
+use ritec_mir as mir;
+
+use crate::{AllocationKind, Layouts, Memory, TARGET};
+
+/// The address of the first function, function pointers are given addresses
+/// far above any allocation. Targets with smaller pointers use the highest
+/// address with the top two bits clear instead.
+const FUNCTION_ADDRESS: u64 = 1 << 48;
+
+/// The default maximum number of nested calls.
+pub const DEPTH_LIMIT: usize = 1024;
+
+#[derive(Clone, Debug)]
+pub struct Frame {
+    pub instance: mir::Instance,
+    /// The addresses of the locals of the function.
+    pub locals: HashMap<mir::LocalId, u64>,
+    pub block: mir::BlockId,
+    /// The index of the next statement in `block`.
+    pub statement: usize,
+    /// The span of the statement or terminator being executed.
+    pub span: Span,
+    /// Where the returned value is written in the calling frame.
+    destination: Option<u64>,
+}
+
+/// Executes MIR directly, detecting undefined behavior like null and dangling
+/// pointer dereferences instead of running into it.
+pub struct Interpreter<'a> {
+    pub program: &'a mir::Program,
+    pub memory: Memory,
+    pub layouts: Layouts,
+    /// The maximum number of statements and terminators executed, `None` for
+    /// no limit.
+    pub step_limit: Option<u64>,
+    pub depth_limit: usize,
+    steps: u64,
+    stack: Vec<Frame>,
+    functions: Vec<mir::Instance>,
+    function_addresses: HashMap<mir::Instance, u64>,
+}
+
+impl<'a> Interpreter<'a> {
+    pub fn new(program: &'a mir::Program) -> Self {
+        Self::with_target(program, TARGET)
+    }
+
+    /// Creates an interpreter laying out memory for `target`, pointers,
+    /// `usize` and `isize` have the size of the pointers of `target`.
+    pub fn with_target(program: &'a mir::Program, target: TargetLayout) -> Self {
+        Self {
+            program,
+            memory: Memory::new(),
+            layouts: Layouts::new(target),
+            step_limit: None,
+            depth_limit: DEPTH_LIMIT,
+            steps: 0,
+            stack: Vec::new(),
+            functions: Vec::new(),
+            function_addresses: HashMap::new(),
+        }
+    }
+
+    /// Returns the frames of the functions being executed, innermost last.
+    pub fn stack(&self) -> &[Frame] {
+        &self.stack
+    }
+
+    /// Runs `main` in the root module with `args`, the first of which is the
+    /// name of the program. Returns the value returned by `main`, or 0 if it
+    /// doesn't return an integer.
+    pub fn run_main(&mut self, args: &[String]) -> Result<i32, Diagnostic> {
+        let main = self
+            .program
+            .functions
+            .iter()
+            .find(|(_, function)| {
+                function.path.is_empty()
+                    && function.ident.value() == "main"
+                    && function.generics.is_empty()
+            })
+            .map(|(id, _)| id)
+            .ok_or_else(|| Diagnostic::error("`main` function not found"))?;
+
+        let function = &self.program[main];
+        let arguments = match function.arguments.as_slice() {
+            [] => Vec::new(),
+            [argc, _] => {
+                let argc = self.int_argument(&argc.ty, args.len() as i128);
+                let argv = self.allocate_args(args);
+                vec![argc, self.encode_pointer(argv)]
+            }
+            _ => {
+                return Err(Diagnostic::error(
+                    "`main` must take either no arguments or `argc` and `argv`",
+                )
+                .with_msg_span("defined here", function.ident.span()));
+            }
+        };
+
+        let value = self.call(mir::Instance::new(main, Vec::new()), arguments)?;
+
+        match function.return_type {
+            mir::Type::Int(ref ty) => Ok(decode_int(&value, ty.signed) as i32),
+            _ => Ok(0),
+        }
+    }
+
+    fn int_argument(&self, ty: &mir::Type, value: i128) -> Vec<u8> {
+        let size = match ty {
+            mir::Type::Int(ty) => self.int_size(ty),
+            _ => self.pointer_size(),
+        };
+
+        encode_int(value, size)
+    }
+
+    fn pointer_size(&self) -> u64 {
+        self.layouts.target.pointer_size
+    }
+
+    /// Returns the size of `ty` in bytes.
+    fn int_size(&self, ty: &mir::IntType) -> u64 {
+        ty.byte_size()
+            .map_or(self.pointer_size(), |size| size as u64)
+    }
+
+    fn encode_pointer(&self, address: u64) -> Vec<u8> {
+        encode_int(address as i128, self.pointer_size())
+    }
+
+    /// Allocates `args` as NUL terminated strings, returns the address of a
+    /// null terminated array of pointers to them.
+    fn allocate_args(&mut self, args: &[String]) -> u64 {
+        let pointer_size = self.pointer_size();
+        let size = (args.len() as u64 + 1) * pointer_size;
+        let argv = self
+            .memory
+            .allocate(size, pointer_size, AllocationKind::Static);
+
+        for (i, arg) in args.iter().enumerate() {
+            let mut bytes = arg.as_bytes().to_vec();
+            bytes.push(0);
+
+            let address = self
+                .memory
+                .allocate(bytes.len() as u64, 1, AllocationKind::Static);
+            self.memory.write(address, &bytes).unwrap();
+
+            let element = argv + i as u64 * pointer_size;
+            let address = self.encode_pointer(address);
+            self.memory.write(element, &address).unwrap();
+        }
+
+        argv
+    }
+
+    /// Calls `instance` with `arguments`, encoded as little endian bytes, and
+    /// returns the returned value.
+    pub fn call(
+        &mut self,
+        instance: mir::Instance,
+        arguments: Vec<Vec<u8>>,
+    ) -> Result<Vec<u8>, Diagnostic> {
+        let depth = self.stack.len();
+        self.push_frame(instance, arguments, None)?;
+
+        loop {
+            if let Some(value) = self.step(depth)? {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Returns the name of `instance` with its full path.
+    pub fn instance_name(&self, instance: &mir::Instance) -> String {
+        let mangled = mir::mangle(self.program, instance.function, &instance.generics);
+        mir::demangle(&mangled).unwrap_or_else(|| instance.name(self.program))
+    }
+
+    /// Returns an error at the statement being executed, with a note for
+    /// every frame on the stack.
+    pub fn error(&self, message: impl Display) -> Diagnostic {
+        let mut err = Diagnostic::error(message.to_string()).with_code(ErrorCode::E0014);
+
+        let mut labeled = false;
+        for (i, frame) in self.stack.iter().rev().enumerate() {
+            if !labeled && !frame.span.is_dummy() {
+                let message = if i == 0 {
+                    "occurred here"
+                } else {
+                    "called here"
+                };
+                err.add_msg_span(message, frame.span);
+                labeled = true;
+            }
+
+            let name = self.instance_name(&frame.instance);
+            let block = frame.block.as_raw_index();
+
+            if i == 0 {
+                err.add_note(format!("in `{}` at bb{}", name, block));
+            } else {
+                err.add_note(format!("called from `{}` at bb{}", name, block));
+            }
+        }
+
+        err
+    }
+
+    fn frame(&self) -> &Frame {
+        self.stack.last().unwrap()
+    }
+
+    fn frame_mut(&mut self) -> &mut Frame {
+        self.stack.last_mut().unwrap()
+    }
+
+    fn function(&self) -> &'a mir::Function {
+        &self.program[self.frame().instance.function]
+    }
+
+    /// Returns `ty` with the generics of the current function instantiated.
+    fn instantiate(&self, ty: &mir::Type) -> mir::Type {
+        let function = self.function();
+        let generics = mir::GenericMap::new(&function.generics, &self.frame().instance.generics);
+
+        let mut ty = ty.clone();
+        ty.instantiate(&generics);
+        ty
+    }
+
+    fn layout(&mut self, ty: &mir::Type) -> Result<Layout, Diagnostic> {
+        let layout = self.layouts.layout(self.program, ty);
+        layout.map_err(|err| self.error(err))
+    }
+
+    fn read(&self, address: u64, size: u64) -> Result<Vec<u8>, Diagnostic> {
+        let result = self.memory.read(address, size).map(<[u8]>::to_vec);
+        result.map_err(|err| self.error(err))
+    }
+
+    fn write(&mut self, address: u64, bytes: &[u8]) -> Result<(), Diagnostic> {
+        let result = self.memory.write(address, bytes);
+        result.map_err(|err| self.error(err))
+    }
+
+    /// Returns the address of the function pointer to `instance`.
+    fn function_address(&mut self, instance: mir::Instance) -> u64 {
+        if let Some(&address) = self.function_addresses.get(&instance) {
+            return address;
+        }
+
+        let address = self.function_base() + self.functions.len() as u64 * 16;
+        self.functions.push(instance.clone());
+        self.function_addresses.insert(instance, address);
+        address
+    }
+
+    /// Returns the address of the first function.
+    fn function_base(&self) -> u64 {
+        let bits = self.pointer_size() * 8;
+        FUNCTION_ADDRESS.min(1 << (bits - 2))
+    }
+
+    fn push_frame(
+        &mut self,
+        instance: mir::Instance,
+        arguments: Vec<Vec<u8>>,
+        destination: Option<u64>,
+    ) -> Result<(), Diagnostic> {
+        if self.stack.len() >= self.depth_limit {
+            return Err(self.error(format!(
+                "stack overflow, more than {} nested calls",
+                self.depth_limit
+            )));
+        }
+
+        let function = &self.program[instance.function];
+        let Some(block) = function.body.blocks.keys().next() else {
+            return Err(self.error(format!("`{}` has no body", self.instance_name(&instance))));
+        };
+
+        let generics = mir::GenericMap::new(&function.generics, &instance.generics);
+
+        let mut locals = HashMap::new();
+        for (id, local) in function.body.locals.iter() {
+            let mut ty = local.ty.clone();
+            ty.instantiate(&generics);
+
+            let layout = self.layout(&ty)?;
+            let address = self
+                .memory
+                .allocate(layout.size, layout.align, AllocationKind::Stack);
+            locals.insert(id, address);
+        }
+
+        for (argument, value) in function.arguments.iter().zip(arguments) {
+            self.write(locals[&argument.local], &value)?;
+        }
+
+        self.stack.push(Frame {
+            instance,
+            locals,
+            block,
+            statement: 0,
+            span: function.ident.span(),
+            destination,
+        });
+
+        Ok(())
+    }
+
+    /// Executes the next statement or terminator, returns the returned value
+    /// if the frame at `depth` returns.
+    fn step(&mut self, depth: usize) -> Result<Option<Vec<u8>>, Diagnostic> {
+        self.steps += 1;
+        if let Some(limit) = self.step_limit {
+            if self.steps > limit {
+                return Err(self.error(format!("exceeded the limit of {} steps", limit)));
+            }
+        }
+
+        let frame = self.frame();
+        let block = &self.function().body[frame.block];
+
+        if let Some(statement) = block.statements.get(frame.statement) {
+            let statement_index = frame.statement;
+
+            let frame = self.frame_mut();
+            frame.span = statement.source_info.span;
+            frame.statement = statement_index + 1;
+
+            self.execute_statement(statement)?;
+            return Ok(None);
+        }
+
+        let Some(ref terminator) = block.terminator else {
+            return Err(self.error("block has no terminator"));
+        };
+
+        self.frame_mut().span = terminator.source_info.span;
+        self.execute_terminator(terminator, depth)
+    }
+
+    fn execute_statement(&mut self, statement: &mir::Statement) -> Result<(), Diagnostic> {
+        match statement.kind {
+            mir::StatementKind::Assign(ref assign) => {
+                let (address, _) = self.place(&assign.place)?;
+
+                if let mir::Value::Call(ref callee, ref arguments) = assign.value {
+                    return self.execute_call(callee, arguments, Some(address));
+                }
+
+                let value = self.value(&assign.value)?;
+                self.write(address, &value)
+            }
+            mir::StatementKind::Drop(ref value) => {
+                if let mir::Value::Call(ref callee, ref arguments) = value {
+                    return self.execute_call(callee, arguments, None);
+                }
+
+                self.value(value)?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Pushes the frame of the called function, the value is written to
+    /// `destination` when it returns.
+    fn execute_call(
+        &mut self,
+        callee: &mir::Operand,
+        arguments: &[mir::Operand],
+        destination: Option<u64>,
+    ) -> Result<(), Diagnostic> {
+        let address = read_u64(&self.operand(callee)?);
+
+        let base = self.function_base();
+        let index = address.wrapping_sub(base) / 16;
+        let instance = match self.functions.get(index as usize) {
+            Some(instance) if address >= base && address & 15 == 0 => instance.clone(),
+            _ => return Err(self.error(format!("call of invalid function pointer {:#x}", address))),
+        };
+
+        let mut values = Vec::with_capacity(arguments.len());
+        for argument in arguments {
+            values.push(self.operand(argument)?);
+        }
+
+        self.push_frame(instance, values, destination)
+    }
+
+    fn execute_terminator(
+        &mut self,
+        terminator: &mir::Terminator,
+        depth: usize,
+    ) -> Result<Option<Vec<u8>>, Diagnostic> {
+        match terminator.kind {
+            mir::TerminatorKind::Goto(target) => self.jump(target),
+            mir::TerminatorKind::Return(ref operand) => {
+                let value = self.operand(operand)?;
+                let frame = self.stack.pop().unwrap();
+
+                for &address in frame.locals.values() {
+                    self.memory.free(address, AllocationKind::Stack).unwrap();
+                }
+
+                if self.stack.len() == depth {
+                    return Ok(Some(value));
+                }
+
+                if let Some(destination) = frame.destination {
+                    self.write(destination, &value)?;
+                }
+            }
+            mir::TerminatorKind::Switch(ref operand, ref targets) => {
+                let value = self.operand(operand)?;
+                let mask = mask(value.len() as u64);
+                let value = decode_int(&value, false) as u128;
+
+                let target = targets
+                    .targets
+                    .iter()
+                    .find(|(case, _)| *case as u128 & mask == value)
+                    .map_or(targets.default, |&(_, target)| target);
+
+                self.jump(target);
+            }
+            mir::TerminatorKind::Assert(ref assert) => {
+                let cond = self.operand(&assert.cond)?[0] != 0;
+
+                if cond != assert.expected {
+                    return Err(self.error(assert.message).with_code(ErrorCode::E0018));
+                }
+
+                self.jump(assert.target);
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn jump(&mut self, target: mir::BlockId) {
+        let frame = self.frame_mut();
+        frame.block = target;
+        frame.statement = 0;
+    }
+
+    /// Returns the address and type of `place`.
+    fn place(&mut self, place: &mir::Place) -> Result<(u64, mir::Type), Diagnostic> {
+        let function = self.function();
+
+        let mut address = self.frame().locals[&place.local];
+        let mut ty = self.instantiate(&function.body[place.local].ty);
+
+        for proj in &place.proj {
+            match proj {
+                mir::Projection::Deref => {
+                    address = read_u64(&self.read(address, self.pointer_size())?);
+                }
+                mir::Projection::Field(field) => {
+                    let offset = self.layouts.offset(self.program, &ty, field.as_raw_index());
+                    address = address.wrapping_add(offset.map_err(|err| self.error(err))?);
+                }
+            }
+
+            ty = self.program.projection_type(&ty, proj);
+        }
+
+        Ok((address, ty))
+    }
+
+    fn operand_type(&self, operand: &mir::Operand) -> mir::Type {
+        let frame = self.frame();
+        (self.program).operand_type(self.function(), &frame.instance.generics, operand)
+    }
+
+    fn operand(&mut self, operand: &mir::Operand) -> Result<Vec<u8>, Diagnostic> {
+        match operand {
+            mir::Operand::Copy(place) | mir::Operand::Move(place) => {
+                let (address, ty) = self.place(place)?;
+                let layout = self.layout(&ty)?;
+                self.read(address, layout.size)
+            }
+            mir::Operand::Constant(constant) => self.constant(constant),
+        }
+    }
+
+    fn constant(&mut self, constant: &mir::Constant) -> Result<Vec<u8>, Diagnostic> {
+        Ok(match constant {
+            mir::Constant::Void => Vec::new(),
+            mir::Constant::Null(_) => self.encode_pointer(0),
+            mir::Constant::Function(id, generics) => {
+                let generics = generics.iter().map(|ty| self.instantiate(ty)).collect();
+                let instance = mir::Instance::new(*id, generics);
+                let address = self.function_address(instance);
+                self.encode_pointer(address)
+            }
+            mir::Constant::Integer(value, ty) => encode_int(*value as i128, self.int_size(ty)),
+            mir::Constant::Float(value, ty) => self.encode_float(*value, ty)?,
+            mir::Constant::Bool(value) => vec![*value as u8],
+        })
+    }
+
+    fn value(&mut self, value: &mir::Value) -> Result<Vec<u8>, Diagnostic> {
+        match value {
+            mir::Value::Use(operand) => self.operand(operand),
+            mir::Value::Address(place) => {
+                let (address, _) = self.place(place)?;
+                Ok(self.encode_pointer(address))
+            }
+            mir::Value::UnaryOp(op, operand) => self.unary_op(*op, operand),
+            mir::Value::BinaryOp(op, lhs, rhs) => self.binary_op(*op, lhs, rhs),
+            mir::Value::Call(..) => Err(self.error("call outside of a statement")),
+            mir::Value::Intrinsic(intrinsic) => self.intrinsic(intrinsic),
+        }
+    }
+
+    fn unary_op(
+        &mut self,
+        op: mir::UnaryOp,
+        operand: &mir::Operand,
+    ) -> Result<Vec<u8>, Diagnostic> {
+        let ty = self.operand_type(operand);
+        let value = self.operand(operand)?;
+        let size = value.len() as u64;
+
+        Ok(match op {
+            mir::UnaryOp::IntNot if ty == mir::Type::Bool => vec![(value[0] == 0) as u8],
+            mir::UnaryOp::IntNot => encode_int(!decode_int(&value, false), size),
+            mir::UnaryOp::IntNeg => encode_int(decode_int(&value, true).wrapping_neg(), size),
+            mir::UnaryOp::FloatNeg => {
+                let float = self.float_type(&ty)?;
+                let value = -self.decode_float(&value, &float)?;
+                self.encode_float(value, &float)?
+            }
+        })
+    }
+
+    fn binary_op(
+        &mut self,
+        op: mir::BinOp,
+        lhs: &mir::Operand,
+        rhs: &mir::Operand,
+    ) -> Result<Vec<u8>, Diagnostic> {
+        use mir::BinOp::*;
+
+        let ty = self.operand_type(lhs);
+        let a = self.operand(lhs)?;
+        let b = self.operand(rhs)?;
+        let size = a.len() as u64;
+
+        if let FloatAdd | FloatSub | FloatMul | FloatDiv | FloatEq | FloatNe | FloatLt | FloatLe
+        | FloatGt | FloatGe = op
+        {
+            let float = self.float_type(&ty)?;
+            let a = self.decode_float(&a, &float)?;
+            let b = self.decode_float(&b, &float)?;
+
+            let value = match op {
+                FloatAdd => a + b,
+                FloatSub => a - b,
+                FloatMul => a * b,
+                FloatDiv => a / b,
+                FloatEq => return Ok(vec![(a == b) as u8]),
+                // ordered, so false if either is NaN
+                FloatNe => return Ok(vec![a.partial_cmp(&b).is_some_and(Ordering::is_ne) as u8]),
+                FloatLt => return Ok(vec![(a < b) as u8]),
+                FloatLe => return Ok(vec![(a <= b) as u8]),
+                FloatGt => return Ok(vec![(a > b) as u8]),
+                FloatGe => return Ok(vec![(a >= b) as u8]),
+                _ => unreachable!(),
+            };
+
+            return self.encode_float(value, &float);
+        }
+
+        let (sa, sb) = (decode_int(&a, true), decode_int(&b, true));
+        let (ua, ub) = (decode_int(&a, false) as u128, decode_int(&b, false) as u128);
+
+        let value = match op {
+            IntAdd => sa.wrapping_add(sb),
+            IntSub => sa.wrapping_sub(sb),
+            IntMul => sa.wrapping_mul(sb),
+            IntDivSigned => {
+                if sb == 0 {
+                    return Err(self.error("division by zero"));
+                }
+
+                let value = sa.wrapping_div(sb);
+                if sign_extend(value, size) != value || (sa == i128::MIN && sb == -1) {
+                    return Err(self.error("overflow in signed division"));
+                }
+
+                value
+            }
+            IntDivUnsigned => {
+                if ub == 0 {
+                    return Err(self.error("division by zero"));
+                }
+
+                (ua / ub) as i128
+            }
+            IntEq => return Ok(vec![(ua == ub) as u8]),
+            IntNe => return Ok(vec![(ua != ub) as u8]),
+            IntLtSigned => return Ok(vec![(sa < sb) as u8]),
+            IntLtUnsigned => return Ok(vec![(ua < ub) as u8]),
+            IntLeSigned => return Ok(vec![(sa <= sb) as u8]),
+            IntLeUnsigned => return Ok(vec![(ua <= ub) as u8]),
+            IntGtSigned => return Ok(vec![(sa > sb) as u8]),
+            IntGtUnsigned => return Ok(vec![(ua > ub) as u8]),
+            IntGeSigned => return Ok(vec![(sa >= sb) as u8]),
+            IntGeUnsigned => return Ok(vec![(ua >= ub) as u8]),
+            _ => unreachable!(),
+        };
+
+        Ok(encode_int(value, size))
+    }
+
+    fn intrinsic(&mut self, intrinsic: &mir::Intrinsic) -> Result<Vec<u8>, Diagnostic> {
+        use mir::Intrinsic::*;
+
+        Ok(match intrinsic {
+            Sizeof(ty) => {
+                let ty = self.instantiate(ty);
+                let size = self.layout(&ty)?.size;
+
+                if size & mask(self.pointer_size()) as u64 != size {
+                    return Err(self.error(format!("the type `{}` is too large", ty)));
+                }
+
+                self.encode_pointer(size)
+            }
+            Alignof(ty) => {
+                let ty = self.instantiate(ty);
+                let align = self.layout(&ty)?.align;
+                self.encode_pointer(align)
+            }
+            Bitcast(operand, ty) => {
+                let value = self.operand(operand)?;
+                let ty = self.instantiate(ty);
+
+                if self.layout(&ty)?.size != value.len() as u64 {
+                    let from = self.operand_type(operand);
+                    return Err(self.error(format!(
+                        "bitcast from `{}` to `{}`, which have different sizes",
+                        from, ty
+                    )));
+                }
+
+                value
+            }
+            Malloc(count, ty) => {
+                let count = read_u64(&self.operand(count)?);
+                let layout = self.layout(&self.instantiate(ty))?;
+
+                let Some(size) = count.checked_mul(layout.size) else {
+                    return Err(self.error(format!("allocation of {} elements overflows", count)));
+                };
+
+                let address = self
+                    .memory
+                    .allocate(size, layout.align, AllocationKind::Heap);
+                self.encode_pointer(address)
+            }
+            Free(operand) => {
+                let address = read_u64(&self.operand(operand)?);
+
+                if address != 0 {
+                    let result = self.memory.free(address, AllocationKind::Heap);
+                    result.map_err(|err| self.error(err))?;
+                }
+
+                Vec::new()
+            }
+            Memcpy(dst, src, size) => {
+                let dst = read_u64(&self.operand(dst)?);
+                let src = read_u64(&self.operand(src)?);
+                let size = read_u64(&self.operand(size)?);
+
+                let result = self.memory.copy(dst, src, size);
+                result.map_err(|err| self.error(err))?;
+
+                Vec::new()
+            }
+            PtrToInt(operand, _, to) => {
+                let address = read_u64(&self.operand(operand)?);
+                encode_int(address as i128, self.int_size(to))
+            }
+            IntToPtr(operand, from, _) => {
+                let value = decode_int(&self.operand(operand)?, from.signed);
+                self.encode_pointer(value as u64)
+            }
+            PtrToPtr(operand, _, _) => self.operand(operand)?,
+            IntToInt(operand, from, to) => {
+                let value = decode_int(&self.operand(operand)?, from.signed);
+                encode_int(value, self.int_size(to))
+            }
+            IntToFloat(operand, from, to) => {
+                let value = decode_int(&self.operand(operand)?, from.signed);
+
+                match (to.size, from.signed) {
+                    // converted directly, to round only once
+                    (FloatSize::F32, true) => (value as f32).to_le_bytes().to_vec(),
+                    (FloatSize::F32, false) => (value as u128 as f32).to_le_bytes().to_vec(),
+                    (_, true) => self.encode_float(value as f64, to)?,
+                    (_, false) => self.encode_float(value as u128 as f64, to)?,
+                }
+            }
+            FloatToInt(operand, from, to) => {
+                let value = self.operand(operand)?;
+                let value = self.decode_float(&value, from)?;
+
+                let value = match to.signed {
+                    true => value as i128,
+                    false => value as u128 as i128,
+                };
+
+                encode_int(value, self.int_size(to))
+            }
+            FloatToFloat(operand, from, to) => {
+                let value = self.operand(operand)?;
+                let value = self.decode_float(&value, from)?;
+                self.encode_float(value, to)?
+            }
+            Overflows(op, lhs, rhs) => {
+                let mir::Type::Int(ty) = self.operand_type(lhs) else {
+                    return Err(self.error("overflow check of a non integer"));
+                };
+
+                let a = self.operand(lhs)?;
+                let b = self.operand(rhs)?;
+                vec![overflows(*op, &a, &b, ty.signed) as u8]
+            }
+            Wrapping(op, lhs, rhs) => self.binary_op(*op, lhs, rhs)?,
+        })
+    }
+
+    fn float_type(&self, ty: &mir::Type) -> Result<mir::FloatType, Diagnostic> {
+        match ty {
+            mir::Type::Float(float) => Ok(float.clone()),
+            _ => Err(self.error(format!("expected a float, found `{}`", ty))),
+        }
+    }
+
+    fn decode_float(&self, bytes: &[u8], ty: &mir::FloatType) -> Result<f64, Diagnostic> {
+        match ty.size {
+            FloatSize::F16 => Err(self.error("`f16` is not supported by the interpreter")),
+            FloatSize::F32 => Ok(f32::from_le_bytes(bytes.try_into().unwrap()) as f64),
+            FloatSize::F64 => Ok(f64::from_le_bytes(bytes.try_into().unwrap())),
+        }
+    }
+
+    fn encode_float(&self, value: f64, ty: &mir::FloatType) -> Result<Vec<u8>, Diagnostic> {
+        match ty.size {
+            FloatSize::F16 => Err(self.error("`f16` is not supported by the interpreter")),
+            FloatSize::F32 => Ok((value as f32).to_le_bytes().to_vec()),
+            FloatSize::F64 => Ok(value.to_le_bytes().to_vec()),
+        }
+    }
+}
+
+/// Returns `true` if the integer operation `op` on `a` and `b` doesn't fit in
+/// their type.
+fn overflows(op: mir::BinOp, a: &[u8], b: &[u8], signed: bool) -> bool {
+    use mir::BinOp::*;
+
+    let size = a.len() as u64;
+
+    if signed {
+        let (a, b) = (decode_int(a, true), decode_int(b, true));
+
+        let value = match op {
+            IntAdd => a.checked_add(b),
+            IntSub => a.checked_sub(b),
+            IntMul => a.checked_mul(b),
+            // only `MIN / -1` overflows
+            IntDivSigned if b == -1 => a.checked_neg(),
+            _ => return false,
+        };
+
+        value.is_none_or(|value| sign_extend(value, size) != value)
+    } else {
+        let (a, b) = (decode_int(a, false) as u128, decode_int(b, false) as u128);
+
+        let value = match op {
+            IntAdd => a.checked_add(b),
+            IntSub => a.checked_sub(b),
+            IntMul => a.checked_mul(b),
+            _ => return false,
+        };
+
+        value.is_none_or(|value| value & mask(size) != value)
+    }
+}
+
+/// Returns a mask of the low `size` bytes.
+fn mask(size: u64) -> u128 {
+    match size {
+        16.. => u128::MAX,
+        _ => (1 << (size * 8)) - 1,
+    }
+}
+
+/// Sign extends the low `size` bytes of `value`.
+fn sign_extend(value: i128, size: u64) -> i128 {
+    let shift = 128 - size.min(16) * 8;
+    (value << shift) >> shift
+}
+
+/// Decodes a little endian integer of up to 16 bytes.
+pub fn decode_int(bytes: &[u8], signed: bool) -> i128 {
+    let mut buffer = [0; 16];
+    buffer[..bytes.len()].copy_from_slice(bytes);
+    let value = i128::from_le_bytes(buffer);
+
+    match signed {
+        true => sign_extend(value, bytes.len() as u64),
+        false => value,
+    }
+}
+
+/// Encodes the low `size` bytes of `value` as a little endian integer.
+pub fn encode_int(value: i128, size: u64) -> Vec<u8> {
+    value.to_le_bytes()[..size as usize].to_vec()
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    decode_int(bytes, false) as u64
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use ritec_core::{Layout, TargetLayout};
+use ritec_mir as mir;
+
+/// The size of pointers, `usize` and `isize` in bytes.
+pub const POINTER_SIZE: u64 = 8;
+
+/// The layout the interpreter lays out memory with by default.
+pub const TARGET: TargetLayout = TargetLayout {
+    pointer_size: POINTER_SIZE,
+    pointer_align: POINTER_SIZE,
+    int_aligns: [1, 2, 4, 8, 8],
+    float_aligns: [2, 4, 8],
+};
+
+#[derive(Clone, Debug)]
+pub struct Layouts {
+    /// The target types are laid out for.
+    pub target: TargetLayout,
+    layouts: HashMap<mir::Type, Layout>,
+}
+
+impl Layouts {
+    pub fn new(target: TargetLayout) -> Self {
+        Self {
+            target,
+            layouts: HashMap::new(),
+        }
+    }
+
+    /// Returns the layout of `ty`, or an error if `ty` has no layout, like
+    /// slices and generics, or is too large.
+    pub fn layout(&mut self, program: &mir::Program, ty: &mir::Type) -> Result<Layout, String> {
+        if let Some(layout) = self.layouts.get(ty) {
+            return Ok(layout.clone());
+        }
+
+        let layout = match ty {
+            mir::Type::Void => Layout::VOID,
+            mir::Type::Bool => self.target.bool(),
+            mir::Type::Int(int) => self.target.int(int.size),
+            mir::Type::Float(float) => self.target.float(float.size),
+            mir::Type::Pointer(_) | mir::Type::Function(_) => self.target.pointer(),
+            mir::Type::Array(array) => {
+                let element = self.layout(program, &array.element)?;
+
+                match element.array(array.size as u64) {
+                    Some(layout) => layout,
+                    None => return Err(format!("the type `{}` is too large", ty)),
+                }
+            }
+            mir::Type::Tuple(tuple) => self.struct_layout(program, &tuple.fields)?,
+            mir::Type::Class(class_type) => {
+                let class = &program[class_type.class];
+                let generics = mir::GenericMap::new(&class.generics, &class_type.generics);
+
+                let mut fields = Vec::new();
+                for field in &class.fields {
+                    let mut field_type = field.ty.clone();
+                    field_type.instantiate(&generics);
+                    fields.push(field_type);
+                }
+
+                self.struct_layout(program, &fields)?
+            }
+            mir::Type::Slice(_) | mir::Type::Generic(_) => {
+                return Err(format!("`{}` has no layout", ty));
+            }
+        };
+
+        self.layouts.insert(ty.clone(), layout.clone());
+        Ok(layout)
+    }
+
+    /// Returns the offset of the field `index` of the tuple or class `ty`.
+    pub fn offset(
+        &mut self,
+        program: &mir::Program,
+        ty: &mir::Type,
+        index: usize,
+    ) -> Result<u64, String> {
+        Ok(self.layout(program, ty)?.fields[index])
+    }
+
+    fn struct_layout(
+        &mut self,
+        program: &mir::Program,
+        fields: &[mir::Type],
+    ) -> Result<Layout, String> {
+        let mut layout = Layout::VOID;
+
+        for field in fields {
+            let field = self.layout(program, field)?;
+            layout.push_field(&field);
+        }
+
+        Ok(layout.finish())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn struct_layout() {
+        let program = mir::Program::new();
+        let mut layouts = Layouts::new(TARGET);
+
+        let ty = mir::Type::tuple([mir::Type::I8, mir::Type::I64, mir::Type::I16]);
+        let layout = layouts.layout(&program, &ty).unwrap();
+        assert_eq!(layout.size, 24);
+        assert_eq!(layout.align, 8);
+        assert_eq!(layout.fields, [0, 8, 16]);
+        assert_eq!(layouts.offset(&program, &ty, 2), Ok(16));
+
+        let ty = mir::Type::array(mir::Type::I32, 3);
+        let layout = layouts.layout(&program, &ty).unwrap();
+        assert_eq!(layout.size, 12);
+
+        let ty = mir::Type::array(mir::Type::U64, usize::MAX);
+        assert!(layouts.layout(&program, &ty).is_err());
+
+        let layout = layouts.layout(&program, &mir::Type::Void).unwrap();
+        assert_eq!(layout.size, 0);
+    }
+
+    #[test]
+    fn follows_target() {
+        let program = mir::Program::new();
+        let mut layouts = Layouts::new(TargetLayout {
+            pointer_size: 4,
+            pointer_align: 4,
+            int_aligns: [1, 2, 4, 4, 4],
+            float_aligns: [2, 4, 4],
+        });
+
+        let ty = mir::Type::tuple([mir::Type::I8, mir::Type::pointer(mir::Type::I8)]);
+        assert_eq!(layouts.layout(&program, &ty).unwrap().size, 8);
+
+        let ty = mir::Type::tuple([mir::Type::USIZE, mir::Type::I64]);
+        assert_eq!(layouts.layout(&program, &ty).unwrap().fields, [0, 4]);
+    }
+}
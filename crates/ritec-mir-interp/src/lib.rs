@@ -0,0 +1,14 @@
+//! Executes MIR directly, without generating any code.
+//!
+//! Every local and every `malloc` gets its own allocation, and addresses are
+//! never reused, so null and dangling pointer dereferences, out of bounds
+//! accesses and uses after free are reported as errors instead of silently
+//! corrupting memory.
+
+mod interpreter;
+mod layout;
+mod memory;
+
+pub use interpreter::*;
+pub use layout::*;
+pub use memory::*;
@@ -0,0 +1,264 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+};
+
+use ritec_core::align_to;
+
+/// The address of the first allocation, addresses below it are never valid.
+const FIRST_ADDRESS: u64 = 0x1000;
+
+/// Space left between allocations, so pointers past the end of an allocation
+/// never point into the next one.
+const GAP: u64 = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AllocationKind {
+    /// A local of a function, freed when the function returns.
+    Stack,
+    /// Allocated by `malloc`.
+    Heap,
+    /// Allocated by the interpreter, e.g. the arguments of `main`.
+    Static,
+}
+
+impl Display for AllocationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stack => write!(f, "stack"),
+            Self::Heap => write!(f, "heap"),
+            Self::Static => write!(f, "static"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Allocation {
+    pub kind: AllocationKind,
+    pub size: u64,
+    pub bytes: Vec<u8>,
+    pub freed: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MemoryError {
+    NullDeref,
+    /// The address doesn't belong to any allocation.
+    Dangling {
+        address: u64,
+    },
+    OutOfBounds {
+        address: u64,
+        size: u64,
+        base: u64,
+        allocation_size: u64,
+    },
+    UseAfterFree {
+        address: u64,
+        kind: AllocationKind,
+    },
+    DoubleFree {
+        address: u64,
+    },
+    /// The address isn't the start of a heap allocation.
+    InvalidFree {
+        address: u64,
+    },
+}
+
+impl Display for MemoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NullDeref => write!(f, "null pointer dereference"),
+            Self::Dangling { address } => {
+                write!(f, "dereference of dangling pointer {:#x}", address)
+            }
+            Self::OutOfBounds {
+                address,
+                size,
+                base,
+                allocation_size,
+            } => write!(
+                f,
+                "out of bounds access of {} bytes at offset {} of an allocation of {} bytes",
+                size,
+                *address as i128 - *base as i128,
+                allocation_size
+            ),
+            Self::UseAfterFree { kind, .. } => {
+                write!(f, "use of {} memory after it was freed", kind)
+            }
+            Self::DoubleFree { .. } => write!(f, "memory freed twice"),
+            Self::InvalidFree { address } => write!(
+                f,
+                "free of pointer {:#x} which wasn't returned by malloc",
+                address
+            ),
+        }
+    }
+}
+
+/// Byte addressed memory made of separate allocations, addresses are never
+/// reused, so accesses through dangling pointers are always detected.
+#[derive(Clone, Debug)]
+pub struct Memory {
+    allocations: BTreeMap<u64, Allocation>,
+    next_address: u64,
+}
+
+impl Memory {
+    pub fn new() -> Self {
+        Self {
+            allocations: BTreeMap::new(),
+            next_address: FIRST_ADDRESS,
+        }
+    }
+
+    /// Allocates `size` zeroed bytes aligned to `align`, returns the address
+    /// of the allocation.
+    pub fn allocate(&mut self, size: u64, align: u64, kind: AllocationKind) -> u64 {
+        let address = align_to(self.next_address, align.max(GAP));
+        self.next_address = address + size + GAP;
+
+        let allocation = Allocation {
+            kind,
+            size,
+            bytes: vec![0; size as usize],
+            freed: false,
+        };
+
+        self.allocations.insert(address, allocation);
+        address
+    }
+
+    /// Frees the allocation at `address`, which must have been allocated with
+    /// `kind`.
+    pub fn free(&mut self, address: u64, kind: AllocationKind) -> Result<(), MemoryError> {
+        let Some(allocation) = self.allocations.get_mut(&address) else {
+            return Err(MemoryError::InvalidFree { address });
+        };
+
+        if allocation.kind != kind {
+            return Err(MemoryError::InvalidFree { address });
+        }
+
+        if allocation.freed {
+            return Err(MemoryError::DoubleFree { address });
+        }
+
+        allocation.freed = true;
+        allocation.bytes = Vec::new();
+
+        Ok(())
+    }
+
+    /// Returns the allocation containing `address` and the offset of `address` in
+    /// it, checking that `size` bytes can be accessed.
+    fn check(&self, address: u64, size: u64) -> Result<(u64, u64), MemoryError> {
+        if address == 0 {
+            return Err(MemoryError::NullDeref);
+        }
+
+        if address >= self.next_address {
+            return Err(MemoryError::Dangling { address });
+        }
+
+        let Some((&base, allocation)) = self.allocations.range(..=address).next_back() else {
+            return Err(MemoryError::Dangling { address });
+        };
+
+        if allocation.freed {
+            return Err(MemoryError::UseAfterFree {
+                address,
+                kind: allocation.kind,
+            });
+        }
+
+        let offset = address - base;
+        if offset + size > allocation.size {
+            return Err(MemoryError::OutOfBounds {
+                address,
+                size,
+                base,
+                allocation_size: allocation.size,
+            });
+        }
+
+        Ok((base, offset))
+    }
+
+    pub fn read(&self, address: u64, size: u64) -> Result<&[u8], MemoryError> {
+        if size == 0 {
+            return Ok(&[]);
+        }
+
+        let (base, offset) = self.check(address, size)?;
+        let bytes = &self.allocations[&base].bytes;
+        Ok(&bytes[offset as usize..(offset + size) as usize])
+    }
+
+    pub fn write(&mut self, address: u64, bytes: &[u8]) -> Result<(), MemoryError> {
+        if bytes.is_empty() {
+            return Ok(());
+        }
+
+        let (base, offset) = self.check(address, bytes.len() as u64)?;
+        let allocation = self.allocations.get_mut(&base).unwrap();
+
+        let offset = offset as usize;
+        allocation.bytes[offset..offset + bytes.len()].copy_from_slice(bytes);
+
+        Ok(())
+    }
+
+    /// Copies `size` bytes from `src` to `dst`, the ranges may overlap.
+    pub fn copy(&mut self, dst: u64, src: u64, size: u64) -> Result<(), MemoryError> {
+        let bytes = self.read(src, size)?.to_vec();
+        self.write(dst, &bytes)
+    }
+}
+
+impl Default for Memory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_invalid_accesses() {
+        let mut memory = Memory::new();
+        let a = memory.allocate(8, 8, AllocationKind::Heap);
+        let b = memory.allocate(4, 4, AllocationKind::Heap);
+
+        memory.write(a, &7u64.to_le_bytes()).unwrap();
+        assert_eq!(memory.read(a, 8).unwrap(), 7u64.to_le_bytes());
+
+        assert_eq!(memory.read(0, 1), Err(MemoryError::NullDeref));
+        assert!(matches!(
+            memory.read(a + 4, 8),
+            Err(MemoryError::OutOfBounds { .. })
+        ));
+        assert!(matches!(
+            memory.read(a + 8, 1),
+            Err(MemoryError::OutOfBounds { .. })
+        ));
+
+        memory.free(b, AllocationKind::Heap).unwrap();
+        assert!(matches!(
+            memory.read(b, 4),
+            Err(MemoryError::UseAfterFree { .. })
+        ));
+        assert_eq!(
+            memory.free(b, AllocationKind::Heap),
+            Err(MemoryError::DoubleFree { address: b })
+        );
+        assert_eq!(
+            memory.free(a + 1, AllocationKind::Heap),
+            Err(MemoryError::InvalidFree { address: a + 1 })
+        );
+    }
+}
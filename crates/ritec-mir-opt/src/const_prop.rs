@@ -0,0 +1,398 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use ritec_core::FloatSize;
+use ritec_mir::{
+    BinOp, Constant, FloatType, Function, IntType, Intrinsic, Operand, StatementKind, UnaryOp,
+    Value,
+};
+
+use crate::visit::{self, Location};
+
+/// Replaces uses of locals assigned a single constant with the constant, and
+/// folds operations on constants, returns `true` if anything changed.
+///
+/// Operations that would trap or are undefined, like division by zero, are
+/// left for the program to perform.
+pub fn const_prop(function: &mut Function) -> bool {
+    let mut changed = false;
+
+    while propagate(function) | fold(function) {
+        changed = true;
+    }
+
+    changed
+}
+
+fn propagate(function: &mut Function) -> bool {
+    let body = &function.body;
+    let dominators = body.dominators();
+
+    let mut constants = HashMap::new();
+    for (local, uses) in visit::local_uses(body) {
+        if uses.escapes() || uses.assigns.len() != 1 || visit::is_argument(function, local) {
+            continue;
+        }
+
+        let Location { block, index } = uses.assigns[0];
+        let Some(statement) = body.blocks[block].statements.get(index) else {
+            continue;
+        };
+
+        let StatementKind::Assign(ref assign) = statement.kind else {
+            continue;
+        };
+
+        let Value::Use(Operand::Constant(ref constant)) = assign.value else {
+            continue;
+        };
+
+        let dominates_reads =
+            (uses.reads.iter()).all(|&read| visit::dominates(&dominators, uses.assigns[0], read));
+
+        if dominates_reads {
+            constants.insert(local, constant.clone());
+        }
+    }
+
+    let mut changed = false;
+    for block in function.body.blocks.values_mut() {
+        let statements = block.statements.iter_mut().map(|s| s.kind.operands_mut());
+        let terminator = block.terminator.iter_mut().map(|t| t.kind.operands_mut());
+
+        for operand in statements.chain(terminator).flatten() {
+            let (Operand::Copy(place) | Operand::Move(place)) = operand else {
+                continue;
+            };
+
+            if !place.proj.is_empty() {
+                continue;
+            }
+
+            if let Some(constant) = constants.get(&place.local) {
+                *operand = Operand::Constant(constant.clone());
+                changed = true;
+            }
+        }
+    }
+
+    changed
+}
+
+fn fold(function: &mut Function) -> bool {
+    let mut changed = false;
+
+    for block in function.body.blocks.values_mut() {
+        for statement in &mut block.statements {
+            let value = match statement.kind {
+                StatementKind::Assign(ref mut assign) => &mut assign.value,
+                StatementKind::Drop(ref mut value) => value,
+            };
+
+            if let Some(constant) = fold_value(value) {
+                *value = Value::Use(Operand::Constant(constant));
+                changed = true;
+            }
+        }
+    }
+
+    changed
+}
+
+/// Evaluates `value` if all its operands are constants.
+pub fn fold_value(value: &Value) -> Option<Constant> {
+    match value {
+        Value::UnaryOp(op, Operand::Constant(operand)) => fold_unary(*op, operand),
+        Value::BinaryOp(op, Operand::Constant(lhs), Operand::Constant(rhs)) => {
+            fold_binary(*op, lhs, rhs)
+        }
+        Value::Intrinsic(Intrinsic::IntToInt(Operand::Constant(operand), from, to)) => {
+            let Constant::Integer(value, _) = operand else {
+                return None;
+            };
+
+            fold_cast(*value, from, to)
+        }
+        Value::Intrinsic(Intrinsic::Wrapping(
+            op,
+            Operand::Constant(lhs),
+            Operand::Constant(rhs),
+        )) => fold_binary(*op, lhs, rhs),
+        Value::Intrinsic(Intrinsic::Overflows(
+            op,
+            Operand::Constant(lhs),
+            Operand::Constant(rhs),
+        )) => fold_overflows(*op, lhs, rhs),
+        _ => None,
+    }
+}
+
+fn fold_unary(op: UnaryOp, operand: &Constant) -> Option<Constant> {
+    match (op, operand) {
+        (UnaryOp::IntNot, Constant::Bool(value)) => Some(Constant::Bool(!value)),
+        (UnaryOp::IntNot, Constant::Integer(value, ty)) => fold_int(ty, |bits| {
+            Some(Constant::Integer(
+                wrap(!(*value as i128), bits, ty),
+                ty.clone(),
+            ))
+        }),
+        (UnaryOp::IntNeg, Constant::Integer(value, ty)) => fold_int(ty, |bits| {
+            let value = extend(*value, bits, ty.signed);
+            Some(Constant::Integer(
+                wrap(value.wrapping_neg(), bits, ty),
+                ty.clone(),
+            ))
+        }),
+        (UnaryOp::FloatNeg, Constant::Float(value, ty)) => float(-value, ty),
+        _ => None,
+    }
+}
+
+fn fold_binary(op: BinOp, lhs: &Constant, rhs: &Constant) -> Option<Constant> {
+    match (lhs, rhs) {
+        (Constant::Bool(lhs), Constant::Bool(rhs)) => match op {
+            BinOp::IntEq => Some(Constant::Bool(lhs == rhs)),
+            BinOp::IntNe => Some(Constant::Bool(lhs != rhs)),
+            _ => None,
+        },
+        (Constant::Integer(lhs, ty), Constant::Integer(rhs, _)) => {
+            fold_int(ty, |bits| fold_int_binary(op, *lhs, *rhs, bits, ty))
+        }
+        (Constant::Float(lhs, ty), Constant::Float(rhs, _)) => {
+            fold_float_binary(op, *lhs, *rhs, ty)
+        }
+        _ => None,
+    }
+}
+
+fn fold_int_binary(op: BinOp, lhs: i64, rhs: i64, bits: u32, ty: &IntType) -> Option<Constant> {
+    let signed = |value| extend(value, bits, true);
+    let unsigned = |value| extend(value, bits, false);
+    let int = |value| Some(Constant::Integer(wrap(value, bits, ty), ty.clone()));
+    let bool = |value| Some(Constant::Bool(value));
+
+    match op {
+        BinOp::IntAdd => int(signed(lhs).wrapping_add(signed(rhs))),
+        BinOp::IntSub => int(signed(lhs).wrapping_sub(signed(rhs))),
+        BinOp::IntMul => int(signed(lhs).wrapping_mul(signed(rhs))),
+        BinOp::IntDivSigned => {
+            let quotient = signed(lhs).checked_div(signed(rhs))?;
+
+            // `MIN / -1` overflows
+            if quotient != signed(wrap(quotient, bits, ty)) {
+                return None;
+            }
+
+            int(quotient)
+        }
+        BinOp::IntDivUnsigned => int(unsigned(lhs).checked_div(unsigned(rhs))?),
+        BinOp::IntEq => bool(unsigned(lhs) == unsigned(rhs)),
+        BinOp::IntNe => bool(unsigned(lhs) != unsigned(rhs)),
+        BinOp::IntLtSigned => bool(signed(lhs) < signed(rhs)),
+        BinOp::IntLtUnsigned => bool(unsigned(lhs) < unsigned(rhs)),
+        BinOp::IntLeSigned => bool(signed(lhs) <= signed(rhs)),
+        BinOp::IntLeUnsigned => bool(unsigned(lhs) <= unsigned(rhs)),
+        BinOp::IntGtSigned => bool(signed(lhs) > signed(rhs)),
+        BinOp::IntGtUnsigned => bool(unsigned(lhs) > unsigned(rhs)),
+        BinOp::IntGeSigned => bool(signed(lhs) >= signed(rhs)),
+        BinOp::IntGeUnsigned => bool(unsigned(lhs) >= unsigned(rhs)),
+        _ => None,
+    }
+}
+
+fn fold_overflows(op: BinOp, lhs: &Constant, rhs: &Constant) -> Option<Constant> {
+    let (Constant::Integer(lhs, ty), Constant::Integer(rhs, _)) = (lhs, rhs) else {
+        return None;
+    };
+
+    fold_int(ty, |bits| {
+        let lhs = extend(*lhs, bits, ty.signed);
+        let rhs = extend(*rhs, bits, ty.signed);
+
+        let value = match op {
+            BinOp::IntAdd => lhs.checked_add(rhs),
+            BinOp::IntSub => lhs.checked_sub(rhs),
+            BinOp::IntMul => lhs.checked_mul(rhs),
+            // division by zero is checked separately
+            BinOp::IntDivSigned if rhs == 0 => return Some(Constant::Bool(false)),
+            BinOp::IntDivSigned => lhs.checked_div(rhs),
+            _ => return None,
+        };
+
+        let fits = value.is_some_and(|value| extend(value as i64, bits, ty.signed) == value);
+        Some(Constant::Bool(!fits))
+    })
+}
+
+fn fold_float_binary(op: BinOp, lhs: f64, rhs: f64, ty: &FloatType) -> Option<Constant> {
+    let bool = |value| Some(Constant::Bool(value));
+
+    match op {
+        BinOp::FloatAdd => float(lhs + rhs, ty),
+        BinOp::FloatSub => float(lhs - rhs, ty),
+        BinOp::FloatMul => float(lhs * rhs, ty),
+        BinOp::FloatDiv => float(lhs / rhs, ty),
+        // comparisons with NaN are false, `!=` included
+        BinOp::FloatEq => bool(lhs == rhs),
+        BinOp::FloatNe => bool(lhs.partial_cmp(&rhs).is_some_and(Ordering::is_ne)),
+        BinOp::FloatLt => bool(lhs < rhs),
+        BinOp::FloatLe => bool(lhs <= rhs),
+        BinOp::FloatGt => bool(lhs > rhs),
+        BinOp::FloatGe => bool(lhs >= rhs),
+        _ => None,
+    }
+}
+
+/// Rounds `value` to `ty`, `f16` isn't folded.
+fn float(value: f64, ty: &FloatType) -> Option<Constant> {
+    let value = match ty.size {
+        FloatSize::F16 => return None,
+        FloatSize::F32 => value as f32 as f64,
+        FloatSize::F64 => value,
+    };
+
+    Some(Constant::Float(value, ty.clone()))
+}
+
+/// Folds an operation on integers of type `ty` with `f`, which is passed the
+/// bit width to compute with.
+///
+/// Pointer sized integers are folded for both 32 and 64 bit targets, and only
+/// if the results agree. 128 bit integers don't fit in a constant and aren't
+/// folded.
+fn fold_int(ty: &IntType, f: impl Fn(u32) -> Option<Constant>) -> Option<Constant> {
+    match ty.bit_width() {
+        Some(bits) if bits <= 64 => f(bits as u32),
+        Some(_) => None,
+        None => {
+            let constant = f(64)?;
+            (f(32)? == constant).then_some(constant)
+        }
+    }
+}
+
+fn fold_cast(value: i64, from: &IntType, to: &IntType) -> Option<Constant> {
+    let cast = |pointer_bits| {
+        let from_bits = from.bit_width().unwrap_or(pointer_bits) as u32;
+        let to_bits = to.bit_width().unwrap_or(pointer_bits) as u32;
+
+        if from_bits > 64 || to_bits > 64 {
+            return None;
+        }
+
+        let value = extend(value, from_bits, from.signed);
+        Some(Constant::Integer(wrap(value, to_bits, to), to.clone()))
+    };
+
+    let constant = cast(64)?;
+    (cast(32)? == constant).then_some(constant)
+}
+
+/// Returns the low `bits` of `value`, sign extended if `signed`.
+fn extend(value: i64, bits: u32, signed: bool) -> i128 {
+    let shift = 128 - bits;
+
+    if signed {
+        ((value as i128) << shift) >> shift
+    } else {
+        (((value as i128) << shift) as u128 >> shift) as i128
+    }
+}
+
+/// Truncates `value` to a constant of type `ty`, with a width of `bits`.
+fn wrap(value: i128, bits: u32, ty: &IntType) -> i64 {
+    extend(value as i64, bits, ty.signed) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int(value: i64, ty: IntType) -> Operand {
+        Operand::Constant(Constant::Integer(value, ty))
+    }
+
+    #[test]
+    fn wrapping() {
+        let value = Value::BinaryOp(BinOp::IntAdd, int(200, IntType::U8), int(200, IntType::U8));
+        assert_eq!(
+            fold_value(&value),
+            Some(Constant::Integer(144, IntType::U8))
+        );
+
+        let value = Value::BinaryOp(BinOp::IntAdd, int(100, IntType::I8), int(100, IntType::I8));
+        assert_eq!(
+            fold_value(&value),
+            Some(Constant::Integer(-56, IntType::I8))
+        );
+
+        let value = Value::BinaryOp(
+            BinOp::IntLtUnsigned,
+            int(-1, IntType::U32),
+            int(1, IntType::U32),
+        );
+        assert_eq!(fold_value(&value), Some(Constant::Bool(false)));
+
+        let value = Value::UnaryOp(UnaryOp::IntNeg, int(1, IntType::U16));
+        assert_eq!(
+            fold_value(&value),
+            Some(Constant::Integer(65535, IntType::U16))
+        );
+
+        let value = Intrinsic::IntToInt(int(-1, IntType::I32), IntType::I32, IntType::U8);
+        let value = Value::Intrinsic(value);
+        assert_eq!(
+            fold_value(&value),
+            Some(Constant::Integer(255, IntType::U8))
+        );
+    }
+
+    #[test]
+    fn overflows() {
+        let overflows = |op, lhs, rhs, ty: IntType| {
+            let value = Intrinsic::Overflows(op, int(lhs, ty.clone()), int(rhs, ty));
+            fold_value(&Value::Intrinsic(value))
+        };
+
+        let yes = Some(Constant::Bool(true));
+        let no = Some(Constant::Bool(false));
+
+        assert_eq!(overflows(BinOp::IntAdd, 200, 55, IntType::U8), no);
+        assert_eq!(overflows(BinOp::IntAdd, 200, 56, IntType::U8), yes);
+        assert_eq!(overflows(BinOp::IntSub, -128, 1, IntType::I8), yes);
+        assert_eq!(overflows(BinOp::IntMul, -64, 2, IntType::I8), no);
+        assert_eq!(overflows(BinOp::IntDivSigned, -128, -1, IntType::I8), yes);
+        assert_eq!(overflows(BinOp::IntDivSigned, 1, 0, IntType::I8), no);
+    }
+
+    #[test]
+    fn not_folded() {
+        let value = Value::BinaryOp(
+            BinOp::IntDivSigned,
+            int(1, IntType::I32),
+            int(0, IntType::I32),
+        );
+        assert_eq!(fold_value(&value), None);
+
+        let value = Value::BinaryOp(
+            BinOp::IntDivSigned,
+            int(-128, IntType::I8),
+            int(-1, IntType::I8),
+        );
+        assert_eq!(fold_value(&value), None);
+
+        // depends on the width of the target
+        let max = int(u32::MAX as i64, IntType::USIZE);
+        let value = Value::BinaryOp(BinOp::IntAdd, max, int(1, IntType::USIZE));
+        assert_eq!(fold_value(&value), None);
+
+        let value = Value::BinaryOp(
+            BinOp::IntAdd,
+            int(1, IntType::USIZE),
+            int(1, IntType::USIZE),
+        );
+        assert_eq!(
+            fold_value(&value),
+            Some(Constant::Integer(2, IntType::USIZE))
+        );
+    }
+}
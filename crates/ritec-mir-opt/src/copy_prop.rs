@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+use ritec_mir::{Function, Operand, Projection, StatementKind, Value};
+
+use crate::visit::{self, Location, PlaceContext};
+
+/// Replaces uses of locals that are assigned a copy of another local with
+/// that local, returns `true` if anything changed.
+///
+/// The copied local must not change while the copy is in use, so it may only
+/// be assigned once, and not anywhere the copy can reach.
+pub fn copy_prop(function: &mut Function) -> bool {
+    let mut changed = false;
+
+    loop {
+        let body = &function.body;
+        let dominators = body.dominators();
+        let uses = visit::local_uses(body);
+
+        let mut copies = HashMap::new();
+        for (&local, local_uses) in &uses {
+            if visit::is_argument(function, local) {
+                continue;
+            }
+
+            if local_uses.escapes() || local_uses.assigns.len() != 1 || local_uses.reads.is_empty()
+            {
+                continue;
+            }
+
+            let assign = local_uses.assigns[0];
+            let StatementKind::Assign(ref statement) =
+                body[assign.block].statements[assign.index].kind
+            else {
+                continue;
+            };
+
+            let Value::Use(Operand::Copy(ref source) | Operand::Move(ref source)) = statement.value
+            else {
+                continue;
+            };
+
+            if !source.proj.is_empty() || source.local == local {
+                continue;
+            }
+
+            if body[source.local].ty != body[local].ty {
+                continue;
+            }
+
+            let source_uses = &uses[&source.local];
+            if source_uses.escapes() || source_uses.assigns.len() > 1 {
+                continue;
+            }
+
+            if let Some(&source_assign) = source_uses.assigns.first() {
+                if assigned_after(function, assign, source_assign) {
+                    continue;
+                }
+            }
+
+            let dominates_reads =
+                (local_uses.reads.iter()).all(|&read| visit::dominates(&dominators, assign, read));
+
+            if dominates_reads {
+                copies.insert(local, source.local);
+            }
+        }
+
+        if copies.is_empty() {
+            break;
+        }
+
+        visit::places_mut(&mut function.body, |place, context| {
+            // writing through a pointer reads it
+            if context == PlaceContext::Write && !place.proj.contains(&Projection::Deref) {
+                return;
+            }
+
+            if let Some(&source) = copies.get(&place.local) {
+                place.local = source;
+            }
+        });
+
+        changed = true;
+    }
+
+    changed
+}
+
+/// Returns `true` if `source_assign` may run after `assign`.
+fn assigned_after(function: &Function, assign: Location, source_assign: Location) -> bool {
+    if source_assign.block == assign.block && source_assign.index > assign.index {
+        return true;
+    }
+
+    visit::reachable_from(&function.body, assign.block).contains(&source_assign.block)
+}
@@ -0,0 +1,71 @@
+use std::collections::HashSet;
+
+use ritec_mir::{Function, Projection, StatementKind};
+
+use crate::visit;
+
+/// Removes assignments to temporaries that are never read, drops of values
+/// without side effects and temporaries that aren't used at all, returns
+/// `true` if anything changed.
+///
+/// Assignments of calls and other values with side effects are replaced with
+/// drops of the value.
+pub fn dead_code(function: &mut Function) -> bool {
+    let mut changed = false;
+
+    loop {
+        let uses = visit::local_uses(&function.body);
+
+        let is_read =
+            |local| (uses.get(&local)).is_some_and(|u| !u.reads.is_empty() || u.addressed);
+        let dead_locals: HashSet<_> = (function.body.locals.keys())
+            .filter(|&local| visit::is_temporary(function, local) && !is_read(local))
+            .collect();
+
+        let mut removed = false;
+        for block in function.body.blocks.values_mut() {
+            let statements = std::mem::take(&mut block.statements);
+
+            for mut statement in statements {
+                match statement.kind {
+                    StatementKind::Assign(ref assign)
+                        if dead_locals.contains(&assign.place.local)
+                            && !assign.place.proj.contains(&Projection::Deref) =>
+                    {
+                        removed = true;
+
+                        if !assign.value.has_side_effects() {
+                            continue;
+                        }
+
+                        statement.kind = StatementKind::Drop(assign.value.clone());
+                    }
+                    StatementKind::Drop(ref value) if !value.has_side_effects() => {
+                        removed = true;
+                        continue;
+                    }
+                    _ => {}
+                }
+
+                block.statements.push(statement);
+            }
+        }
+
+        if removed {
+            changed = true;
+            continue;
+        }
+
+        // nothing mentions the dead locals anymore
+        for local in dead_locals {
+            if !uses.contains_key(&local) {
+                function.body.locals.remove(local);
+                changed = true;
+            }
+        }
+
+        break;
+    }
+
+    changed
+}
@@ -0,0 +1,349 @@
+use std::collections::{HashMap, HashSet};
+
+use ritec_core::Inline;
+use ritec_mir::{
+    Assign, Block, Constant, Function, FunctionId, GenericMap, Local, Operand, Place, Program,
+    Statement, StatementKind, TerminatorKind, Type, Value,
+};
+
+use crate::visit::{self, Location};
+
+/// Functions with at most this many statements are inlined without an
+/// `#[inline]` attribute.
+pub const INLINE_THRESHOLD: usize = 8;
+
+/// Replaces calls to small functions and functions marked `#[inline]` with
+/// the body of the function, returns `true` if anything changed.
+///
+/// Recursive functions and functions marked `#[inline(never)]` are never
+/// inlined.
+pub fn inline(program: &mut Program) -> bool {
+    let recursive = recursive_functions(program);
+    let mut changed = false;
+
+    for caller in program.functions.keys().collect::<Vec<_>>() {
+        while let Some((location, callee, generics)) = find_call(program, caller, &recursive) {
+            let callee = program[callee].clone();
+            inline_call(&mut program[caller], location, &callee, &generics);
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+fn should_inline(function: &Function) -> bool {
+    if function.body.entry_block().is_none() {
+        return false;
+    }
+
+    match function.inline {
+        Inline::Always => true,
+        Inline::Never => false,
+        Inline::Auto => {
+            let size: usize = (function.body.blocks.values())
+                .map(|block| block.statements.len())
+                .sum();
+
+            size <= INLINE_THRESHOLD
+        }
+    }
+}
+
+/// Returns the first call in `caller` that should be inlined.
+fn find_call(
+    program: &Program,
+    caller: FunctionId,
+    recursive: &HashSet<FunctionId>,
+) -> Option<(Location, FunctionId, Vec<Type>)> {
+    for (block_id, block) in program[caller].body.blocks.iter() {
+        for (index, statement) in block.statements.iter().enumerate() {
+            let value = match statement.kind {
+                StatementKind::Assign(ref assign) => &assign.value,
+                StatementKind::Drop(ref value) => value,
+            };
+
+            let Value::Call(Operand::Constant(Constant::Function(callee, ref generics)), _) =
+                *value
+            else {
+                continue;
+            };
+
+            if recursive.contains(&callee) || !should_inline(&program[callee]) {
+                continue;
+            }
+
+            let location = Location {
+                block: block_id,
+                index,
+            };
+
+            return Some((location, callee, generics.clone()));
+        }
+    }
+
+    None
+}
+
+/// Returns the functions that can end up calling themselves.
+fn recursive_functions(program: &Program) -> HashSet<FunctionId> {
+    let mut callees = HashMap::new();
+    for (id, function) in program.functions.iter() {
+        let mut referenced = HashSet::new();
+
+        for block in function.body.blocks.values() {
+            let statement_operands = block.statements.iter().flat_map(|s| s.kind.operands());
+            let terminator_operands = block.terminator.iter().flat_map(|t| t.kind.operands());
+
+            for operand in statement_operands.chain(terminator_operands) {
+                if let Operand::Constant(Constant::Function(callee, _)) = *operand {
+                    referenced.insert(callee);
+                }
+            }
+        }
+
+        callees.insert(id, referenced);
+    }
+
+    let mut recursive = HashSet::new();
+    for &function in callees.keys() {
+        let mut visited = HashSet::new();
+        let mut stack: Vec<_> = callees[&function].iter().copied().collect();
+
+        while let Some(callee) = stack.pop() {
+            if callee == function {
+                recursive.insert(function);
+                break;
+            }
+
+            if visited.insert(callee) {
+                stack.extend(callees.get(&callee).into_iter().flatten().copied());
+            }
+        }
+    }
+
+    recursive
+}
+
+/// Replaces the call at `location` in `caller` with the body of `callee`
+/// instantiated with `generics`.
+///
+/// The block of the call is split in two, the first half assigns the
+/// arguments and jumps to the inlined body, which returns by assigning the
+/// result and jumping to the second half. Inlined statements keep the spans
+/// of the callee, so failed checks point at the code that failed.
+fn inline_call(caller: &mut Function, location: Location, callee: &Function, generics: &[Type]) {
+    let body = &mut caller.body;
+    let generic_map = GenericMap::new(&callee.generics, generics);
+
+    let block = &mut body.blocks[location.block];
+    let mut rest = block.statements.split_off(location.index);
+    let call = rest.remove(0);
+    let source_info = call.source_info;
+
+    let (place, value) = match call.kind {
+        StatementKind::Assign(assign) => (Some(assign.place), assign.value),
+        StatementKind::Drop(value) => (None, value),
+    };
+
+    let Value::Call(_, arguments) = value else {
+        unreachable!("inlined statement must be a call");
+    };
+
+    let terminator = block.terminator.take();
+
+    let mut locals = HashMap::new();
+    for (id, local) in callee.body.locals.iter() {
+        let mut ty = local.ty.clone();
+        ty.instantiate(&generic_map);
+
+        // keep the names of the callee's variables, so the inlined MIR is
+        // still readable
+        let local = Local {
+            ident: local.ident.clone(),
+            ty,
+            source_info: local.source_info,
+        };
+
+        locals.insert(id, body.locals.push(local));
+    }
+
+    // projections of the place may depend on what the callee does, so the
+    // result is only moved into it after the call
+    let result = match place {
+        Some(place) if !place.proj.is_empty() => {
+            let mut return_type = callee.return_type.clone();
+            return_type.instantiate(&generic_map);
+
+            let temp = body.locals.push(Local::new(return_type, source_info));
+            let assign = Assign {
+                place,
+                value: Value::move_operand(temp),
+            };
+            rest.insert(0, Statement::new(assign, source_info));
+
+            Some(Place::local(temp))
+        }
+        place => place,
+    };
+
+    let blocks: HashMap<_, _> = (callee.body.blocks.keys())
+        .map(|id| (id, body.blocks.reserve()))
+        .collect();
+
+    let continuation = body.blocks.push(Block {
+        statements: rest,
+        terminator,
+    });
+
+    let mut inlined = callee.body.clone();
+    visit::places_mut(&mut inlined, |place, _| place.local = locals[&place.local]);
+
+    for id in inlined.blocks.keys().collect::<Vec<_>>() {
+        let mut block = inlined.blocks.take(id).unwrap();
+
+        for statement in &mut block.statements {
+            match statement.kind {
+                StatementKind::Assign(ref mut assign) => assign.value.instantiate(&generic_map),
+                StatementKind::Drop(ref mut value) => value.instantiate(&generic_map),
+            }
+        }
+
+        if let Some(mut terminator) = block.terminator.take() {
+            for operand in terminator.kind.operands_mut() {
+                operand.instantiate(&generic_map);
+            }
+
+            for successor in terminator.kind.successors_mut() {
+                *successor = blocks[successor];
+            }
+
+            if let TerminatorKind::Return(operand) = terminator.kind {
+                if let Some(ref result) = result {
+                    block.push_assign(result.clone(), operand, terminator.source_info);
+                }
+
+                terminator.kind = TerminatorKind::Goto(continuation);
+            }
+
+            block.terminator = Some(terminator);
+        }
+
+        body.blocks.insert(blocks[&id], block);
+    }
+
+    let block = &mut body.blocks[location.block];
+    for (argument, operand) in callee.arguments.iter().zip(arguments) {
+        block.push_assign(locals[&argument.local], operand, source_info);
+    }
+
+    let entry = callee.body.entry_block().unwrap();
+    block.terminate_goto(blocks[&entry], source_info);
+}
+
+#[cfg(test)]
+mod tests {
+    use ritec_core::{Generic, Ident};
+    use ritec_mir::{FunctionArgument, IntType, Projection, SourceInfo};
+
+    use super::*;
+
+    fn function(ident: &str, arguments: &[Type], return_type: Type) -> Function {
+        let mut function = Function {
+            ident: Ident::from(ident),
+            path: Vec::new(),
+            class: None,
+            generics: Vec::new(),
+            arguments: Vec::new(),
+            return_type,
+            body: Default::default(),
+            inline: Inline::Auto,
+            export: false,
+        };
+
+        for (i, ty) in arguments.iter().enumerate() {
+            let ident = Ident::from(format!("arg{}", i).as_str());
+            let local = Local::with_ident(ident.clone(), ty.clone());
+
+            function.arguments.push(FunctionArgument {
+                ident,
+                ty: ty.clone(),
+                local: function.body.locals.push(local),
+            });
+        }
+
+        function
+    }
+
+    fn call(function: FunctionId, generics: Vec<Type>, arguments: Vec<Operand>) -> Value {
+        let callee = Operand::Constant(Constant::Function(function, generics));
+        Value::Call(callee, arguments)
+    }
+
+    #[test]
+    fn generic_into_projection() {
+        let mut program = Program::new();
+
+        // fn id<T>(arg0: T) -> T
+        let generic = Type::Generic(Generic::new("T"));
+        let mut id = function("id", std::slice::from_ref(&generic), generic.clone());
+        id.generics.push(Generic::new("T"));
+
+        let mut block = Block::new();
+        let argument = Place::local(id.arguments[0].local);
+        block.terminate_return(Operand::Move(argument), SourceInfo::DUMMY);
+        id.body.blocks.push(block);
+        let id = program.functions.push(id);
+
+        // fn f(arg0: *i32) { *arg0 = id<i32>(1) }
+        let mut f = function("f", &[Type::pointer(Type::I32)], Type::Void);
+        let place = Place {
+            local: f.arguments[0].local,
+            proj: vec![Projection::Deref],
+        };
+
+        let mut block = Block::new();
+        let one = Operand::Constant(Constant::Integer(1, IntType::I32));
+        let value = call(id, vec![Type::I32], vec![one]);
+        block.push_assign(place.clone(), value, SourceInfo::DUMMY);
+        block.terminate_return(Operand::VOID, SourceInfo::DUMMY);
+        f.body.blocks.push(block);
+        let f = program.functions.push(f);
+
+        assert!(inline(&mut program));
+        assert!(!inline(&mut program));
+        ritec_mir::validate(&program).unwrap();
+
+        let body = &program[f].body;
+        let types: Vec<_> = body.locals.values().map(|local| &local.ty).collect();
+        assert_eq!(types, [&Type::pointer(Type::I32), &Type::I32, &Type::I32]);
+
+        // the result is only moved into `*arg0` after the inlined body
+        let continuation = body.blocks.keys().last().unwrap();
+        let StatementKind::Assign(ref assign) = body[continuation].statements[0].kind else {
+            panic!("expected an assignment");
+        };
+        assert_eq!(assign.place, place);
+    }
+
+    #[test]
+    fn recursion() {
+        let mut program = Program::new();
+        let a = FunctionId::from_raw_index(0);
+        let b = FunctionId::from_raw_index(1);
+
+        for (ident, callee) in [("a", b), ("b", a)] {
+            let mut function = function(ident, &[], Type::Void);
+
+            let mut block = Block::new();
+            block.push_drop(call(callee, Vec::new(), Vec::new()), SourceInfo::DUMMY);
+            block.terminate_return(Operand::VOID, SourceInfo::DUMMY);
+            function.body.blocks.push(block);
+
+            program.functions.push(function);
+        }
+
+        assert_eq!(recursive_functions(&program), HashSet::from([a, b]));
+        assert!(!inline(&mut program));
+    }
+}
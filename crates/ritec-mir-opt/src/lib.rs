@@ -0,0 +1,20 @@
+//! Optimization passes over MIR, run between building the MIR and generating
+//! code from it.
+//!
+//! Locals with a name are never removed, so user variables stay visible in
+//! debug info, only the temporaries introduced while building MIR are.
+
+mod const_prop;
+mod copy_prop;
+mod dead_code;
+mod inline;
+mod pass;
+mod simplify_cfg;
+mod visit;
+
+pub use const_prop::*;
+pub use copy_prop::*;
+pub use dead_code::*;
+pub use inline::*;
+pub use pass::*;
+pub use simplify_cfg::*;
@@ -0,0 +1,213 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use ritec_error::Diagnostic;
+use ritec_mir::{self as mir, Program};
+
+use crate::{const_prop, copy_prop, dead_code, inline, simplify_cfg};
+
+/// An optimization pass, declared in the order they run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Pass {
+    Inline,
+    ConstProp,
+    CopyProp,
+    DeadCode,
+    SimplifyCfg,
+}
+
+impl Pass {
+    pub const ALL: &'static [Self] = &[
+        Self::Inline,
+        Self::ConstProp,
+        Self::CopyProp,
+        Self::DeadCode,
+        Self::SimplifyCfg,
+    ];
+
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Inline => "inline",
+            Self::ConstProp => "const-prop",
+            Self::CopyProp => "copy-prop",
+            Self::DeadCode => "dead-code",
+            Self::SimplifyCfg => "simplify-cfg",
+        }
+    }
+
+    /// Runs the pass on every function of `program`, returns `true` if
+    /// anything changed.
+    pub fn run(self, program: &mut Program) -> bool {
+        if self == Self::Inline {
+            return inline(program);
+        }
+
+        let mut changed = false;
+
+        for function in program.functions.values_mut() {
+            changed |= match self {
+                Self::ConstProp => const_prop(function),
+                Self::CopyProp => copy_prop(function),
+                Self::DeadCode => dead_code(function),
+                Self::SimplifyCfg => simplify_cfg(&mut function.body),
+                Self::Inline => unreachable!(),
+            };
+        }
+
+        changed
+    }
+}
+
+impl Display for Pass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Pass {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Self::ALL.iter().find(|pass| pass.as_str() == s) {
+            Some(&pass) => Ok(pass),
+            None => {
+                let passes: Vec<_> = Self::ALL.iter().map(|pass| pass.as_str()).collect();
+                Err(format!(
+                    "unknown MIR pass `{}`, expected one of: {}",
+                    s,
+                    passes.join(", ")
+                ))
+            }
+        }
+    }
+}
+
+/// Enables or disables passes, parsed from `PASS` or `no-PASS`, and `all` or
+/// `none` for every pass.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PassToggle {
+    /// The pass to toggle, `None` for all of them.
+    pub pass: Option<Pass>,
+    pub enabled: bool,
+}
+
+impl FromStr for PassToggle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, enabled) = match s.strip_prefix("no-") {
+            Some(name) => (name, false),
+            None => (s, true),
+        };
+
+        match s {
+            "all" => Ok(Self {
+                pass: None,
+                enabled: true,
+            }),
+            "none" => Ok(Self {
+                pass: None,
+                enabled: false,
+            }),
+            _ => Ok(Self {
+                pass: Some(name.parse()?),
+                enabled,
+            }),
+        }
+    }
+}
+
+/// The passes run on MIR before generating code, every pass unless disabled.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PassManager {
+    disabled: Vec<Pass>,
+}
+
+impl PassManager {
+    pub const fn new() -> Self {
+        Self {
+            disabled: Vec::new(),
+        }
+    }
+
+    pub fn is_enabled(&self, pass: Pass) -> bool {
+        !self.disabled.contains(&pass)
+    }
+
+    pub fn enable(&mut self, pass: Pass) {
+        self.disabled.retain(|&disabled| disabled != pass);
+    }
+
+    pub fn disable(&mut self, pass: Pass) {
+        if self.is_enabled(pass) {
+            self.disabled.push(pass);
+        }
+    }
+
+    pub fn toggle(&mut self, toggle: PassToggle) {
+        let passes = match toggle.pass {
+            Some(ref pass) => std::slice::from_ref(pass),
+            None => Pass::ALL,
+        };
+
+        for &pass in passes {
+            if toggle.enabled {
+                self.enable(pass);
+            } else {
+                self.disable(pass);
+            }
+        }
+    }
+
+    /// Returns the enabled passes, in the order they run.
+    pub fn passes(&self) -> impl Iterator<Item = Pass> + '_ {
+        (Pass::ALL.iter().copied()).filter(|&pass| self.is_enabled(pass))
+    }
+
+    /// Runs the enabled passes on `program` until none of them changes
+    /// anything, in debug builds the MIR is validated after every pass.
+    pub fn run(&self, program: &mut Program) -> Result<(), Diagnostic> {
+        let mut changed = true;
+
+        while changed {
+            changed = false;
+
+            for pass in self.passes() {
+                if !pass.run(program) {
+                    continue;
+                }
+
+                changed = true;
+
+                if cfg!(debug_assertions) {
+                    mir::validate(program)
+                        .map_err(|err| err.with_note(format!("after the `{}` pass", pass)))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle() {
+        let mut passes = PassManager::new();
+        assert_eq!(passes.passes().count(), Pass::ALL.len());
+
+        passes.toggle("no-copy-prop".parse().unwrap());
+        assert!(!passes.is_enabled(Pass::CopyProp));
+
+        passes.toggle("none".parse().unwrap());
+        passes.toggle("dead-code".parse().unwrap());
+        assert_eq!(passes.passes().collect::<Vec<_>>(), [Pass::DeadCode]);
+
+        assert!("no-inline-everything".parse::<PassToggle>().is_err());
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::HashSet;
+
+use ritec_mir::{Body, Constant, Operand, TerminatorKind};
+
+/// Simplifies the control flow graph of `body`, returns `true` if anything
+/// changed.
+///
+/// Switches on constants, switches with a single target and asserts that
+/// always hold become gotos, jumps to empty blocks go straight to where the
+/// empty block jumps, blocks are merged into their only predecessor if it
+/// jumps to them unconditionally, and unreachable blocks are removed.
+pub fn simplify_cfg(body: &mut Body) -> bool {
+    let mut changed = false;
+
+    while remove_unreachable_blocks(body)
+        | simplify_branches(body)
+        | skip_empty_blocks(body)
+        | merge_blocks(body)
+    {
+        changed = true;
+    }
+
+    changed
+}
+
+fn simplify_branches(body: &mut Body) -> bool {
+    let mut changed = false;
+
+    for block in body.blocks.values_mut() {
+        let Some(ref mut terminator) = block.terminator else {
+            continue;
+        };
+
+        let (operand, targets) = match terminator.kind {
+            TerminatorKind::Switch(ref operand, ref targets) => (operand, targets),
+            TerminatorKind::Assert(ref assert) => {
+                if assert.cond != Operand::Constant(Constant::Bool(assert.expected)) {
+                    continue;
+                }
+
+                terminator.kind = TerminatorKind::Goto(assert.target);
+                changed = true;
+                continue;
+            }
+            _ => continue,
+        };
+
+        let target = match operand {
+            Operand::Constant(constant) => match switch_value(constant) {
+                Some(value) => (targets.targets.iter())
+                    .find(|(case, _)| *case == value)
+                    .map_or(targets.default, |&(_, target)| target),
+                None => continue,
+            },
+            _ if targets.successors().all(|&t| t == targets.default) => targets.default,
+            _ => continue,
+        };
+
+        terminator.kind = TerminatorKind::Goto(target);
+        changed = true;
+    }
+
+    changed
+}
+
+/// Returns the case a switch on `constant` takes, integers are only known
+/// if they don't depend on the width of pointers.
+fn switch_value(constant: &Constant) -> Option<u64> {
+    match constant {
+        Constant::Bool(value) => Some(*value as u64),
+        Constant::Integer(value, ty) => {
+            let bits = ty.bit_width().filter(|&bits| bits <= 64)?;
+            Some(*value as u64 & (u64::MAX >> (64 - bits)))
+        }
+        _ => None,
+    }
+}
+
+/// Redirects jumps to blocks without statements that just jump elsewhere.
+fn skip_empty_blocks(body: &mut Body) -> bool {
+    let entry = body.entry_block();
+    let mut changed = false;
+
+    for block_id in body.blocks.keys().collect::<Vec<_>>() {
+        let block = &body.blocks[block_id];
+        if Some(block_id) == entry || !block.statements.is_empty() {
+            continue;
+        }
+
+        let Some(TerminatorKind::Goto(target)) = block.terminator.as_ref().map(|t| &t.kind) else {
+            continue;
+        };
+
+        let target = *target;
+        if target == block_id {
+            continue;
+        }
+
+        for block in body.blocks.values_mut() {
+            let Some(ref mut terminator) = block.terminator else {
+                continue;
+            };
+
+            for successor in terminator.kind.successors_mut() {
+                if *successor == block_id {
+                    *successor = target;
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    changed
+}
+
+/// Appends blocks to their only predecessor, if it jumps to them with a goto.
+fn merge_blocks(body: &mut Body) -> bool {
+    let entry = body.entry_block();
+    let mut changed = false;
+
+    for block_id in body.blocks.keys().collect::<Vec<_>>() {
+        // the block may have been merged already
+        let Some(block) = body.blocks.get(block_id) else {
+            continue;
+        };
+
+        let Some(TerminatorKind::Goto(target)) = block.terminator.as_ref().map(|t| &t.kind) else {
+            continue;
+        };
+
+        let target = *target;
+        if target == block_id || Some(target) == entry {
+            continue;
+        }
+
+        if body.predecessors()[&target] != [block_id] {
+            continue;
+        }
+
+        let merged = body.blocks.remove(target).unwrap();
+        let block = &mut body.blocks[block_id];
+        block.statements.extend(merged.statements);
+        block.terminator = merged.terminator;
+        changed = true;
+    }
+
+    changed
+}
+
+fn remove_unreachable_blocks(body: &mut Body) -> bool {
+    let reachable: HashSet<_> = body.reverse_postorder().into_iter().collect();
+    let unreachable: Vec<_> = (body.blocks.keys())
+        .filter(|block| !reachable.contains(block))
+        .collect();
+
+    for &block in &unreachable {
+        body.blocks.remove(block);
+    }
+
+    !unreachable.is_empty()
+}
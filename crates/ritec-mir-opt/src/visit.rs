@@ -0,0 +1,188 @@
+use std::collections::{HashMap, HashSet};
+
+use ritec_mir::{
+    Block, BlockId, Body, Dominators, Function, LocalId, Operand, Place, Projection, StatementKind,
+    Value,
+};
+
+/// How a place is used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaceContext {
+    Read,
+    Write,
+    Address,
+}
+
+/// Where in a block something happens, the terminator comes after the last
+/// statement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub block: BlockId,
+    pub index: usize,
+}
+
+fn value_places(value: &Value, f: &mut impl FnMut(&Place, PlaceContext)) {
+    if let Value::Address(place) = value {
+        f(place, PlaceContext::Address);
+    }
+
+    for operand in value.operands() {
+        if let Some(place) = operand.as_place() {
+            f(place, PlaceContext::Read);
+        }
+    }
+}
+
+fn value_places_mut(value: &mut Value, f: &mut impl FnMut(&mut Place, PlaceContext)) {
+    if let Value::Address(place) = value {
+        f(place, PlaceContext::Address);
+    }
+
+    for operand in value.operands_mut() {
+        if let Operand::Copy(place) | Operand::Move(place) = operand {
+            f(place, PlaceContext::Read);
+        }
+    }
+}
+
+/// Calls `f` with every place used in `block`, and where it is used.
+pub fn block_places(
+    block_id: BlockId,
+    block: &Block,
+    f: &mut impl FnMut(&Place, PlaceContext, Location),
+) {
+    for (index, statement) in block.statements.iter().enumerate() {
+        let location = Location {
+            block: block_id,
+            index,
+        };
+
+        match statement.kind {
+            StatementKind::Assign(ref assign) => {
+                value_places(&assign.value, &mut |p, c| f(p, c, location));
+                f(&assign.place, PlaceContext::Write, location);
+            }
+            StatementKind::Drop(ref value) => {
+                value_places(value, &mut |p, c| f(p, c, location));
+            }
+        }
+    }
+
+    if let Some(ref terminator) = block.terminator {
+        let location = Location {
+            block: block_id,
+            index: block.statements.len(),
+        };
+
+        for operand in terminator.kind.operands() {
+            if let Some(place) = operand.as_place() {
+                f(place, PlaceContext::Read, location);
+            }
+        }
+    }
+}
+
+/// Calls `f` with every place used in `body`.
+pub fn places_mut(body: &mut Body, mut f: impl FnMut(&mut Place, PlaceContext)) {
+    for block in body.blocks.values_mut() {
+        for statement in &mut block.statements {
+            match statement.kind {
+                StatementKind::Assign(ref mut assign) => {
+                    value_places_mut(&mut assign.value, &mut f);
+                    f(&mut assign.place, PlaceContext::Write);
+                }
+                StatementKind::Drop(ref mut value) => value_places_mut(value, &mut f),
+            }
+        }
+
+        if let Some(ref mut terminator) = block.terminator {
+            for operand in terminator.kind.operands_mut() {
+                if let Operand::Copy(place) | Operand::Move(place) = operand {
+                    f(place, PlaceContext::Read);
+                }
+            }
+        }
+    }
+}
+
+/// Everything a body does with a local.
+#[derive(Clone, Debug, Default)]
+pub struct LocalUses {
+    /// Where the local is read, including reading it to dereference it.
+    pub reads: Vec<Location>,
+    /// Where the whole local is assigned.
+    pub assigns: Vec<Location>,
+    /// Where a field of the local is assigned.
+    pub partial_assigns: Vec<Location>,
+    /// Whether the address of the local, or a part of it, is taken.
+    pub addressed: bool,
+}
+
+impl LocalUses {
+    /// Returns `true` if the local may change other than by assigning all of
+    /// it.
+    pub fn escapes(&self) -> bool {
+        self.addressed || !self.partial_assigns.is_empty()
+    }
+}
+
+/// Collects the uses of every local in `body`, locals that aren't used at
+/// all are missing.
+pub fn local_uses(body: &Body) -> HashMap<LocalId, LocalUses> {
+    let mut uses: HashMap<LocalId, LocalUses> = HashMap::new();
+
+    for (block_id, block) in body.blocks.iter() {
+        block_places(block_id, block, &mut |place, context, location| {
+            let uses = uses.entry(place.local).or_default();
+
+            // going through a pointer reads the pointer, whatever is done
+            // with the place
+            if place.proj.contains(&Projection::Deref) {
+                uses.reads.push(location);
+                return;
+            }
+
+            match context {
+                PlaceContext::Read => uses.reads.push(location),
+                PlaceContext::Write if place.proj.is_empty() => uses.assigns.push(location),
+                PlaceContext::Write => uses.partial_assigns.push(location),
+                PlaceContext::Address => uses.addressed = true,
+            }
+        });
+    }
+
+    uses
+}
+
+pub fn is_argument(function: &Function, local: LocalId) -> bool {
+    (function.arguments.iter()).any(|argument| argument.local == local)
+}
+
+/// Returns `true` if the passes may remove or replace `local`, arguments and
+/// named locals are kept.
+pub fn is_temporary(function: &Function, local: LocalId) -> bool {
+    !is_argument(function, local) && function.body.locals[local].ident.is_none()
+}
+
+/// Returns `true` if every path from the entry block to `b` passes `a` first.
+pub fn dominates(dominators: &Dominators, a: Location, b: Location) -> bool {
+    if a.block == b.block {
+        a.index < b.index
+    } else {
+        dominators.dominates(a.block, b.block)
+    }
+}
+
+/// Returns the blocks reachable from `block` by following at least one edge.
+pub fn reachable_from(body: &Body, block: BlockId) -> HashSet<BlockId> {
+    let mut reachable = HashSet::new();
+    let mut stack = body.successors(block);
+
+    while let Some(block) = stack.pop() {
+        if reachable.insert(block) {
+            stack.extend(body.successors(block));
+        }
+    }
+
+    reachable
+}
@@ -2,7 +2,10 @@ use std::fmt::Display;
 
 use ritec_core::Id;
 
-use crate::{Assign, Operand, Place, Statement, SwitchTargets, Terminator, Value};
+use crate::{
+    Assert, AssertMessage, Assign, Operand, Place, SourceInfo, Statement, StatementKind,
+    SwitchTargets, Terminator, TerminatorKind, Value,
+};
 
 pub type BlockId = Id<Block>;
 
@@ -40,29 +43,69 @@ impl Block {
         self.statements.push(stmt);
     }
 
-    pub fn push_assign(&mut self, place: impl Into<Place>, value: impl Into<Value>) {
+    pub fn push_assign(
+        &mut self,
+        place: impl Into<Place>,
+        value: impl Into<Value>,
+        source_info: impl Into<SourceInfo>,
+    ) {
         let assign = Assign {
             place: place.into(),
             value: value.into(),
         };
 
-        self.push(Statement::Assign(assign));
+        self.push(Statement::new(assign, source_info));
     }
 
-    pub fn push_drop(&mut self, value: impl Into<Value>) {
-        self.push(Statement::Drop(value.into()));
+    pub fn push_drop(&mut self, value: impl Into<Value>, source_info: impl Into<SourceInfo>) {
+        self.push(Statement::new(
+            StatementKind::Drop(value.into()),
+            source_info,
+        ));
     }
 
-    pub fn terminate_return(&mut self, value: impl Into<Operand>) {
-        self.terminate(Terminator::Return(value.into()));
+    pub fn terminate_return(
+        &mut self,
+        value: impl Into<Operand>,
+        source_info: impl Into<SourceInfo>,
+    ) {
+        self.terminate(Terminator::new(
+            TerminatorKind::Return(value.into()),
+            source_info,
+        ));
     }
 
-    pub fn terminate_goto(&mut self, target: BlockId) {
-        self.terminate(Terminator::Goto(target));
+    pub fn terminate_goto(&mut self, target: BlockId, source_info: impl Into<SourceInfo>) {
+        self.terminate(Terminator::new(TerminatorKind::Goto(target), source_info));
     }
 
-    pub fn terminate_switch(&mut self, value: impl Into<Operand>, targets: SwitchTargets) {
-        self.terminate(Terminator::Switch(value.into(), targets));
+    pub fn terminate_switch(
+        &mut self,
+        value: impl Into<Operand>,
+        targets: SwitchTargets,
+        source_info: impl Into<SourceInfo>,
+    ) {
+        let kind = TerminatorKind::Switch(value.into(), targets);
+        self.terminate(Terminator::new(kind, source_info));
+    }
+
+    pub fn terminate_assert(
+        &mut self,
+        cond: impl Into<Operand>,
+        expected: bool,
+        message: AssertMessage,
+        target: BlockId,
+        source_info: impl Into<SourceInfo>,
+    ) {
+        let assert = Assert {
+            cond: cond.into(),
+            expected,
+            message,
+            target,
+        };
+
+        let kind = TerminatorKind::Assert(assert);
+        self.terminate(Terminator::new(kind, source_info));
     }
 }
 
@@ -5,7 +5,7 @@ use std::{
 
 use ritec_core::Arena;
 
-use crate::{Block, BlockId, Local, LocalId};
+use crate::{Block, BlockId, Local, LocalId, StatementKind, Type};
 
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Body {
@@ -20,6 +20,28 @@ impl Body {
             blocks: Arena::new(),
         }
     }
+
+    /// Calls `f` with every type in the body, see [`Type::visit_mut`].
+    pub fn visit_types_mut(&mut self, f: &mut impl FnMut(&mut Type)) {
+        for local in self.locals.values_mut() {
+            local.ty.visit_mut(f);
+        }
+
+        for block in self.blocks.values_mut() {
+            for statement in &mut block.statements {
+                match statement.kind {
+                    StatementKind::Assign(ref mut assign) => assign.value.visit_types_mut(f),
+                    StatementKind::Drop(ref mut value) => value.visit_types_mut(f),
+                }
+            }
+
+            if let Some(ref mut terminator) = block.terminator {
+                for operand in terminator.kind.operands_mut() {
+                    operand.visit_types_mut(f);
+                }
+            }
+        }
+    }
 }
 
 impl Index<LocalId> for Body {
@@ -53,13 +75,14 @@ impl IndexMut<BlockId> for Body {
 impl Display for Body {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for (id, local) in self.locals.iter() {
-            writeln!(
-                f,
-                "\tlet _{}: {}; {}",
-                id.as_raw_index(),
-                local.ty,
-                local.comment()
-            )?;
+            write!(f, "\tlet _{}: {};", id.as_raw_index(), local.ty)?;
+
+            let comment = local.comment();
+            if comment.is_empty() {
+                writeln!(f)?;
+            } else {
+                writeln!(f, " {}", comment)?;
+            }
         }
 
         for (id, block) in self.blocks.iter() {
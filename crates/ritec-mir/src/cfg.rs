@@ -0,0 +1,355 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::{BlockId, Body};
+
+impl Body {
+    /// Returns the block execution starts in, the first block of the body.
+    pub fn entry_block(&self) -> Option<BlockId> {
+        self.blocks.keys().next()
+    }
+
+    /// Returns the successors of `block`, blocks without a terminator have
+    /// none.
+    pub fn successors(&self, block: BlockId) -> Vec<BlockId> {
+        match self.blocks[block].terminator {
+            Some(ref terminator) => terminator.successors(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the predecessors of every block, each predecessor appears once
+    /// and in block order.
+    pub fn predecessors(&self) -> HashMap<BlockId, Vec<BlockId>> {
+        let mut predecessors: HashMap<_, Vec<_>> = HashMap::new();
+
+        for block in self.blocks.keys() {
+            predecessors.entry(block).or_default();
+
+            for successor in self.successors(block) {
+                let entry = predecessors.entry(successor).or_default();
+
+                if entry.last() != Some(&block) {
+                    entry.push(block);
+                }
+            }
+        }
+
+        predecessors
+    }
+
+    /// Returns the blocks reachable from the entry block in reverse
+    /// postorder, so every block comes before its successors, except along
+    /// back edges.
+    pub fn reverse_postorder(&self) -> Vec<BlockId> {
+        let Some(entry) = self.entry_block() else {
+            return Vec::new();
+        };
+
+        let mut postorder = Vec::new();
+        let mut visited = HashSet::from([entry]);
+        let mut stack = vec![(entry, self.successors(entry), 0)];
+
+        while let Some((block, successors, index)) = stack.last_mut() {
+            let Some(&successor) = successors.get(*index) else {
+                postorder.push(*block);
+                stack.pop();
+                continue;
+            };
+
+            *index += 1;
+
+            if visited.insert(successor) {
+                stack.push((successor, self.successors(successor), 0));
+            }
+        }
+
+        postorder.reverse();
+        postorder
+    }
+
+    /// Computes the dominator tree of the blocks reachable from the entry
+    /// block.
+    pub fn dominators(&self) -> Dominators {
+        Dominators::new(self)
+    }
+
+    /// Returns the natural loops of the body, outer loops before the loops
+    /// nested in them.
+    pub fn loops(&self) -> Vec<Loop> {
+        let dominators = self.dominators();
+        let predecessors = self.predecessors();
+
+        let mut loops: Vec<Loop> = Vec::new();
+        for block in self.reverse_postorder() {
+            for header in self.successors(block) {
+                if !dominators.dominates(header, block) {
+                    continue;
+                }
+
+                match loops.iter_mut().find(|l| l.header == header) {
+                    Some(l) if l.latches.contains(&block) => {}
+                    Some(l) => l.latches.push(block),
+                    None => loops.push(Loop {
+                        header,
+                        latches: vec![block],
+                        blocks: BTreeSet::new(),
+                    }),
+                }
+            }
+        }
+
+        for l in &mut loops {
+            l.blocks.insert(l.header);
+
+            // every block that reaches a latch without passing the header
+            let mut stack = l.latches.clone();
+            while let Some(block) = stack.pop() {
+                if !l.blocks.insert(block) {
+                    continue;
+                }
+
+                for &predecessor in &predecessors[&block] {
+                    if dominators.is_reachable(predecessor) {
+                        stack.push(predecessor);
+                    }
+                }
+            }
+        }
+
+        loops.sort_by_key(|l| dominators.rpo_index[&l.header]);
+        loops
+    }
+}
+
+/// The dominator tree of a [`Body`], a block dominates another if every path
+/// from the entry block to it passes through the first.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dominators {
+    /// The immediate dominator of every reachable block, the entry block is
+    /// its own.
+    idom: HashMap<BlockId, BlockId>,
+    rpo_index: HashMap<BlockId, usize>,
+}
+
+impl Dominators {
+    /// Computes the dominators with the algorithm by Cooper, Harvey and
+    /// Kennedy.
+    fn new(body: &Body) -> Self {
+        let rpo = body.reverse_postorder();
+        let rpo_index: HashMap<_, _> = rpo.iter().enumerate().map(|(i, &b)| (b, i)).collect();
+        let predecessors = body.predecessors();
+
+        let mut idom = HashMap::new();
+        if let Some(&entry) = rpo.first() {
+            idom.insert(entry, entry);
+        }
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for &block in rpo.iter().skip(1) {
+                let mut new_idom = None;
+
+                for &predecessor in &predecessors[&block] {
+                    if !idom.contains_key(&predecessor) {
+                        continue;
+                    }
+
+                    new_idom = match new_idom {
+                        Some(other) => Some(intersect(&idom, &rpo_index, predecessor, other)),
+                        None => Some(predecessor),
+                    };
+                }
+
+                let new_idom = new_idom.unwrap();
+                if idom.insert(block, new_idom) != Some(new_idom) {
+                    changed = true;
+                }
+            }
+        }
+
+        Self { idom, rpo_index }
+    }
+
+    pub fn is_reachable(&self, block: BlockId) -> bool {
+        self.idom.contains_key(&block)
+    }
+
+    /// Returns the immediate dominator of `block`, `None` for the entry block
+    /// and unreachable blocks.
+    pub fn immediate_dominator(&self, block: BlockId) -> Option<BlockId> {
+        let idom = *self.idom.get(&block)?;
+
+        if idom == block {
+            None
+        } else {
+            Some(idom)
+        }
+    }
+
+    /// Returns `true` if `a` dominates `b`, every block dominates itself.
+    /// Unreachable blocks neither dominate nor are dominated.
+    pub fn dominates(&self, a: BlockId, b: BlockId) -> bool {
+        if !self.is_reachable(a) || !self.is_reachable(b) {
+            return false;
+        }
+
+        let mut block = b;
+        loop {
+            if block == a {
+                return true;
+            }
+
+            match self.immediate_dominator(block) {
+                Some(idom) => block = idom,
+                None => return false,
+            }
+        }
+    }
+}
+
+fn intersect(
+    idom: &HashMap<BlockId, BlockId>,
+    rpo_index: &HashMap<BlockId, usize>,
+    mut a: BlockId,
+    mut b: BlockId,
+) -> BlockId {
+    while a != b {
+        while rpo_index[&a] > rpo_index[&b] {
+            a = idom[&a];
+        }
+
+        while rpo_index[&b] > rpo_index[&a] {
+            b = idom[&b];
+        }
+    }
+
+    a
+}
+
+/// A natural loop, the blocks that can reach one of its back edges without
+/// passing through its header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Loop {
+    /// The block every iteration starts in, which dominates the loop.
+    pub header: BlockId,
+    /// The blocks jumping back to the header.
+    pub latches: Vec<BlockId>,
+    /// Every block of the loop, including the header.
+    pub blocks: BTreeSet<BlockId>,
+}
+
+impl Loop {
+    pub fn contains(&self, block: BlockId) -> bool {
+        self.blocks.contains(&block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Block, Constant, Operand, SourceInfo, SwitchTargets};
+
+    /// Builds a body where block `i` jumps to the blocks in `edges[i]`.
+    fn body(edges: &[&[usize]]) -> Body {
+        let mut body = Body::new();
+        let ids: Vec<BlockId> = edges
+            .iter()
+            .map(|_| body.blocks.push(Block::new()))
+            .collect();
+
+        for (i, successors) in edges.iter().enumerate() {
+            let block = &mut body.blocks[ids[i]];
+
+            match successors {
+                [] => block.terminate_return(Operand::VOID, SourceInfo::DUMMY),
+                [target] => block.terminate_goto(ids[*target], SourceInfo::DUMMY),
+                [targets @ .., default] => {
+                    let targets = SwitchTargets {
+                        targets: (targets.iter())
+                            .enumerate()
+                            .map(|(case, &target)| (case as u64, ids[target]))
+                            .collect(),
+                        default: ids[*default],
+                    };
+
+                    let value = Operand::Constant(Constant::Bool(true));
+                    block.terminate_switch(value, targets, SourceInfo::DUMMY);
+                }
+            }
+        }
+
+        body
+    }
+
+    fn id(index: usize) -> BlockId {
+        BlockId::from_raw_index(index)
+    }
+
+    fn ids(indices: &[usize]) -> Vec<BlockId> {
+        indices.iter().map(|&i| id(i)).collect()
+    }
+
+    #[test]
+    fn diamond() {
+        // 0 -> 1 -> 3, 0 -> 2 -> 3, 4 is unreachable
+        let body = body(&[&[1, 2], &[3], &[3], &[], &[3]]);
+
+        assert_eq!(body.successors(id(0)), ids(&[1, 2]));
+        assert_eq!(body.successors(id(3)), ids(&[]));
+
+        let predecessors = body.predecessors();
+        assert_eq!(predecessors[&id(3)], ids(&[1, 2, 4]));
+        assert_eq!(predecessors[&id(0)], ids(&[]));
+
+        let rpo = body.reverse_postorder();
+        assert_eq!(rpo.len(), 4);
+        assert_eq!(rpo[0], id(0));
+        assert_eq!(rpo[3], id(3));
+
+        let dominators = body.dominators();
+        assert_eq!(dominators.immediate_dominator(id(0)), None);
+        assert_eq!(dominators.immediate_dominator(id(1)), Some(id(0)));
+        assert_eq!(dominators.immediate_dominator(id(3)), Some(id(0)));
+        assert_eq!(dominators.immediate_dominator(id(4)), None);
+        assert!(dominators.dominates(id(0), id(3)));
+        assert!(!dominators.dominates(id(1), id(3)));
+        assert!(!dominators.dominates(id(4), id(3)));
+
+        assert!(body.loops().is_empty());
+    }
+
+    #[test]
+    fn nested_loops() {
+        // 0 -> 1 (outer header) -> 2 (inner header) -> 3 -> 2, 3 -> 4 -> 1,
+        // 1 -> 5 exit
+        let body = body(&[&[1], &[2, 5], &[3], &[2, 4], &[1], &[]]);
+
+        let dominators = body.dominators();
+        assert_eq!(dominators.immediate_dominator(id(5)), Some(id(1)));
+        assert_eq!(dominators.immediate_dominator(id(4)), Some(id(3)));
+
+        let loops = body.loops();
+        assert_eq!(loops.len(), 2);
+
+        assert_eq!(loops[0].header, id(1));
+        assert_eq!(loops[0].latches, ids(&[4]));
+        assert_eq!(loops[0].blocks, ids(&[1, 2, 3, 4]).into_iter().collect());
+
+        assert_eq!(loops[1].header, id(2));
+        assert_eq!(loops[1].latches, ids(&[3]));
+        assert_eq!(loops[1].blocks, ids(&[2, 3]).into_iter().collect());
+        assert!(!loops[1].contains(id(4)));
+    }
+
+    #[test]
+    fn self_loop() {
+        let body = body(&[&[0, 1], &[]]);
+
+        let loops = body.loops();
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].latches, ids(&[0]));
+        assert_eq!(loops[0].blocks, ids(&[0]).into_iter().collect());
+    }
+}
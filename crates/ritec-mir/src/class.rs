@@ -24,20 +24,32 @@ pub type ClassId = Id<Class>;
 #[derive(Clone, Debug, PartialEq)]
 pub struct Class {
     pub ident: Ident,
+    /// The modules the class is declared in.
+    pub path: Vec<Ident>,
     pub generics: Vec<Generic>,
     pub fields: Vec<Field>,
 }
 
 impl Display for Class {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let generics: Vec<_> = self.generics.iter().map(Generic::to_string).collect();
-        writeln!(f, "class {}<{}> {{", self.ident, generics.join(", "))?;
+        write!(f, "class ")?;
+        for segment in &self.path {
+            write!(f, "{}::", segment)?;
+        }
+
+        write!(f, "{}", self.ident)?;
+        if !self.generics.is_empty() {
+            let generics: Vec<_> = self.generics.iter().map(Generic::to_string).collect();
+            write!(f, "<{}>", generics.join(", "))?;
+        }
+
+        writeln!(f, " {{")?;
 
         for field in &self.fields {
-            write!(f, "    {}", field)?;
+            write!(f, "\t{}", field)?;
 
             if let Some(init) = field.init {
-                write!(f, " = func{}", init.as_raw_index())?;
+                write!(f, " = fn[{}]", init.as_raw_index())?;
             }
 
             writeln!(f, ",")?;
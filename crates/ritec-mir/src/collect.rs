@@ -0,0 +1,295 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+use ritec_core::Span;
+use ritec_error::{Diagnostic, ErrorCode};
+
+use crate::{Constant, Function, FunctionId, GenericMap, Operand, Program, Type};
+
+/// How many instances of the same function may be nested inside each other
+/// before the function is considered infinitely recursive.
+pub const RECURSION_LIMIT: usize = 64;
+
+/// A function instantiated with concrete generic arguments.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Instance {
+    pub function: FunctionId,
+    pub generics: Vec<Type>,
+}
+
+impl Instance {
+    pub fn new(function: FunctionId, generics: Vec<Type>) -> Self {
+        Self { function, generics }
+    }
+
+    /// Returns the name of the instance, e.g. `push<i32>`.
+    pub fn name(&self, program: &Program) -> String {
+        let ident = &program[self.function].ident;
+
+        if self.generics.is_empty() {
+            return ident.to_string();
+        }
+
+        let generics: Vec<_> = self.generics.iter().map(Type::to_string).collect();
+        format!("{}<{}>", ident, generics.join(", "))
+    }
+}
+
+impl Display for Instance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "fn[{}]", self.function.as_raw_index())?;
+
+        if !self.generics.is_empty() {
+            let generics: Vec<_> = self.generics.iter().map(Type::to_string).collect();
+            write!(f, "<{}>", generics.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Collects every instance reachable from the roots of `program`, in the
+/// order they are discovered.
+///
+/// The roots are `main` in the root module, followed by the functions with
+/// the `#[export]` attribute in declaration order. Functions that aren't
+/// reachable from a root are never compiled.
+pub fn collect_instances(program: &Program) -> Result<Vec<Instance>, Diagnostic> {
+    let mut collector = Collector {
+        program,
+        instances: Vec::new(),
+        parents: Vec::new(),
+        indices: HashMap::new(),
+    };
+
+    let is_main = |function: &Function| {
+        function.path.is_empty() && function.ident.value() == "main" && function.generics.is_empty()
+    };
+
+    let main = program.functions.iter().filter(|(_, f)| is_main(f));
+    let exported = program
+        .functions
+        .iter()
+        .filter(|(_, f)| f.export && !is_main(f));
+    let roots: Vec<_> = main.chain(exported).map(|(id, _)| id).collect();
+
+    for id in roots {
+        collector.push(Instance::new(id, Vec::new()), None);
+    }
+
+    let mut index = 0;
+    while index < collector.instances.len() {
+        collector.collect_uses(index)?;
+        index += 1;
+    }
+
+    Ok(collector.instances)
+}
+
+struct Collector<'a> {
+    program: &'a Program,
+    instances: Vec<Instance>,
+    /// The instance that first used each instance, `None` for roots.
+    parents: Vec<Option<usize>>,
+    indices: HashMap<Instance, usize>,
+}
+
+impl<'a> Collector<'a> {
+    fn push(&mut self, instance: Instance, parent: Option<usize>) {
+        if self.indices.contains_key(&instance) {
+            return;
+        }
+
+        self.indices.insert(instance.clone(), self.instances.len());
+        self.instances.push(instance);
+        self.parents.push(parent);
+    }
+
+    /// Returns how many instances of `function` there are in the chain of
+    /// instances leading up to `index`.
+    fn depth(&self, function: FunctionId, mut index: Option<usize>) -> usize {
+        let mut depth = 0;
+
+        while let Some(i) = index {
+            if self.instances[i].function == function {
+                depth += 1;
+            }
+
+            index = self.parents[i];
+        }
+
+        depth
+    }
+
+    fn collect_uses(&mut self, index: usize) -> Result<(), Diagnostic> {
+        let program = self.program;
+        let instance = &self.instances[index];
+        let function = &program[instance.function];
+        let generics = instance.generics.clone();
+        let generic_map = GenericMap::new(&function.generics, &generics);
+
+        for block in function.body.blocks.values() {
+            for statement in &block.statements {
+                for operand in statement.kind.operands() {
+                    let span = statement.source_info.span;
+                    self.collect_operand(index, operand, &generic_map, span)?;
+                }
+            }
+
+            if let Some(ref terminator) = block.terminator {
+                for operand in terminator.kind.operands() {
+                    let span = terminator.source_info.span;
+                    self.collect_operand(index, operand, &generic_map, span)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn collect_operand(
+        &mut self,
+        parent: usize,
+        operand: &Operand,
+        generic_map: &GenericMap,
+        span: Span,
+    ) -> Result<(), Diagnostic> {
+        let Operand::Constant(Constant::Function(id, generics)) = operand else {
+            return Ok(());
+        };
+
+        let mut resolved = Vec::new();
+        for mut generic in generics.iter().cloned() {
+            generic.instantiate(generic_map);
+            resolved.push(generic);
+        }
+
+        let instance = Instance::new(*id, resolved);
+        if self.indices.contains_key(&instance) {
+            return Ok(());
+        }
+
+        if self.depth(instance.function, Some(parent)) >= RECURSION_LIMIT {
+            let function = &self.program[instance.function];
+            let name = instance.name(self.program);
+
+            let err = Diagnostic::error(format!(
+                "reached the recursion limit while instantiating `{}`",
+                name
+            ))
+            .with_code(ErrorCode::E0013)
+            .with_msg_span("instantiated here", span)
+            .with_secondary(
+                format!("`{}` defined here", function.ident),
+                function.ident.span(),
+            )
+            .with_note(format!(
+                "`{}` instantiates itself with ever growing generic arguments",
+                function.ident
+            ));
+
+            return Err(err);
+        }
+
+        self.push(instance, Some(parent));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ritec_core::{Generic, Ident, Inline};
+
+    use super::*;
+    use crate::{Block, Body, Local, SourceInfo, Value};
+
+    /// Adds a function that calls each of `calls` and returns.
+    fn function(
+        program: &mut Program,
+        ident: &str,
+        generics: &[&str],
+        calls: &[(FunctionId, Vec<Type>)],
+    ) -> FunctionId {
+        let mut body = Body::new();
+        let local = body.locals.push(Local::new(Type::Void, SourceInfo::DUMMY));
+
+        let mut block = Block::new();
+        for (id, generics) in calls {
+            let callee = Operand::Constant(Constant::Function(*id, generics.clone()));
+            let call = Value::Call(callee, Vec::new());
+            block.push_assign(local, call, SourceInfo::DUMMY);
+        }
+
+        block.terminate_return(Operand::Constant(Constant::Void), SourceInfo::DUMMY);
+        body.blocks.push(block);
+
+        program.functions.push(Function {
+            ident: Ident::from(ident),
+            path: Vec::new(),
+            class: None,
+            generics: generics
+                .iter()
+                .map(|&g| Generic::new(Ident::from(g)))
+                .collect(),
+            arguments: Vec::new(),
+            return_type: Type::Void,
+            body,
+            inline: Inline::Auto,
+            export: false,
+        })
+    }
+
+    fn generic(ident: &str) -> Type {
+        Type::Generic(Generic::new(Ident::from(ident)))
+    }
+
+    #[test]
+    fn collect_reachable() {
+        let mut program = Program::new();
+        let unused = function(&mut program, "unused", &["T"], &[]);
+        let leaf = function(&mut program, "leaf", &["T"], &[]);
+        let wrap = function(&mut program, "wrap", &["T"], &[(leaf, vec![generic("T")])]);
+        let helper = function(&mut program, "helper", &[], &[(leaf, vec![Type::Bool])]);
+        let private = function(&mut program, "private", &[], &[(leaf, vec![Type::U8])]);
+        let main = function(&mut program, "main", &[], &[(wrap, vec![Type::I32])]);
+        program[helper].export = true;
+
+        let instances = collect_instances(&program).unwrap();
+        assert_eq!(
+            instances,
+            [
+                Instance::new(main, Vec::new()),
+                Instance::new(helper, Vec::new()),
+                Instance::new(wrap, vec![Type::I32]),
+                Instance::new(leaf, vec![Type::Bool]),
+                Instance::new(leaf, vec![Type::I32]),
+            ]
+        );
+        assert!(instances.iter().all(|i| i.function != unused));
+
+        // `private` is neither exported nor called
+        assert!(instances.iter().all(|i| i.function != private));
+    }
+
+    #[test]
+    fn polymorphic_recursion() {
+        let mut program = Program::new();
+        let f = program.functions.next_id();
+
+        // `f<T>` calls `f<*T>`, which calls `f<**T>`, and so on
+        let pointer = Type::pointer(generic("T"));
+        function(
+            &mut program,
+            "f",
+            &["T"],
+            &[(f, vec![pointer]), (f, vec![generic("T")])],
+        );
+        function(&mut program, "main", &[], &[(f, vec![Type::I32])]);
+
+        let err = collect_instances(&program).unwrap_err();
+        assert_eq!(err.code, Some(ErrorCode::E0013));
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt::{self, Display};
 
-use crate::{FloatType, FunctionId, IntType, Type};
+use crate::{FloatType, FunctionId, GenericMap, IntType, Type};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Constant {
@@ -12,6 +12,33 @@ pub enum Constant {
     Bool(bool),
 }
 
+impl Constant {
+    pub fn instantiate(&mut self, generics: &GenericMap) {
+        match self {
+            Self::Null(ty) => ty.instantiate(generics),
+            Self::Function(_, function_generics) => {
+                for generic in function_generics {
+                    generic.instantiate(generics);
+                }
+            }
+            Self::Void | Self::Integer(..) | Self::Float(..) | Self::Bool(_) => {}
+        }
+    }
+
+    /// Calls `f` with every type in the constant, see [`Type::visit_mut`].
+    pub fn visit_types_mut(&mut self, f: &mut impl FnMut(&mut Type)) {
+        match self {
+            Self::Null(ty) => ty.visit_mut(f),
+            Self::Function(_, generics) => {
+                for generic in generics {
+                    generic.visit_mut(f);
+                }
+            }
+            Self::Void | Self::Integer(..) | Self::Float(..) | Self::Bool(_) => {}
+        }
+    }
+}
+
 impl Display for Constant {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -0,0 +1,154 @@
+use std::fmt::{self, Display};
+
+use ritec_core::Generic;
+
+use crate::{Assert, Function, FunctionId, Program, TerminatorKind};
+
+/// Prints the control flow graph of every function in a [`Program`] as a
+/// Graphviz `dot` digraph, with a node listing the statements of each block.
+pub struct CfgPrinter<'a> {
+    pub program: &'a Program,
+}
+
+impl<'a> CfgPrinter<'a> {
+    pub const fn new(program: &'a Program) -> Self {
+        Self { program }
+    }
+
+    fn write_function(
+        f: &mut fmt::Formatter<'_>,
+        id: FunctionId,
+        function: &Function,
+    ) -> fmt::Result {
+        let mut name = format!("fn[{}] ", id.as_raw_index());
+        for segment in &function.path {
+            name.push_str(&format!("{}::", segment));
+        }
+
+        name.push_str(function.ident.value());
+        if !function.generics.is_empty() {
+            let generics: Vec<_> = function.generics.iter().map(Generic::to_string).collect();
+            name.push_str(&format!("<{}>", generics.join(", ")));
+        }
+
+        writeln!(f, "digraph \"{}\" {{", escape(&name))?;
+        writeln!(f, "\tnode [shape=box, fontname=monospace];")?;
+
+        for (block_id, block) in function.body.blocks.iter() {
+            // `\l` ends a left aligned line
+            let mut label = format!("bb{}:\\l", block_id.as_raw_index());
+            for statement in &block.statements {
+                label.push_str(&format!("{}\\l", escape(&statement.kind.to_string())));
+            }
+
+            if let Some(ref terminator) = block.terminator {
+                label.push_str(&format!("{}\\l", escape(&terminator.kind.to_string())));
+            }
+
+            writeln!(f, "\tbb{} [label=\"{}\"];", block_id.as_raw_index(), label)?;
+        }
+
+        for (block_id, block) in function.body.blocks.iter() {
+            let Some(ref terminator) = block.terminator else {
+                continue;
+            };
+
+            let block_id = block_id.as_raw_index();
+            match terminator.kind {
+                // the panic of a failed assert isn't a block
+                TerminatorKind::Goto(target) | TerminatorKind::Assert(Assert { target, .. }) => {
+                    writeln!(f, "\tbb{} -> bb{};", block_id, target.as_raw_index())?;
+                }
+                TerminatorKind::Return(_) => {}
+                TerminatorKind::Switch(_, ref targets) => {
+                    for &(value, target) in &targets.targets {
+                        let target = target.as_raw_index();
+                        writeln!(f, "\tbb{} -> bb{} [label=\"{}\"];", block_id, target, value)?;
+                    }
+
+                    let default = targets.default.as_raw_index();
+                    writeln!(f, "\tbb{} -> bb{} [label=\"default\"];", block_id, default)?;
+                }
+            }
+        }
+
+        writeln!(f, "}}")
+    }
+}
+
+impl Display for CfgPrinter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (id, function) in self.program.functions.iter() {
+            Self::write_function(f, id, function)?;
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Escapes `text` for use in a quoted `dot` string.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use ritec_core::{Ident, Inline};
+
+    use super::*;
+    use crate::{Block, BlockId, Body, Constant, Local, Operand, SourceInfo, SwitchTargets, Type};
+
+    #[test]
+    fn switch() {
+        let mut body = Body::new();
+        let local = body.locals.push(Local::new(Type::Bool, SourceInfo::DUMMY));
+
+        let mut entry = Block::new();
+        let value = Operand::Constant(Constant::Bool(true));
+        entry.push_assign(local, value, SourceInfo::DUMMY);
+        let targets = SwitchTargets {
+            targets: vec![(1, BlockId::from_raw_index(1))],
+            default: BlockId::from_raw_index(2),
+        };
+        entry.terminate_switch(Operand::Copy(local.into()), targets, SourceInfo::DUMMY);
+        body.blocks.push(entry);
+
+        let mut then = Block::new();
+        then.terminate_goto(BlockId::from_raw_index(2), SourceInfo::DUMMY);
+        body.blocks.push(then);
+
+        let mut end = Block::new();
+        end.terminate_return(Operand::VOID, SourceInfo::DUMMY);
+        body.blocks.push(end);
+
+        let mut program = Program::new();
+        program.functions.push(Function {
+            ident: Ident::from("f"),
+            path: vec![Ident::from("a")],
+            class: None,
+            generics: Vec::new(),
+            arguments: Vec::new(),
+            return_type: Type::Void,
+            body,
+            inline: Inline::Auto,
+            export: false,
+        });
+
+        assert_eq!(
+            CfgPrinter::new(&program).to_string(),
+            "\
+digraph \"fn[0] a::f\" {
+	node [shape=box, fontname=monospace];
+	bb0 [label=\"bb0:\\l_0 = true\\lswitch (copy _0) [1 -> bb1], default -> bb2\\l\"];
+	bb1 [label=\"bb1:\\lgoto bb2\\l\"];
+	bb2 [label=\"bb2:\\lreturn void\\l\"];
+	bb0 -> bb1 [label=\"1\"];
+	bb0 -> bb2 [label=\"default\"];
+	bb1 -> bb2;
+}
+
+"
+        );
+    }
+}
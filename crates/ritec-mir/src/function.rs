@@ -1,8 +1,8 @@
 use std::fmt::Display;
 
-use ritec_core::{Generic, Id, Ident};
+use ritec_core::{Generic, Id, Ident, Inline};
 
-use crate::{Body, FunctionType, LocalId, Type};
+use crate::{Body, ClassId, FunctionType, LocalId, Type};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct FunctionArgument {
@@ -16,7 +16,7 @@ pub struct FunctionArgument {
 
 impl Display for FunctionArgument {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}: {}", self.ident, self.ty)
+        write!(f, "_{}: {}", self.local.as_raw_index(), self.ty)
     }
 }
 
@@ -26,6 +26,12 @@ pub type FunctionId = Id<Function>;
 pub struct Function {
     /// The name of the function.
     pub ident: Ident,
+    /// The modules the function is declared in, followed by the class for
+    /// methods.
+    pub path: Vec<Ident>,
+    /// The class of methods and field initializers, which is the last
+    /// segment of `path`.
+    pub class: Option<ClassId>,
     /// The generics of the function.
     pub generics: Vec<Generic>,
     /// The arguments of the function.
@@ -34,6 +40,11 @@ pub struct Function {
     pub return_type: Type,
     /// The body of the function.
     pub body: Body,
+    /// Whether the function should be inlined into its callers.
+    pub inline: Inline,
+    /// Whether the function is exported, exported functions are compiled
+    /// even if they are never called.
+    pub export: bool,
 }
 
 impl Function {
@@ -47,7 +58,26 @@ impl Display for Function {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let generics: Vec<_> = self.generics.iter().map(Generic::to_string).collect();
 
-        write!(f, "fn {}", self.ident)?;
+        if self.export {
+            writeln!(f, "#[export]")?;
+        }
+
+        if let Some(attribute) = self.inline.attribute() {
+            writeln!(f, "{}", attribute)?;
+        }
+
+        write!(f, "fn ")?;
+        if self.class.is_some() {
+            // methods are written `<path::Class>::method`
+            let path: Vec<_> = self.path.iter().map(Ident::to_string).collect();
+            write!(f, "<{}>::", path.join("::"))?;
+        } else {
+            for segment in &self.path {
+                write!(f, "{}::", segment)?;
+            }
+        }
+
+        write!(f, "{}", self.ident)?;
         if generics.is_empty() {
             write!(f, "(")?;
         } else {
@@ -1,27 +1,41 @@
 mod block;
 mod body;
+mod cfg;
 mod class;
+mod collect;
 mod constant;
+mod dot;
 mod function;
 mod generic;
 mod local;
+mod mangle;
 mod operand;
+mod parse;
 mod program;
+mod source_info;
 mod statement;
 mod terminator;
 mod ty;
+mod validate;
 mod value;
 
 pub use block::*;
 pub use body::*;
+pub use cfg::*;
 pub use class::*;
+pub use collect::*;
 pub use constant::*;
+pub use dot::*;
 pub use function::*;
 pub use generic::*;
 pub use local::*;
+pub use mangle::*;
 pub use operand::*;
+pub use parse::*;
 pub use program::*;
+pub use source_info::*;
 pub use statement::*;
 pub use terminator::*;
 pub use ty::*;
+pub use validate::*;
 pub use value::*;
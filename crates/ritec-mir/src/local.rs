@@ -1,6 +1,6 @@
 use ritec_core::{Id, Ident};
 
-use crate::Type;
+use crate::{SourceInfo, Type};
 
 pub type LocalId = Id<Local>;
 
@@ -8,24 +8,32 @@ pub type LocalId = Id<Local>;
 pub struct Local {
     pub ident: Option<Ident>,
     pub ty: Type,
+    pub source_info: SourceInfo,
 }
 
 impl Local {
-    pub const fn new(ty: Type) -> Self {
-        Self { ident: None, ty }
+    pub const fn new(ty: Type, source_info: SourceInfo) -> Self {
+        Self {
+            ident: None,
+            ty,
+            source_info,
+        }
     }
 
-    pub const fn with_ident(ident: Ident, ty: Type) -> Self {
+    pub fn with_ident(ident: Ident, ty: Type) -> Self {
         Self {
+            source_info: SourceInfo::new(ident.span()),
             ident: Some(ident),
             ty,
         }
     }
 
     pub fn comment(&self) -> String {
-        match &self.ident {
-            Some(ident) => format!("// {}", ident),
-            None => String::new(),
+        match (&self.ident, self.source_info.is_dummy()) {
+            (Some(ident), false) => format!("// {} {}", ident, self.source_info),
+            (Some(ident), true) => format!("// {}", ident),
+            (None, false) => format!("// {}", self.source_info),
+            (None, true) => String::new(),
         }
     }
 }
@@ -0,0 +1,381 @@
+//! Symbol mangling.
+//!
+//! ```text
+//! <symbol> = "_RT" <ident>* ["C" <ident>] <ident> ["I" <type>+ "E"]
+//! <ident>  = <decimal byte length> <bytes>
+//! <type>   = "v" void | "b" bool
+//!          | "a" i8 | "s" i16 | "l" i32 | "x" i64 | "n" i128 | "i" isize
+//!          | "h" u8 | "t" u16 | "m" u32 | "y" u64 | "o" u128 | "j" usize
+//!          | "k" f16 | "f" f32 | "d" f64
+//!          | "P" <type>                        pointer
+//!          | "A" <decimal length> "_" <type>   array
+//!          | "S" <type>                        slice
+//!          | "F" <type>* "E" <type>            function, arguments then return type
+//!          | "T" <type>* "E"                   tuple
+//!          | "C" <ident>+ ["I" <type>+ "E"]    class
+//!          | "G" <ident>                       generic parameter
+//! ```
+//!
+//! The identifiers of a symbol are the modules the function is declared in,
+//! the class for methods, tagged with `C` so a method can't collide with a
+//! function in a module of the same name, and the name of the function. The
+//! types are its generic arguments.
+
+use ritec_core::{FloatSize, Ident, IntSize};
+
+use crate::{FunctionId, Program, Type};
+
+const PREFIX: &str = "_RT";
+
+/// Returns the symbol name of `function` instantiated with `generics`.
+///
+/// `main` in the root module is left unmangled, so it can be found by the
+/// linker and the JIT.
+pub fn mangle(program: &Program, function: FunctionId, generics: &[Type]) -> String {
+    let function = &program[function];
+
+    if function.path.is_empty() && function.ident.value() == "main" && generics.is_empty() {
+        return String::from("main");
+    }
+
+    let mut symbol = String::from(PREFIX);
+    for (i, ident) in function.path.iter().enumerate() {
+        if function.class.is_some() && i == function.path.len() - 1 {
+            symbol.push('C');
+        }
+
+        mangle_ident(ident, &mut symbol);
+    }
+
+    mangle_ident(&function.ident, &mut symbol);
+
+    mangle_generics(program, generics, &mut symbol);
+
+    symbol
+}
+
+fn mangle_ident(ident: &Ident, out: &mut String) {
+    out.push_str(&ident.len().to_string());
+    out.push_str(ident);
+}
+
+fn mangle_generics(program: &Program, generics: &[Type], out: &mut String) {
+    if generics.is_empty() {
+        return;
+    }
+
+    out.push('I');
+    for ty in generics {
+        mangle_type(program, ty, out);
+    }
+    out.push('E');
+}
+
+fn mangle_type(program: &Program, ty: &Type, out: &mut String) {
+    match ty {
+        Type::Void => out.push('v'),
+        Type::Bool => out.push('b'),
+        Type::Int(ty) => out.push(match (ty.signed, ty.size) {
+            (true, Some(IntSize::I8)) => 'a',
+            (true, Some(IntSize::I16)) => 's',
+            (true, Some(IntSize::I32)) => 'l',
+            (true, Some(IntSize::I64)) => 'x',
+            (true, Some(IntSize::I128)) => 'n',
+            (true, None) => 'i',
+            (false, Some(IntSize::I8)) => 'h',
+            (false, Some(IntSize::I16)) => 't',
+            (false, Some(IntSize::I32)) => 'm',
+            (false, Some(IntSize::I64)) => 'y',
+            (false, Some(IntSize::I128)) => 'o',
+            (false, None) => 'j',
+        }),
+        Type::Float(ty) => out.push(match ty.size {
+            FloatSize::F16 => 'k',
+            FloatSize::F32 => 'f',
+            FloatSize::F64 => 'd',
+        }),
+        Type::Pointer(ty) => {
+            out.push('P');
+            mangle_type(program, ty.pointee(), out);
+        }
+        Type::Array(ty) => {
+            out.push_str(&format!("A{}_", ty.size));
+            mangle_type(program, &ty.element, out);
+        }
+        Type::Slice(ty) => {
+            out.push('S');
+            mangle_type(program, &ty.element, out);
+        }
+        Type::Function(ty) => {
+            out.push('F');
+            for argument in &ty.arguments {
+                mangle_type(program, argument, out);
+            }
+            out.push('E');
+            mangle_type(program, &ty.return_type, out);
+        }
+        Type::Tuple(ty) => {
+            out.push('T');
+            for field in &ty.fields {
+                mangle_type(program, field, out);
+            }
+            out.push('E');
+        }
+        Type::Class(ty) => {
+            out.push('C');
+
+            let path = match program.classes.get(ty.class) {
+                Some(class) => class.path.as_slice(),
+                None => &[],
+            };
+
+            for ident in path.iter().chain([&ty.ident]) {
+                mangle_ident(ident, out);
+            }
+
+            mangle_generics(program, &ty.generics, out);
+        }
+        Type::Generic(generic) => {
+            out.push('G');
+            mangle_ident(&generic.ident, out);
+        }
+    }
+}
+
+/// Returns the readable form of a symbol created by [`mangle`], e.g.
+/// `vec::Vec::push<i32>`, or `None` if `symbol` isn't a mangled symbol.
+pub fn demangle(symbol: &str) -> Option<String> {
+    let mut demangler = Demangler {
+        rest: symbol.strip_prefix(PREFIX)?,
+    };
+
+    let path = demangler.function_path()?;
+    let generics = demangler.generics()?;
+
+    if !demangler.rest.is_empty() {
+        return None;
+    }
+
+    Some(format!("{}{}", path, generics))
+}
+
+struct Demangler<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Demangler<'a> {
+    fn eat(&mut self, c: char) -> bool {
+        match self.rest.strip_prefix(c) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.rest.chars().next()?;
+        self.rest = &self.rest[c.len_utf8()..];
+        Some(c)
+    }
+
+    fn starts_with_digit(&self) -> bool {
+        self.rest.starts_with(|c: char| c.is_ascii_digit())
+    }
+
+    fn number(&mut self) -> Option<usize> {
+        let len = self.rest.find(|c: char| !c.is_ascii_digit());
+        let (digits, rest) = self.rest.split_at(len.unwrap_or(self.rest.len()));
+        self.rest = rest;
+        digits.parse().ok()
+    }
+
+    fn ident(&mut self) -> Option<&'a str> {
+        let len = self.number()?;
+        let ident = self.rest.get(..len)?;
+        self.rest = &self.rest[len..];
+
+        (!ident.is_empty()).then_some(ident)
+    }
+
+    fn path(&mut self) -> Option<String> {
+        let mut idents = vec![self.ident()?];
+        while self.starts_with_digit() {
+            idents.push(self.ident()?);
+        }
+
+        Some(idents.join("::"))
+    }
+
+    /// Parses the path of a function, where the class of a method is tagged
+    /// with `C`.
+    fn function_path(&mut self) -> Option<String> {
+        let mut idents = Vec::new();
+        while self.starts_with_digit() {
+            idents.push(self.ident()?);
+        }
+
+        if self.eat('C') {
+            idents.push(self.ident()?);
+            idents.push(self.ident()?);
+        }
+
+        (!idents.is_empty()).then(|| idents.join("::"))
+    }
+
+    /// Parses types until `E`.
+    fn types(&mut self) -> Option<Vec<String>> {
+        let mut types = Vec::new();
+        while !self.eat('E') {
+            types.push(self.ty()?);
+        }
+
+        Some(types)
+    }
+
+    fn generics(&mut self) -> Option<String> {
+        if !self.eat('I') {
+            return Some(String::new());
+        }
+
+        let types = self.types()?;
+        if types.is_empty() {
+            return None;
+        }
+
+        Some(format!("<{}>", types.join(", ")))
+    }
+
+    fn ty(&mut self) -> Option<String> {
+        let ty = match self.next()? {
+            'v' => String::from("void"),
+            'b' => String::from("bool"),
+            'a' => String::from("i8"),
+            's' => String::from("i16"),
+            'l' => String::from("i32"),
+            'x' => String::from("i64"),
+            'n' => String::from("i128"),
+            'i' => String::from("isize"),
+            'h' => String::from("u8"),
+            't' => String::from("u16"),
+            'm' => String::from("u32"),
+            'y' => String::from("u64"),
+            'o' => String::from("u128"),
+            'j' => String::from("usize"),
+            'k' => String::from("f16"),
+            'f' => String::from("f32"),
+            'd' => String::from("f64"),
+            'P' => format!("*{}", self.ty()?),
+            'A' => {
+                let len = self.number()?;
+                if !self.eat('_') {
+                    return None;
+                }
+
+                format!("[{}; {}]", self.ty()?, len)
+            }
+            'S' => format!("[{}]", self.ty()?),
+            'F' => {
+                let arguments = self.types()?;
+                let return_type = self.ty()?;
+
+                if return_type == "void" {
+                    format!("fn({})", arguments.join(", "))
+                } else {
+                    format!("fn({}) -> {}", arguments.join(", "), return_type)
+                }
+            }
+            'T' => format!("({})", self.types()?.join(", ")),
+            'C' => format!("{}{}", self.path()?, self.generics()?),
+            'G' => String::from(self.ident()?),
+            _ => return None,
+        };
+
+        Some(ty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ritec_core::{Inline, Span};
+
+    use super::*;
+    use crate::{Body, Class, ClassId, Function};
+
+    fn function(
+        program: &mut Program,
+        path: &[&str],
+        class: Option<ClassId>,
+        ident: &str,
+    ) -> FunctionId {
+        program.functions.push(Function {
+            ident: Ident::new(ident, Span::DUMMY),
+            path: path.iter().map(|&ident| Ident::from(ident)).collect(),
+            class,
+            generics: Vec::new(),
+            arguments: Vec::new(),
+            return_type: Type::Void,
+            body: Body::new(),
+            inline: Inline::Auto,
+            export: false,
+        })
+    }
+
+    #[test]
+    fn mangle_and_demangle() {
+        let mut program = Program::new();
+        let class = program.classes.push(Class {
+            ident: Ident::from("Vec"),
+            path: vec![Ident::from("vec")],
+            generics: Vec::new(),
+            fields: Vec::new(),
+        });
+
+        let main = function(&mut program, &[], None, "main");
+        let nested_main = function(&mut program, &["module"], None, "main");
+        let push = function(&mut program, &["vec", "Vec"], Some(class), "push");
+        let module_push = function(&mut program, &["vec", "Vec"], None, "push");
+
+        assert_eq!(mangle(&program, main, &[]), "main");
+
+        let symbol = mangle(&program, nested_main, &[]);
+        assert_eq!(symbol, "_RT6module4main");
+        assert_eq!(demangle(&symbol).unwrap(), "module::main");
+
+        let symbol = mangle(&program, push, &[Type::I32]);
+        assert_eq!(symbol, "_RT3vecC3Vec4pushIlE");
+        assert_eq!(demangle(&symbol).unwrap(), "vec::Vec::push<i32>");
+
+        // `push` in the module `vec::Vec` isn't the method `vec::Vec::push`
+        let symbol = mangle(&program, module_push, &[Type::I32]);
+        assert_eq!(symbol, "_RT3vec3Vec4pushIlE");
+        assert_eq!(demangle(&symbol).unwrap(), "vec::Vec::push<i32>");
+
+        let class = Type::class(class, Ident::from("Vec"), [Type::pointer(Type::U8)]);
+        let generics = [
+            Type::array(class, 4),
+            Type::function(
+                [Type::USIZE, Type::tuple([Type::Bool, Type::F64])],
+                Type::Void,
+            ),
+        ];
+
+        let symbol = mangle(&program, push, &generics);
+        assert_eq!(
+            demangle(&symbol).unwrap(),
+            "vec::Vec::push<[vec::Vec<*u8>; 4], fn(usize, (bool, f64))>"
+        );
+    }
+
+    #[test]
+    fn demangle_invalid() {
+        assert_eq!(demangle("main"), None);
+        assert_eq!(demangle("_RT"), None);
+        assert_eq!(demangle("_RT9main"), None);
+        assert_eq!(demangle("_RT4mainI"), None);
+        assert_eq!(demangle("_RT4mainIE"), None);
+        assert_eq!(demangle("_RT4mainIlEx"), None);
+        assert_eq!(demangle("_RT3vecC3Vec"), None);
+    }
+}
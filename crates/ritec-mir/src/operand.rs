@@ -1,6 +1,6 @@
 use std::fmt::{self, Display};
 
-use crate::{Constant, Place};
+use crate::{Constant, GenericMap, Place, Type};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Operand {
@@ -27,6 +27,19 @@ impl Operand {
             Operand::Constant(_) => None,
         }
     }
+
+    pub fn instantiate(&mut self, generics: &GenericMap) {
+        if let Self::Constant(constant) = self {
+            constant.instantiate(generics);
+        }
+    }
+
+    /// Calls `f` with every type in the operand, see [`Type::visit_mut`].
+    pub fn visit_types_mut(&mut self, f: &mut impl FnMut(&mut Type)) {
+        if let Self::Constant(constant) = self {
+            constant.visit_types_mut(f);
+        }
+    }
 }
 
 impl Display for Operand {
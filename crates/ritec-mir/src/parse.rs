@@ -0,0 +1,1350 @@
+//! Parses the textual form of MIR, the format [`Program`] is displayed in.
+//!
+//! A program is a list of classes followed by a list of functions, which
+//! are numbered in the order they are declared, starting at zero.
+//!
+//! ```text
+//! class vec::Vec<T> {
+//!     len: usize,
+//!     ptr: *T = fn[3],
+//! }
+//!
+//! #[inline]
+//! fn <vec::Vec>::is_empty<T>(_0: *vec::Vec<T>) -> bool {
+//!     let _0: *vec::Vec<T>; // self file[1] 20..24
+//!     let _1: bool;
+//!
+//!     bb0: {
+//!         _1 = IntEq(copy (*(_0)).0, 0usize)
+//!         switch (_1) [0 -> bb1], default -> bb2 // file[1] 30..42
+//!     }
+//!     bb1: {
+//!         return false
+//!     }
+//!     bb2: {
+//!         return true
+//!     }
+//! }
+//! ```
+//!
+//! - Classes and functions are named by their path, methods as
+//!   `<path::Class>::method`. Fields initialized by a function refer to it as
+//!   `fn[N]`, where `N` is its number.
+//! - Function arguments name the local they are stored in, every local,
+//!   statement and terminator is written on a line of its own.
+//! - A comment at the end of such a line is its source info, `file[F] L..H`
+//!   for a span in file `F`, preceded by the name of the variable for
+//!   locals. Other comments are ignored.
+//! - Places are locals `_N`, dereferences `*(place)` and fields
+//!   `(place).N`, operands are places, which are moved, `copy place` or
+//!   constants like `void`, `true`, `null *T`, `fn[N]<T, ..>`, `1i32` and
+//!   `1.5f64`, which always carry their type.
+//! - Values are operands, `&place`, operators like `IntAdd(a, b)`, calls
+//!   `callee(a, b)` and intrinsics like `sizeof(T)` and
+//!   `inttoint(a as i32 as u8)`.
+//! - Statements are `place = value` and `drop value`, terminators are
+//!   `goto bbN`, `return operand`,
+//!   `switch (operand) [V -> bbN, ..], default -> bbN` and
+//!   `assert(operand, Message) -> bbN`, where the operand is negated by a
+//!   leading `!` if the assert expects it to be false.
+//!
+//! Classes are referred to by their path in types, like `vec::Vec<T>`. The
+//! parsed program is validated like the MIR
+//! built by the compiler, so it can be handed to any backend.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use ritec_core::{FileId, FloatSize, Generic, Ident, Inline, IntSize, Span};
+use ritec_error::{Diagnostic, ErrorCode};
+
+use crate::{
+    Assert, AssertMessage, Assign, BinOp, Block, BlockId, Body, Class, ClassId, ClassType,
+    Constant, Field, FieldId, FloatType, Function, FunctionArgument, FunctionId, IntType,
+    Intrinsic, Local, LocalId, Operand, Place, PointerType, Program, Projection, SourceInfo,
+    Statement, StatementKind, SwitchTargets, Terminator, TerminatorKind, Type, UnaryOp, Value,
+};
+
+use crate::validate::validate_parsed;
+
+const UNARY_OPS: &[UnaryOp] = &[UnaryOp::IntNot, UnaryOp::IntNeg, UnaryOp::FloatNeg];
+
+const BINARY_OPS: &[BinOp] = &[
+    BinOp::IntAdd,
+    BinOp::IntSub,
+    BinOp::IntMul,
+    BinOp::IntDivSigned,
+    BinOp::IntDivUnsigned,
+    BinOp::IntEq,
+    BinOp::IntNe,
+    BinOp::IntLtSigned,
+    BinOp::IntLtUnsigned,
+    BinOp::IntLeSigned,
+    BinOp::IntLeUnsigned,
+    BinOp::IntGtSigned,
+    BinOp::IntGtUnsigned,
+    BinOp::IntGeSigned,
+    BinOp::IntGeUnsigned,
+    BinOp::FloatAdd,
+    BinOp::FloatSub,
+    BinOp::FloatMul,
+    BinOp::FloatDiv,
+    BinOp::FloatEq,
+    BinOp::FloatNe,
+    BinOp::FloatLt,
+    BinOp::FloatLe,
+    BinOp::FloatGt,
+    BinOp::FloatGe,
+];
+
+/// Parses the MIR in `source`, the text of `file`.
+pub fn parse_program(source: &str, file: FileId) -> Result<Program, Diagnostic> {
+    let tokens = lex(source, file)?;
+    let mut parser = Parser {
+        tokens,
+        index: 0,
+        classes: HashMap::new(),
+        generics: Vec::new(),
+        function_refs: Vec::new(),
+    };
+
+    parser.program()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum TokenKind {
+    /// Identifiers and keywords, locals like `_0` and blocks like `bb0`.
+    Ident(String),
+    /// Number literals with their type suffix, e.g. `-1i32` or `1.5f64`.
+    Number(String),
+    Punct(&'static str),
+    /// The text after `//`.
+    Comment(String),
+    Eof,
+}
+
+impl Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ident(text) | Self::Number(text) => write!(f, "`{}`", text),
+            Self::Punct(punct) => write!(f, "`{}`", punct),
+            Self::Comment(_) => write!(f, "comment"),
+            Self::Eof => write!(f, "end of file"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Token {
+    kind: TokenKind,
+    span: Span,
+    /// Whether a line ends between the previous token and this one.
+    newline: bool,
+}
+
+const PUNCTS: &[&str] = &[
+    "->", "::", ":", ";", ",", "=", "&", "*", "(", ")", "[", "]", "{", "}", "<", ">", ".", "#",
+    "-", "!",
+];
+
+fn lex(source: &str, file: FileId) -> Result<Vec<Token>, Diagnostic> {
+    let mut tokens = Vec::new();
+    let mut newline = true;
+    let mut index = 0;
+
+    while index < source.len() {
+        let rest = &source[index..];
+        let c = rest.chars().next().unwrap();
+
+        if c.is_whitespace() {
+            newline |= c == '\n';
+            index += c.len_utf8();
+            continue;
+        }
+
+        let is_number =
+            c.is_ascii_digit() || c == '-' && rest[1..].starts_with(|c: char| c.is_ascii_digit());
+
+        let (kind, len) = if let Some(comment) = rest.strip_prefix("//") {
+            let len = comment.find('\n').unwrap_or(comment.len());
+            let text = comment[..len].trim().to_string();
+            (TokenKind::Comment(text), len + 2)
+        } else if is_number {
+            let mut len = c.len_utf8() + digits(&rest[1..]);
+
+            if rest[len..].starts_with('.') && digits(&rest[len + 1..]) > 0 {
+                len += 1 + digits(&rest[len + 1..]);
+            }
+
+            len += ident_len(&rest[len..]);
+            (TokenKind::Number(rest[..len].to_string()), len)
+        } else if c.is_alphabetic() || c == '_' {
+            let len = ident_len(rest);
+            (TokenKind::Ident(rest[..len].to_string()), len)
+        } else if let Some(&punct) = PUNCTS.iter().find(|&&punct| rest.starts_with(punct)) {
+            (TokenKind::Punct(punct), punct.len())
+        } else {
+            let span = Span::new(index, index + c.len_utf8(), file);
+            let message = format!("unexpected character `{}`", c);
+            return Err(error(message, "not part of MIR", span));
+        };
+
+        tokens.push(Token {
+            kind,
+            span: Span::new(index, index + len, file),
+            newline,
+        });
+
+        newline = false;
+        index += len;
+    }
+
+    tokens.push(Token {
+        kind: TokenKind::Eof,
+        span: Span::new(source.len(), source.len(), file),
+        newline: true,
+    });
+
+    Ok(tokens)
+}
+
+fn error(message: impl Into<String>, label: impl Into<String>, span: Span) -> Diagnostic {
+    Diagnostic::error(message)
+        .with_code(ErrorCode::E0016)
+        .with_msg_span(label, span)
+}
+
+fn digits(text: &str) -> usize {
+    text.find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len())
+}
+
+fn ident_len(text: &str) -> usize {
+    text.find(|c: char| !c.is_alphanumeric() && c != '_')
+        .unwrap_or(text.len())
+}
+
+fn int_type(name: &str) -> Option<IntType> {
+    let (signed, bits) = match name.split_at_checked(1)? {
+        ("i", bits) => (true, bits),
+        ("u", bits) => (false, bits),
+        _ => return None,
+    };
+
+    if bits == "size" {
+        return Some(IntType::new(signed, None));
+    }
+
+    let size = IntSize::from_bit_width(bits.parse().ok()?)?;
+    Some(IntType::new(signed, Some(size)))
+}
+
+fn float_type(name: &str) -> Option<FloatType> {
+    let size = match name {
+        "f16" => FloatSize::F16,
+        "f32" => FloatSize::F32,
+        "f64" => FloatSize::F64,
+        _ => return None,
+    };
+
+    Some(FloatType::new(size))
+}
+
+/// Parses `N` from `prefixN`, e.g. the index of a local `_0`.
+fn index(text: &str, prefix: &str) -> Option<usize> {
+    let digits = text.strip_prefix(prefix)?;
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    digits.parse().ok()
+}
+
+/// Parses the source info in a comment, `[ident] [file[F] L..H]`.
+fn source_comment(text: &str) -> Option<(Option<&str>, Span)> {
+    let mut words: Vec<_> = text.split_whitespace().collect();
+    let mut span = Span::DUMMY;
+
+    let range = words.last().and_then(|word| word.split_once(".."));
+    if let Some((Ok(lo), Ok(hi))) = range.map(|(lo, hi)| (lo.parse(), hi.parse())) {
+        words.pop();
+        span.lo = lo;
+        span.hi = hi;
+
+        let file = words.last().and_then(|word| {
+            let index = word.strip_prefix("file[")?.strip_suffix(']')?;
+            index.parse().ok()
+        });
+
+        if let Some(file) = file {
+            words.pop();
+            span.file = Some(FileId::from_raw_index(file));
+        }
+    }
+
+    match words[..] {
+        [] => Some((None, span)),
+        [ident] if ident.chars().all(|c| c.is_alphanumeric() || c == '_') => {
+            Some((Some(ident), span))
+        }
+        _ => None,
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+    /// The classes by path, `None` if the path is used more than once.
+    classes: HashMap<String, Option<ClassId>>,
+    /// The generics in scope.
+    generics: Vec<Generic>,
+    /// Every function referred to by a field initializer.
+    function_refs: Vec<(FunctionId, Span)>,
+}
+
+impl Parser {
+    fn position(&self) -> usize {
+        let mut index = self.index;
+        while let TokenKind::Comment(_) = self.tokens[index].kind {
+            index += 1;
+        }
+
+        index
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position()]
+    }
+
+    fn next(&mut self) -> Token {
+        let index = self.position();
+        if index + 1 < self.tokens.len() {
+            self.index = index + 1;
+        }
+
+        self.tokens[index].clone()
+    }
+
+    fn is(&self, punct: &str) -> bool {
+        matches!(self.peek().kind, TokenKind::Punct(p) if p == punct)
+    }
+
+    fn is_ident(&self, ident: &str) -> bool {
+        matches!(self.peek().kind, TokenKind::Ident(ref text) if text == ident)
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        let is = self.is(punct);
+        if is {
+            self.next();
+        }
+
+        is
+    }
+
+    fn expected(&self, expected: impl Display) -> Diagnostic {
+        let token = self.peek();
+        error(
+            format!("expected {}", expected),
+            format!("found {}", token.kind),
+            token.span,
+        )
+    }
+
+    fn expect(&mut self, punct: &str) -> Result<Span, Diagnostic> {
+        if !self.is(punct) {
+            return Err(self.expected(format!("`{}`", punct)));
+        }
+
+        Ok(self.next().span)
+    }
+
+    fn expect_ident(&mut self, ident: &str) -> Result<Span, Diagnostic> {
+        if !self.is_ident(ident) {
+            return Err(self.expected(format!("`{}`", ident)));
+        }
+
+        Ok(self.next().span)
+    }
+
+    fn ident(&mut self) -> Result<Ident, Diagnostic> {
+        match self.peek().kind {
+            TokenKind::Ident(ref text) => {
+                let ident = Ident::new(text.as_str(), self.peek().span);
+                self.next();
+                Ok(ident)
+            }
+            _ => Err(self.expected("identifier")),
+        }
+    }
+
+    /// Parses `N` from the next token `prefixN`.
+    fn index(&mut self, prefix: &str, what: &str) -> Result<usize, Diagnostic> {
+        let index = match self.peek().kind {
+            TokenKind::Ident(ref text) | TokenKind::Number(ref text) => index(text, prefix),
+            _ => None,
+        };
+
+        match index {
+            Some(index) => {
+                self.next();
+                Ok(index)
+            }
+            None => Err(self.expected(what)),
+        }
+    }
+
+    /// Consumes a comment on the same line as the previous token.
+    fn trailing_comment(&mut self) -> Option<String> {
+        let token = &self.tokens[self.index];
+
+        match token.kind {
+            TokenKind::Comment(ref text) if !token.newline => {
+                let text = text.clone();
+                self.index += 1;
+                Some(text)
+            }
+            _ => None,
+        }
+    }
+
+    fn source_info(&mut self) -> SourceInfo {
+        let comment = self.trailing_comment();
+
+        match comment.as_deref().and_then(source_comment) {
+            Some((None, span)) => SourceInfo::new(span),
+            _ => SourceInfo::DUMMY,
+        }
+    }
+
+    fn comma_separated<T>(
+        &mut self,
+        close: &str,
+        mut f: impl FnMut(&mut Self) -> Result<T, Diagnostic>,
+    ) -> Result<Vec<T>, Diagnostic> {
+        let mut items = Vec::new();
+
+        while !self.is(close) {
+            items.push(f(self)?);
+
+            if !self.eat(",") {
+                break;
+            }
+        }
+
+        self.expect(close)?;
+        Ok(items)
+    }
+
+    fn program(&mut self) -> Result<Program, Diagnostic> {
+        self.declare_classes();
+
+        let mut program = Program::new();
+
+        loop {
+            if self.is_ident("class") {
+                let class = self.class()?;
+                program.classes.push(class);
+            } else if self.is_ident("fn") || self.is("#") {
+                let function = self.function()?;
+                program.functions.push(function);
+            } else if self.peek().kind == TokenKind::Eof {
+                break;
+            } else {
+                return Err(self.expected("`class` or `fn`"));
+            }
+        }
+
+        for &(function, span) in &self.function_refs {
+            if !program.functions.contains_key(function) {
+                let message = format!("unknown function fn[{}]", function.as_raw_index());
+                return Err(error(message, "not declared", span));
+            }
+        }
+
+        validate_parsed(&program)?;
+        Ok(program)
+    }
+
+    /// Numbers the classes, which may be used before they are declared.
+    fn declare_classes(&mut self) {
+        let mut id = 0;
+
+        for (i, token) in self.tokens.iter().enumerate() {
+            if token.kind != TokenKind::Ident(String::from("class")) || !token.newline {
+                continue;
+            }
+
+            let mut path = String::new();
+            for token in &self.tokens[i + 1..] {
+                match token.kind {
+                    TokenKind::Ident(ref ident) => path.push_str(ident),
+                    TokenKind::Punct("::") => path.push_str("::"),
+                    _ => break,
+                }
+            }
+
+            // a field may also be called `class`
+            if path.is_empty() {
+                continue;
+            }
+
+            let class = ClassId::from_raw_index(id);
+            self.classes
+                .entry(path)
+                .and_modify(|class| *class = None)
+                .or_insert(Some(class));
+
+            id += 1;
+        }
+    }
+
+    /// Parses `a::b::name`, returning the path and the name.
+    fn path(&mut self) -> Result<(Vec<Ident>, Ident), Diagnostic> {
+        let mut path = Vec::new();
+        let mut ident = self.ident()?;
+
+        while self.eat("::") {
+            path.push(ident);
+            ident = self.ident()?;
+        }
+
+        Ok((path, ident))
+    }
+
+    /// Returns the class declared at `path::ident`.
+    fn class_id(&self, path: &[Ident], ident: &Ident) -> Result<ClassId, Diagnostic> {
+        let mut name = String::new();
+        for segment in path {
+            name.push_str(segment);
+            name.push_str("::");
+        }
+        name.push_str(ident);
+
+        let span = match path.first() {
+            Some(first) => first.span() | ident.span(),
+            None => ident.span(),
+        };
+
+        match self.classes.get(&name) {
+            Some(&Some(class)) => Ok(class),
+            Some(None) => {
+                let message = format!("ambiguous class `{}`", name);
+                let label = "several classes have this path";
+                Err(error(message, label, span))
+            }
+            None => {
+                let message = format!("unknown type `{}`", name);
+                let label = "not a type, generic or class";
+                Err(error(message, label, span))
+            }
+        }
+    }
+
+    fn generic_params(&mut self) -> Result<Vec<Generic>, Diagnostic> {
+        if !self.eat("<") {
+            return Ok(Vec::new());
+        }
+
+        self.comma_separated(">", |parser| Ok(Generic::new(parser.ident()?)))
+    }
+
+    fn class(&mut self) -> Result<Class, Diagnostic> {
+        self.expect_ident("class")?;
+        let (path, ident) = self.path()?;
+        let generics = self.generic_params()?;
+
+        self.generics = generics.clone();
+        self.expect("{")?;
+
+        let fields = self.comma_separated("}", |parser| {
+            let ident = parser.ident()?;
+            parser.expect(":")?;
+            let ty = parser.ty()?;
+
+            let init = if parser.eat("=") {
+                let span = parser.expect_ident("fn")?;
+                let function = parser.function_index()?;
+                parser.function_refs.push((function, span));
+                Some(function)
+            } else {
+                None
+            };
+
+            Ok(Field { ident, ty, init })
+        })?;
+
+        Ok(Class {
+            ident,
+            path,
+            generics,
+            fields,
+        })
+    }
+
+    /// Parses the attributes of a function, `#[export]`, `#[inline]` and
+    /// `#[inline(never)]`.
+    fn attributes(&mut self) -> Result<(bool, Inline), Diagnostic> {
+        let mut export = false;
+        let mut inline = Inline::Auto;
+
+        while self.eat("#") {
+            self.expect("[")?;
+
+            if self.is_ident("export") {
+                self.next();
+                export = true;
+            } else {
+                self.expect_ident("inline")?;
+
+                inline = if self.eat("(") {
+                    self.expect_ident("never")?;
+                    self.expect(")")?;
+                    Inline::Never
+                } else {
+                    Inline::Always
+                };
+            }
+
+            self.expect("]")?;
+        }
+
+        Ok((export, inline))
+    }
+
+    fn function(&mut self) -> Result<Function, Diagnostic> {
+        let (export, inline) = self.attributes()?;
+
+        self.expect_ident("fn")?;
+        let (path, class, ident) = if self.eat("<") {
+            let (mut path, class_ident) = self.path()?;
+            let class = self.class_id(&path, &class_ident)?;
+            path.push(class_ident);
+
+            self.expect(">")?;
+            self.expect("::")?;
+            (path, Some(class), self.ident()?)
+        } else {
+            let (path, ident) = self.path()?;
+            (path, None, ident)
+        };
+
+        let generics = self.generic_params()?;
+        self.generics = generics.clone();
+
+        self.expect("(")?;
+        let arguments = self.comma_separated(")", |parser| {
+            let local = parser.index("_", "local")?;
+            parser.expect(":")?;
+            Ok((local, parser.ty()?))
+        })?;
+
+        let return_type = if self.eat("->") {
+            self.ty()?
+        } else {
+            Type::Void
+        };
+
+        let body = self.body()?;
+
+        let arguments = arguments.into_iter().map(|(local, ty)| {
+            let local = LocalId::from_raw_index(local);
+            let ident = match body.locals.get(local).and_then(|l| l.ident.clone()) {
+                Some(ident) => ident,
+                None => Ident::new(format!("_{}", local.as_raw_index()), Span::DUMMY),
+            };
+
+            FunctionArgument { ident, ty, local }
+        });
+
+        Ok(Function {
+            ident,
+            path,
+            class,
+            generics,
+            arguments: arguments.collect(),
+            return_type,
+            body,
+            inline,
+            export,
+        })
+    }
+
+    fn body(&mut self) -> Result<Body, Diagnostic> {
+        let mut body = Body::new();
+        self.expect("{")?;
+
+        while self.is_ident("let") {
+            let span = self.next().span;
+            let id = self.index("_", "local")?;
+            self.expect(":")?;
+            let ty = self.ty()?;
+            self.expect(";")?;
+
+            let (ident, source_info) = match self.trailing_comment() {
+                Some(comment) => match source_comment(&comment) {
+                    Some((ident, span)) => {
+                        let ident = ident.map(|ident| Ident::new(ident, span));
+                        (ident, SourceInfo::new(span))
+                    }
+                    None => (None, SourceInfo::DUMMY),
+                },
+                None => (None, SourceInfo::DUMMY),
+            };
+
+            let local = Local {
+                ident,
+                ty,
+                source_info,
+            };
+
+            if body
+                .locals
+                .insert(LocalId::from_raw_index(id), local)
+                .is_some()
+            {
+                let message = format!("local _{} is declared twice", id);
+                return Err(error(message, "declared again here", span));
+            }
+        }
+
+        while !self.eat("}") {
+            let span = self.peek().span;
+            let id = self.index("bb", "block or `}`")?;
+            self.expect(":")?;
+            let block = self.block()?;
+
+            if body
+                .blocks
+                .insert(BlockId::from_raw_index(id), block)
+                .is_some()
+            {
+                let message = format!("block bb{} is declared twice", id);
+                return Err(error(message, "declared again here", span));
+            }
+        }
+
+        Ok(body)
+    }
+
+    fn block(&mut self) -> Result<Block, Diagnostic> {
+        let mut block = Block::new();
+        self.expect("{")?;
+
+        while !self.eat("}") {
+            if block.is_terminated() {
+                return Err(self.expected("`}` after the terminator"));
+            }
+
+            let is_terminator = ["goto", "return", "switch", "assert"]
+                .iter()
+                .any(|keyword| self.is_ident(keyword));
+
+            if is_terminator {
+                let kind = self.terminator()?;
+                let source_info = self.source_info();
+                block.terminate(Terminator::new(kind, source_info));
+                continue;
+            }
+
+            let kind = if self.is_ident("drop") {
+                self.next();
+                StatementKind::Drop(self.value()?)
+            } else {
+                let place = self.place()?;
+                self.expect("=")?;
+                let value = self.value()?;
+                StatementKind::Assign(Assign { place, value })
+            };
+
+            let source_info = self.source_info();
+            block.push(Statement::new(kind, source_info));
+        }
+
+        Ok(block)
+    }
+
+    fn terminator(&mut self) -> Result<TerminatorKind, Diagnostic> {
+        let keyword = self.ident()?;
+
+        match keyword.value() {
+            "goto" => Ok(TerminatorKind::Goto(self.block_id()?)),
+            "return" => Ok(TerminatorKind::Return(self.operand()?)),
+            "assert" => {
+                self.expect("(")?;
+                let expected = !self.eat("!");
+                let cond = self.operand()?;
+                self.expect(",")?;
+                let message = self.assert_message()?;
+                self.expect(")")?;
+                self.expect("->")?;
+                let target = self.block_id()?;
+
+                Ok(TerminatorKind::Assert(Assert {
+                    cond,
+                    expected,
+                    message,
+                    target,
+                }))
+            }
+            _ => {
+                self.expect("(")?;
+                let operand = self.operand()?;
+                self.expect(")")?;
+
+                self.expect("[")?;
+                let targets = self.comma_separated("]", |parser| {
+                    let value = parser.integer("switch value")?;
+                    parser.expect("->")?;
+                    Ok((value, parser.block_id()?))
+                })?;
+
+                self.expect(",")?;
+                self.expect_ident("default")?;
+                self.expect("->")?;
+                let default = self.block_id()?;
+
+                Ok(TerminatorKind::Switch(
+                    operand,
+                    SwitchTargets { targets, default },
+                ))
+            }
+        }
+    }
+
+    fn assert_message(&mut self) -> Result<AssertMessage, Diagnostic> {
+        let span = self.peek().span;
+
+        match self.ident()?.value() {
+            "Overflow" => {
+                self.expect("(")?;
+                let op = self.binary_op()?;
+                self.expect(")")?;
+                Ok(AssertMessage::Overflow(op))
+            }
+            "NegOverflow" => Ok(AssertMessage::NegOverflow),
+            "DivisionByZero" => Ok(AssertMessage::DivisionByZero),
+            "NullDeref" => Ok(AssertMessage::NullDeref),
+            name => Err(error(
+                format!("unknown assert message `{}`", name),
+                "expected `Overflow(op)`, `NegOverflow`, `DivisionByZero` or `NullDeref`",
+                span,
+            )),
+        }
+    }
+
+    fn binary_op(&mut self) -> Result<BinOp, Diagnostic> {
+        let op = match self.peek().kind {
+            TokenKind::Ident(ref name) => BINARY_OPS.iter().find(|op| format!("{:?}", op) == *name),
+            _ => None,
+        };
+
+        match op {
+            Some(&op) => {
+                self.next();
+                Ok(op)
+            }
+            None => Err(self.expected("operator")),
+        }
+    }
+
+    /// Parses an integer without a suffix, e.g. an array length.
+    fn integer<T: FromStr>(&mut self, what: &str) -> Result<T, Diagnostic> {
+        let value = match self.peek().kind {
+            TokenKind::Number(ref text) => text.parse().ok(),
+            _ => None,
+        };
+
+        match value {
+            Some(value) => {
+                self.next();
+                Ok(value)
+            }
+            None => Err(self.expected(what)),
+        }
+    }
+
+    fn block_id(&mut self) -> Result<BlockId, Diagnostic> {
+        let index = self.index("bb", "block")?;
+        Ok(BlockId::from_raw_index(index))
+    }
+
+    fn function_index(&mut self) -> Result<FunctionId, Diagnostic> {
+        self.expect("[")?;
+        let index = self.integer("function number")?;
+        self.expect("]")?;
+
+        Ok(FunctionId::from_raw_index(index))
+    }
+
+    fn place(&mut self) -> Result<Place, Diagnostic> {
+        if self.eat("*") {
+            self.expect("(")?;
+            let mut place = self.place()?;
+            self.expect(")")?;
+
+            place.proj.push(Projection::Deref);
+            return Ok(place);
+        }
+
+        if self.eat("(") {
+            let mut place = self.place()?;
+            self.expect(")")?;
+            self.expect(".")?;
+
+            let field = self.integer("field number")?;
+            place
+                .proj
+                .push(Projection::Field(FieldId::from_raw_index(field)));
+            return Ok(place);
+        }
+
+        let local = self.index("_", "place")?;
+        Ok(Place::local(LocalId::from_raw_index(local)))
+    }
+
+    fn operand(&mut self) -> Result<Operand, Diagnostic> {
+        let token = self.peek().clone();
+
+        let constant = match token.kind {
+            TokenKind::Ident(ref text) => match text.as_str() {
+                "copy" => {
+                    self.next();
+                    return Ok(Operand::Copy(self.place()?));
+                }
+                "void" => {
+                    self.next();
+                    Constant::Void
+                }
+                "true" | "false" => {
+                    self.next();
+                    Constant::Bool(text == "true")
+                }
+                "null" => {
+                    self.next();
+                    self.expect("*")?;
+                    Constant::Null(self.ty()?)
+                }
+                "fn" => {
+                    self.next();
+                    let function = self.function_index()?;
+
+                    let generics = if self.eat("<") {
+                        self.comma_separated(">", Self::ty)?
+                    } else {
+                        Vec::new()
+                    };
+
+                    Constant::Function(function, generics)
+                }
+                _ if text.starts_with("NaN") || text.starts_with("inf") => {
+                    self.next();
+                    self.literal(text, token.span)?
+                }
+                _ => return Ok(Operand::Move(self.place()?)),
+            },
+            TokenKind::Number(ref text) => {
+                self.next();
+                self.literal(text, token.span)?
+            }
+            TokenKind::Punct("-") => {
+                self.next();
+
+                match self.peek().kind {
+                    TokenKind::Ident(ref text) if text.starts_with("inf") => {
+                        let text = format!("-{}", text);
+                        self.next();
+                        self.literal(&text, token.span)?
+                    }
+                    _ => return Err(self.expected("`inf`")),
+                }
+            }
+            _ => return Ok(Operand::Move(self.place()?)),
+        };
+
+        Ok(Operand::Constant(constant))
+    }
+
+    fn literal(&self, text: &str, span: Span) -> Result<Constant, Diagnostic> {
+        let invalid = || {
+            error(
+                format!("invalid literal `{}`", text),
+                "expected a number followed by its type, e.g. `1i32`",
+                span,
+            )
+        };
+
+        let sign = usize::from(text.starts_with('-'));
+        let digits = &text[sign..];
+        let len = if digits.starts_with("NaN") || digits.starts_with("inf") {
+            3
+        } else {
+            digits
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .unwrap_or(digits.len())
+        };
+
+        let (value, suffix) = text.split_at(sign + len);
+
+        if let Some(ty) = int_type(suffix) {
+            let value = match value.parse::<i64>() {
+                Ok(value) => value,
+                Err(_) => value.parse::<u64>().map_err(|_| invalid())? as i64,
+            };
+
+            return Ok(Constant::Integer(value, ty));
+        }
+
+        match (float_type(suffix), value.parse::<f64>()) {
+            (Some(ty), Ok(value)) => Ok(Constant::Float(value, ty)),
+            _ => Err(invalid()),
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, Diagnostic> {
+        if self.eat("&") {
+            return Ok(Value::Address(self.place()?));
+        }
+
+        if let TokenKind::Ident(ref name) = self.peek().kind {
+            let name = name.clone();
+
+            if let Some(&op) = UNARY_OPS.iter().find(|op| format!("{:?}", op) == name) {
+                self.next();
+                self.expect("(")?;
+                let operand = self.operand()?;
+                self.expect(")")?;
+
+                return Ok(Value::UnaryOp(op, operand));
+            }
+
+            if let Some(&op) = BINARY_OPS.iter().find(|op| format!("{:?}", op) == name) {
+                self.next();
+                self.expect("(")?;
+                let lhs = self.operand()?;
+                self.expect(",")?;
+                let rhs = self.operand()?;
+                self.expect(")")?;
+
+                return Ok(Value::BinaryOp(op, lhs, rhs));
+            }
+
+            if let Some(intrinsic) = self.intrinsic(&name)? {
+                return Ok(Value::Intrinsic(intrinsic));
+            }
+        }
+
+        let operand = self.operand()?;
+
+        // a call must start on the same line as the callee
+        if self.is("(") && !self.peek().newline {
+            self.next();
+            let arguments = self.comma_separated(")", Self::operand)?;
+            return Ok(Value::Call(operand, arguments));
+        }
+
+        Ok(Value::Use(operand))
+    }
+
+    fn intrinsic(&mut self, name: &str) -> Result<Option<Intrinsic>, Diagnostic> {
+        let is_intrinsic = matches!(
+            name,
+            "sizeof"
+                | "alignof"
+                | "bitcast"
+                | "malloc"
+                | "free"
+                | "memcpy"
+                | "ptrtoint"
+                | "inttoptr"
+                | "ptrtoptr"
+                | "inttoint"
+                | "inttofloat"
+                | "floattoint"
+                | "floattofloat"
+                | "overflows"
+                | "wrapping"
+        );
+
+        if !is_intrinsic {
+            return Ok(None);
+        }
+
+        self.next();
+        self.expect("(")?;
+
+        let intrinsic = match name {
+            "sizeof" => Intrinsic::Sizeof(self.ty()?),
+            "alignof" => Intrinsic::Alignof(self.ty()?),
+            "bitcast" => {
+                let operand = self.operand()?;
+                self.expect_ident("as")?;
+                Intrinsic::Bitcast(operand, self.ty()?)
+            }
+            "malloc" => {
+                let operand = self.operand()?;
+                self.expect(",")?;
+                Intrinsic::Malloc(operand, self.ty()?)
+            }
+            "free" => Intrinsic::Free(self.operand()?),
+            "memcpy" => {
+                let dst = self.operand()?;
+                self.expect(",")?;
+                let src = self.operand()?;
+                self.expect(",")?;
+                Intrinsic::Memcpy(dst, src, self.operand()?)
+            }
+            "overflows" | "wrapping" => {
+                let op = self.binary_op()?;
+                self.expect(",")?;
+                let lhs = self.operand()?;
+                self.expect(",")?;
+                let rhs = self.operand()?;
+
+                match name {
+                    "overflows" => Intrinsic::Overflows(op, lhs, rhs),
+                    _ => Intrinsic::Wrapping(op, lhs, rhs),
+                }
+            }
+            _ => {
+                let operand = self.operand()?;
+                self.expect_ident("as")?;
+                let span = self.peek().span;
+                let from = self.ty()?;
+                self.expect_ident("as")?;
+                let to = self.ty()?;
+
+                match (name, from, to) {
+                    ("ptrtoint", Type::Pointer(from), Type::Int(to)) => {
+                        Intrinsic::PtrToInt(operand, from, to)
+                    }
+                    ("inttoptr", Type::Int(from), Type::Pointer(to)) => {
+                        Intrinsic::IntToPtr(operand, from, to)
+                    }
+                    ("ptrtoptr", Type::Pointer(from), Type::Pointer(to)) => {
+                        Intrinsic::PtrToPtr(operand, from, to)
+                    }
+                    ("inttoint", Type::Int(from), Type::Int(to)) => {
+                        Intrinsic::IntToInt(operand, from, to)
+                    }
+                    ("inttofloat", Type::Int(from), Type::Float(to)) => {
+                        Intrinsic::IntToFloat(operand, from, to)
+                    }
+                    ("floattoint", Type::Float(from), Type::Int(to)) => {
+                        Intrinsic::FloatToInt(operand, from, to)
+                    }
+                    ("floattofloat", Type::Float(from), Type::Float(to)) => {
+                        Intrinsic::FloatToFloat(operand, from, to)
+                    }
+                    (_, from, to) => {
+                        let err = error(
+                            format!("`{}` cannot convert `{}` to `{}`", name, from, to),
+                            "invalid types",
+                            span | self.tokens[self.index - 1].span,
+                        );
+
+                        return Err(err);
+                    }
+                }
+            }
+        };
+
+        self.expect(")")?;
+        Ok(Some(intrinsic))
+    }
+
+    fn ty(&mut self) -> Result<Type, Diagnostic> {
+        if self.eat("*") {
+            return Ok(Type::Pointer(PointerType::new(self.ty()?)));
+        }
+
+        if self.eat("[") {
+            let element = self.ty()?;
+
+            if !self.eat(";") {
+                self.expect("]")?;
+                return Ok(Type::slice(element));
+            }
+
+            let length = self.integer("array length")?;
+            self.expect("]")?;
+
+            return Ok(Type::array(element, length));
+        }
+
+        if self.eat("(") {
+            return Ok(Type::tuple(self.comma_separated(")", Self::ty)?));
+        }
+
+        let (path, ident) = self.path()?;
+
+        if path.is_empty() && ident.value() == "fn" {
+            self.expect("(")?;
+            let arguments = self.comma_separated(")", Self::ty)?;
+
+            let return_type = if self.eat("->") {
+                self.ty()?
+            } else {
+                Type::Void
+            };
+
+            return Ok(Type::function(arguments, return_type));
+        }
+
+        if path.is_empty() {
+            match ident.value() {
+                "void" => return Ok(Type::Void),
+                "bool" => return Ok(Type::Bool),
+                name => {
+                    if let Some(ty) = int_type(name) {
+                        return Ok(Type::Int(ty));
+                    }
+
+                    if let Some(ty) = float_type(name) {
+                        return Ok(Type::Float(ty));
+                    }
+                }
+            }
+
+            if let Some(generic) = self.generics.iter().find(|g| g.ident == ident) {
+                return Ok(Type::Generic(generic.clone()));
+            }
+        }
+
+        let class = self.class_id(&path, &ident)?;
+
+        let generics = if self.eat("<") {
+            self.comma_separated(">", Self::ty)?
+        } else {
+            Vec::new()
+        };
+
+        let mut class_type = ClassType::new(class, ident, generics);
+        class_type.path = path;
+        Ok(Type::Class(class_type))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: &str = "\
+class geo::Point<T> {
+	x: T,
+	y: i32 = fn[0],
+}
+
+#[inline(never)]
+fn geo::origin() -> i32 {
+	let _0: f64; // file[0] 1..2
+	let _1: bool;
+	let _2: i32;
+	let _3: *f64;
+
+	bb0: {
+		_0 = 1.5f64
+		_0 = FloatNeg(copy _0)
+		_0 = NaNf64
+		_0 = -inff64
+		_1 = FloatLt(copy _0, 0.25f64) // file[0] 3..4
+		_3 = null *f64
+		_3 = &_0
+		_2 = floattoint(copy *(_3) as f64 as i32)
+		switch (_1) [0 -> bb1, 1 -> bb2], default -> bb1 // file[0] 5..6
+	}
+	bb1: {
+		return -7i32
+	}
+	bb2: {
+		return _2
+	}
+}
+
+#[inline]
+fn geo::Point::x<T>(_0: *geo::Point<T>) -> T {
+	let _0: *geo::Point<T>; // self file[0] 7..11
+
+	bb0: {
+		return copy (*(_0)).0
+	}
+}
+
+fn main(_0: i32, _1: **i8) -> i32 {
+	let _0: i32; // argc
+	let _1: **i8; // argv
+	let _2: geo::Point<i32>;
+	let _3: usize;
+	let _4: *geo::Point<i32>;
+	let _5: i32;
+	let _6: bool;
+
+	bb0: {
+		_3 = sizeof(geo::Point<i32>)
+		(_2).0 = fn[0]<>()
+		_4 = &_2
+		drop fn[1]<i32>(_4)
+		_5 = inttoint(_3 as usize as i32)
+		_6 = overflows(IntAdd, copy _5, 1i32)
+		assert(!_6, Overflow(IntAdd)) -> bb1
+	}
+	bb1: {
+		_5 = wrapping(IntAdd, copy _5, 1i32)
+		return _5
+	}
+}";
+
+    fn parse(source: &str) -> Result<Program, Diagnostic> {
+        parse_program(source, FileId::from_raw_index(0))
+    }
+
+    fn error(source: &str) -> String {
+        parse(source).unwrap_err().title
+    }
+
+    #[test]
+    fn round_trip() {
+        let program = parse(PROGRAM).unwrap();
+        assert_eq!(program.to_string().trim_end(), PROGRAM);
+
+        let function = &program[FunctionId::from_raw_index(1)];
+        assert_eq!(function.inline, Inline::Always);
+        assert_eq!(function.arguments[0].ident, Ident::from("self"));
+        assert_eq!(
+            function.body.locals.first().unwrap().source_info.span.hi,
+            11
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let function = |body: &str| format!("fn f() -> i32 {{\n\tlet _0: i32;\n{}\n}}", body);
+
+        assert_eq!(
+            error(&function("\tbb0: {\n\t\treturn 0\n\t}")),
+            "invalid literal `0`"
+        );
+        assert_eq!(
+            error(&function("\tlet _0: i32;")),
+            "local _0 is declared twice"
+        );
+        assert_eq!(
+            error(&function("\tbb0: {\n\t\treturn _1\n\t}")),
+            "invalid MIR: use of missing local _1"
+        );
+        assert_eq!(error("fn f(_0: Foo) {\n}"), "unknown type `Foo`");
+        assert_eq!(
+            error("class a::A {\n}\nclass b::A {\n}\nfn f(_0: A) {\n}"),
+            "unknown type `A`"
+        );
+        assert_eq!(
+            error("class a::A {\n}\nclass a::A {\n}\nfn f(_0: a::A) {\n}"),
+            "ambiguous class `a::A`"
+        );
+        assert_eq!(error("fn f() $"), "unexpected character `$`");
+    }
+}
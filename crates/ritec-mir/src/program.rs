@@ -1,11 +1,14 @@
 use std::{
+    collections::HashMap,
     fmt::Display,
     ops::{Index, IndexMut},
 };
 
 use ritec_core::Arena;
 
-use crate::{Class, ClassId, Function, FunctionId};
+use crate::{
+    Class, ClassId, Constant, Function, FunctionId, GenericMap, Operand, Place, Projection, Type,
+};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Program {
@@ -20,6 +23,107 @@ impl Program {
             functions: Arena::new(),
         }
     }
+
+    /// Calls `f` with every type in the program, see [`Type::visit_mut`].
+    pub fn visit_types_mut(&mut self, f: &mut impl FnMut(&mut Type)) {
+        for class in self.classes.values_mut() {
+            for field in &mut class.fields {
+                field.ty.visit_mut(f);
+            }
+        }
+
+        for function in self.functions.values_mut() {
+            for argument in &mut function.arguments {
+                argument.ty.visit_mut(f);
+            }
+
+            function.return_type.visit_mut(f);
+            function.body.visit_types_mut(f);
+        }
+    }
+
+    /// Sets the paths of the class types in the program to the path of their
+    /// class, so they can be told apart when displayed.
+    pub fn set_class_paths(&mut self) {
+        let paths: HashMap<_, _> = self
+            .classes
+            .iter()
+            .map(|(id, class)| (id, class.path.clone()))
+            .collect();
+
+        self.visit_types_mut(&mut |ty| {
+            if let Type::Class(class_type) = ty {
+                if let Some(path) = paths.get(&class_type.class) {
+                    class_type.path = path.clone();
+                }
+            }
+        });
+    }
+
+    /// Returns the type of `proj` applied to a place of type `ty`, which must
+    /// not contain any generics.
+    pub fn projection_type(&self, ty: &Type, proj: &Projection) -> Type {
+        match (proj, ty) {
+            (Projection::Deref, Type::Pointer(pointer)) => pointer.pointee().clone(),
+            (Projection::Field(field), Type::Tuple(tuple)) => {
+                tuple.fields[field.as_raw_index()].clone()
+            }
+            (Projection::Field(field), Type::Class(class_type)) => {
+                let class = &self[class_type.class];
+                let generics = GenericMap::new(&class.generics, &class_type.generics);
+
+                let mut field_type = class.fields[field.as_raw_index()].ty.clone();
+                field_type.instantiate(&generics);
+                field_type
+            }
+            _ => panic!("invalid projection {:?} of {}", proj, ty),
+        }
+    }
+
+    /// Returns the type of `place` in `function` instantiated with `generics`.
+    pub fn place_type(&self, function: &Function, generics: &[Type], place: &Place) -> Type {
+        let generic_map = GenericMap::new(&function.generics, generics);
+
+        let mut ty = function.body[place.local].ty.clone();
+        ty.instantiate(&generic_map);
+
+        for proj in &place.proj {
+            ty = self.projection_type(&ty, proj);
+        }
+
+        ty
+    }
+
+    /// Returns the type of `operand` in `function` instantiated with `generics`.
+    pub fn operand_type(&self, function: &Function, generics: &[Type], operand: &Operand) -> Type {
+        let generic_map = GenericMap::new(&function.generics, generics);
+
+        let mut ty = match operand {
+            Operand::Copy(place) | Operand::Move(place) => {
+                return self.place_type(function, generics, place);
+            }
+            Operand::Constant(Constant::Void) => Type::Void,
+            Operand::Constant(Constant::Null(ty)) => Type::pointer(ty.clone()),
+            Operand::Constant(Constant::Function(id, callee_generics)) => {
+                let callee = &self[*id];
+
+                let mut callee_generics = callee_generics.clone();
+                for generic in &mut callee_generics {
+                    generic.instantiate(&generic_map);
+                }
+
+                let mut ty = Type::Function(callee.ty());
+                ty.instantiate(&GenericMap::new(&callee.generics, &callee_generics));
+                return ty;
+            }
+            Operand::Constant(Constant::Integer(_, ty)) => Type::Int(ty.clone()),
+            Operand::Constant(Constant::Float(_, ty)) => Type::Float(ty.clone()),
+            Operand::Constant(Constant::Bool(_)) => Type::Bool,
+        };
+
+        ty.instantiate(&generic_map);
+        ty
+    }
 }
 
 impl Index<ClassId> for Program {
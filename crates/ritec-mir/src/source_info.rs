@@ -0,0 +1,51 @@
+use std::fmt::{self, Display};
+
+use ritec_core::Span;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SourceInfo {
+    pub span: Span,
+}
+
+impl SourceInfo {
+    pub const DUMMY: Self = Self::new(Span::DUMMY);
+
+    pub const fn new(span: Span) -> Self {
+        Self { span }
+    }
+
+    pub const fn is_dummy(&self) -> bool {
+        self.span.is_dummy()
+    }
+
+    /// Returns the trailing comment used when displaying MIR, or an empty
+    /// string if the span is a dummy.
+    pub fn comment(&self) -> String {
+        if self.is_dummy() {
+            String::new()
+        } else {
+            format!(" // {}", self)
+        }
+    }
+}
+
+impl From<Span> for SourceInfo {
+    fn from(span: Span) -> Self {
+        Self::new(span)
+    }
+}
+
+impl Display for SourceInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.span.file {
+            Some(file) => write!(
+                f,
+                "file[{}] {}..{}",
+                file.as_raw_index(),
+                self.span.lo,
+                self.span.hi
+            ),
+            None => write!(f, "{}..{}", self.span.lo, self.span.hi),
+        }
+    }
+}
@@ -2,7 +2,7 @@ use std::fmt::{self, Display};
 
 use ritec_core::Id;
 
-use crate::{Field, LocalId, Value};
+use crate::{Field, LocalId, Operand, SourceInfo, Value};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Projection {
@@ -64,18 +64,34 @@ impl Display for Assign {
 pub type StmtId = Id<Statement>;
 
 #[derive(Clone, Debug, PartialEq)]
-pub enum Statement {
+pub enum StatementKind {
     Assign(Assign),
     Drop(Value),
 }
 
-impl From<Assign> for Statement {
+impl StatementKind {
+    pub fn operands(&self) -> Vec<&Operand> {
+        match self {
+            Self::Assign(assign) => assign.value.operands(),
+            Self::Drop(value) => value.operands(),
+        }
+    }
+
+    pub fn operands_mut(&mut self) -> Vec<&mut Operand> {
+        match self {
+            Self::Assign(assign) => assign.value.operands_mut(),
+            Self::Drop(value) => value.operands_mut(),
+        }
+    }
+}
+
+impl From<Assign> for StatementKind {
     fn from(assign: Assign) -> Self {
         Self::Assign(assign)
     }
 }
 
-impl Display for Statement {
+impl Display for StatementKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Assign(assign) => write!(f, "{}", assign),
@@ -83,3 +99,24 @@ impl Display for Statement {
         }
     }
 }
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statement {
+    pub kind: StatementKind,
+    pub source_info: SourceInfo,
+}
+
+impl Statement {
+    pub fn new(kind: impl Into<StatementKind>, source_info: impl Into<SourceInfo>) -> Self {
+        Self {
+            kind: kind.into(),
+            source_info: source_info.into(),
+        }
+    }
+}
+
+impl Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.kind, self.source_info.comment())
+    }
+}
@@ -1,6 +1,8 @@
 use std::fmt::{self, Display};
 
-use crate::{BlockId, Operand};
+use ritec_core::{SourceMap, Span};
+
+use crate::{BinOp, BlockId, Operand, SourceInfo};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct SwitchTargets {
@@ -22,23 +24,103 @@ impl SwitchTargets {
     }
 }
 
+/// Why an [`Assert`] failed, displayed as the message the program panics
+/// with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AssertMessage {
+    Overflow(BinOp),
+    NegOverflow,
+    DivisionByZero,
+    NullDeref,
+}
+
+impl AssertMessage {
+    /// Returns the line a program prints when an assert fails at `span`.
+    pub fn panic_message(&self, span: Span, source_map: &SourceMap) -> String {
+        match source_map.location(span) {
+            Some(location) => format!("panicked at {}: {}\n", location, self),
+            None => format!("panicked: {}\n", self),
+        }
+    }
+}
+
+impl Display for AssertMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overflow(BinOp::IntAdd) => write!(f, "attempt to add with overflow"),
+            Self::Overflow(BinOp::IntSub) => write!(f, "attempt to subtract with overflow"),
+            Self::Overflow(BinOp::IntMul) => write!(f, "attempt to multiply with overflow"),
+            Self::Overflow(BinOp::IntDivSigned | BinOp::IntDivUnsigned) => {
+                write!(f, "attempt to divide with overflow")
+            }
+            Self::Overflow(op) => write!(f, "attempt to compute `{:?}` with overflow", op),
+            Self::NegOverflow => write!(f, "attempt to negate with overflow"),
+            Self::DivisionByZero => write!(f, "attempt to divide by zero"),
+            Self::NullDeref => write!(f, "attempt to dereference a null pointer"),
+        }
+    }
+}
+
+/// Continues at `target` if `cond` is `expected`, otherwise the program
+/// panics with `message`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Assert {
+    pub cond: Operand,
+    pub expected: bool,
+    pub message: AssertMessage,
+    pub target: BlockId,
+}
+
 #[derive(Clone, Debug, PartialEq)]
-pub enum Terminator {
+pub enum TerminatorKind {
     Goto(BlockId),
     Return(Operand),
     Switch(Operand, SwitchTargets),
+    Assert(Assert),
 }
 
-impl Terminator {
+impl TerminatorKind {
+    pub fn operands(&self) -> Vec<&Operand> {
+        match self {
+            Self::Goto(_) => Vec::new(),
+            Self::Return(operand) | Self::Switch(operand, _) => vec![operand],
+            Self::Assert(assert) => vec![&assert.cond],
+        }
+    }
+
+    pub fn operands_mut(&mut self) -> Vec<&mut Operand> {
+        match self {
+            Self::Goto(_) => Vec::new(),
+            Self::Return(operand) | Self::Switch(operand, _) => vec![operand],
+            Self::Assert(assert) => vec![&mut assert.cond],
+        }
+    }
+
+    /// Returns the blocks control can flow to, a block may appear more than
+    /// once if several switch targets lead to it.
     pub fn successors(&self) -> Vec<BlockId> {
         match self {
+            Self::Goto(target) => vec![*target],
+            Self::Return(_) => Vec::new(),
+            Self::Switch(_, targets) => targets.successors().copied().collect(),
+            Self::Assert(assert) => vec![assert.target],
+        }
+    }
+
+    pub fn successors_mut(&mut self) -> Vec<&mut BlockId> {
+        match self {
+            Self::Goto(target) | Self::Assert(Assert { target, .. }) => vec![target],
             Self::Return(_) => Vec::new(),
-            _ => Vec::new(),
+            Self::Switch(_, targets) => {
+                let mut successors: Vec<_> = targets.targets.iter_mut().map(|(_, t)| t).collect();
+                successors.push(&mut targets.default);
+                successors
+            }
         }
     }
 }
 
-impl Display for Terminator {
+impl Display for TerminatorKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Goto(target) => write!(f, "goto bb{}", target.as_raw_index()),
@@ -58,6 +140,39 @@ impl Display for Terminator {
                     switch_targets.default.as_raw_index()
                 )
             }
+            Self::Assert(assert) => write!(
+                f,
+                "assert({}{}, {:?}) -> bb{}",
+                if assert.expected { "" } else { "!" },
+                assert.cond,
+                assert.message,
+                assert.target.as_raw_index()
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Terminator {
+    pub kind: TerminatorKind,
+    pub source_info: SourceInfo,
+}
+
+impl Terminator {
+    pub fn new(kind: TerminatorKind, source_info: impl Into<SourceInfo>) -> Self {
+        Self {
+            kind,
+            source_info: source_info.into(),
         }
     }
+
+    pub fn successors(&self) -> Vec<BlockId> {
+        self.kind.successors()
+    }
+}
+
+impl Display for Terminator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.kind, self.source_info.comment())
+    }
 }
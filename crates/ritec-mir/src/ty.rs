@@ -248,6 +248,10 @@ impl Display for TupleType {
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct ClassType {
     pub class: ClassId,
+    /// The modules the class is declared in, see [`Program::set_class_paths`].
+    ///
+    /// [`Program::set_class_paths`]: crate::Program::set_class_paths
+    pub path: Vec<Ident>,
     pub ident: Ident,
     pub generics: Vec<Type>,
 }
@@ -256,6 +260,7 @@ impl ClassType {
     pub fn new(class: ClassId, ident: Ident, generics: impl Into<Vec<Type>>) -> Self {
         Self {
             class,
+            path: Vec::new(),
             ident,
             generics: generics.into(),
         }
@@ -270,6 +275,10 @@ impl ClassType {
 
 impl Display for ClassType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in &self.path {
+            write!(f, "{}::", segment)?;
+        }
+
         if self.generics.is_empty() {
             write!(f, "{}", self.ident)
         } else {
@@ -353,6 +362,35 @@ impl Type {
         }
     }
 
+    /// Calls `f` with `self` and every type nested in it.
+    pub fn visit_mut(&mut self, f: &mut impl FnMut(&mut Type)) {
+        f(self);
+
+        match self {
+            Type::Pointer(t) => t.pointee.visit_mut(f),
+            Type::Array(t) => t.element.visit_mut(f),
+            Type::Slice(t) => t.element.visit_mut(f),
+            Type::Function(t) => {
+                for argument in &mut t.arguments {
+                    argument.visit_mut(f);
+                }
+
+                t.return_type.visit_mut(f);
+            }
+            Type::Tuple(t) => {
+                for field in &mut t.fields {
+                    field.visit_mut(f);
+                }
+            }
+            Type::Class(t) => {
+                for generic in &mut t.generics {
+                    generic.visit_mut(f);
+                }
+            }
+            Type::Void | Type::Bool | Type::Int(_) | Type::Float(_) | Type::Generic(_) => {}
+        }
+    }
+
     pub fn instantiate(&mut self, generics: &GenericMap) {
         match self {
             Type::Void => {}
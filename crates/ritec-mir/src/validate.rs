@@ -0,0 +1,583 @@
+use std::fmt::Display;
+
+use ritec_core::Span;
+use ritec_error::{Diagnostic, ErrorCode};
+
+use crate::{
+    BinOp, BlockId, Constant, Function, FunctionId, GenericMap, Intrinsic, Operand, Place, Program,
+    Projection, Statement, StatementKind, Terminator, TerminatorKind, Type, UnaryOp, Value,
+};
+
+/// Checks that every function of `program` is well formed, returns an
+/// internal compiler error describing the first problem found.
+pub fn validate(program: &Program) -> Result<(), Diagnostic> {
+    for id in program.functions.keys() {
+        validate_function(program, id)?;
+    }
+
+    Ok(())
+}
+
+/// Checks that parsed MIR is well formed, unlike [`validate`] this
+/// reports problems as errors in the input.
+pub(crate) fn validate_parsed(program: &Program) -> Result<(), Diagnostic> {
+    for id in program.functions.keys() {
+        let mut validator = Validator::new(program, id);
+        validator.parsed = true;
+        validator.validate()?;
+    }
+
+    Ok(())
+}
+
+/// Checks that the body of `function` is well formed and type checks, with
+/// its generics left as generics.
+pub fn validate_function(program: &Program, function: FunctionId) -> Result<(), Diagnostic> {
+    Validator::new(program, function).validate()
+}
+
+struct Validator<'a> {
+    program: &'a Program,
+    function: &'a Function,
+    block: Option<BlockId>,
+    span: Span,
+    /// Whether the MIR was parsed rather than built by the compiler.
+    parsed: bool,
+}
+
+impl<'a> Validator<'a> {
+    fn new(program: &'a Program, function: FunctionId) -> Self {
+        Self {
+            program,
+            function: &program[function],
+            block: None,
+            span: Span::DUMMY,
+            parsed: false,
+        }
+    }
+
+    fn error(&self, message: impl Display) -> Diagnostic {
+        let mut name = String::new();
+        for segment in &self.function.path {
+            name.push_str(&format!("{}::", segment));
+        }
+        name.push_str(self.function.ident.value());
+
+        let location = match self.block {
+            Some(block) => format!("in `{}` at bb{}", name, block.as_raw_index()),
+            None => format!("in `{}`", name),
+        };
+
+        if self.parsed {
+            return Diagnostic::error(format!("invalid MIR: {}", message))
+                .with_code(ErrorCode::E0016)
+                .with_note(location);
+        }
+
+        let mut err = Diagnostic::error(format!("internal compiler error: {}", message))
+            .with_note(location)
+            .with_note("this is a bug in the compiler, the MIR is malformed");
+
+        if !self.span.is_dummy() {
+            err.add_msg_span("while compiling this", self.span);
+        }
+
+        err
+    }
+
+    fn expect(&self, found: &Type, expected: &Type, what: &str) -> Result<(), Diagnostic> {
+        if found == expected {
+            return Ok(());
+        }
+
+        Err(self.error(format!(
+            "{} has type `{}`, expected `{}`",
+            what, found, expected
+        )))
+    }
+
+    fn validate(&mut self) -> Result<(), Diagnostic> {
+        let body = &self.function.body;
+
+        if body.blocks.is_empty() {
+            return Err(self.error("function has no blocks"));
+        }
+
+        for argument in &self.function.arguments {
+            let Some(local) = body.locals.get(argument.local) else {
+                return Err(self.error(format!(
+                    "argument `{}` is stored in missing local _{}",
+                    argument.ident,
+                    argument.local.as_raw_index()
+                )));
+            };
+
+            self.expect(
+                &local.ty,
+                &argument.ty,
+                &format!("argument `{}`", argument.ident),
+            )?;
+        }
+
+        for (id, block) in body.blocks.iter() {
+            self.block = Some(id);
+
+            for statement in &block.statements {
+                self.span = statement.source_info.span;
+                self.validate_statement(statement)?;
+            }
+
+            let Some(ref terminator) = block.terminator else {
+                self.span = Span::DUMMY;
+                return Err(self.error("block has no terminator"));
+            };
+
+            self.span = terminator.source_info.span;
+            self.validate_terminator(terminator)?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_statement(&self, statement: &Statement) -> Result<(), Diagnostic> {
+        match statement.kind {
+            StatementKind::Assign(ref assign) => {
+                let place = self.place_type(&assign.place)?;
+                let value = self.value_type(&assign.value)?;
+
+                let what = format!("value assigned to `{}`", assign.place);
+                self.expect(&value, &place, &what)
+            }
+            StatementKind::Drop(ref value) => {
+                self.value_type(value)?;
+                Ok(())
+            }
+        }
+    }
+
+    fn validate_terminator(&self, terminator: &Terminator) -> Result<(), Diagnostic> {
+        for target in terminator.successors() {
+            if !self.function.body.blocks.contains_key(target) {
+                return Err(
+                    self.error(format!("jump to missing block bb{}", target.as_raw_index()))
+                );
+            }
+        }
+
+        match terminator.kind {
+            TerminatorKind::Goto(_) => Ok(()),
+            TerminatorKind::Return(ref operand) => {
+                let ty = self.operand_type(operand)?;
+                self.expect(&ty, &self.function.return_type, "returned value")
+            }
+            TerminatorKind::Switch(ref operand, _) => match self.operand_type(operand)? {
+                Type::Int(_) | Type::Bool => Ok(()),
+                ty => Err(self.error(format!("switch on `{}`", ty))),
+            },
+            TerminatorKind::Assert(ref assert) => {
+                let ty = self.operand_type(&assert.cond)?;
+                self.expect(&ty, &Type::Bool, "assert condition")
+            }
+        }
+    }
+
+    fn place_type(&self, place: &Place) -> Result<Type, Diagnostic> {
+        let Some(local) = self.function.body.locals.get(place.local) else {
+            return Err(self.error(format!(
+                "use of missing local _{}",
+                place.local.as_raw_index()
+            )));
+        };
+
+        let mut ty = local.ty.clone();
+        for proj in &place.proj {
+            let valid = match (proj, &ty) {
+                (Projection::Deref, Type::Pointer(_)) => true,
+                (Projection::Field(field), Type::Tuple(tuple)) => {
+                    field.as_raw_index() < tuple.fields.len()
+                }
+                (Projection::Field(field), Type::Class(class_type)) => {
+                    match self.program.classes.get(class_type.class) {
+                        Some(class) => field.as_raw_index() < class.fields.len(),
+                        None => false,
+                    }
+                }
+                _ => false,
+            };
+
+            if !valid {
+                return Err(self.error(format!("invalid projection in `{}` of `{}`", place, ty)));
+            }
+
+            ty = self.program.projection_type(&ty, proj);
+        }
+
+        Ok(ty)
+    }
+
+    fn operand_type(&self, operand: &Operand) -> Result<Type, Diagnostic> {
+        match operand {
+            Operand::Copy(place) | Operand::Move(place) => self.place_type(place),
+            Operand::Constant(Constant::Function(id, generics)) => {
+                let Some(callee) = self.program.functions.get(*id) else {
+                    return Err(self.error(format!(
+                        "reference to missing function fn[{}]",
+                        id.as_raw_index()
+                    )));
+                };
+
+                if callee.generics.len() != generics.len() {
+                    return Err(self.error(format!(
+                        "`{}` takes {} generic arguments, but {} were given",
+                        callee.ident,
+                        callee.generics.len(),
+                        generics.len()
+                    )));
+                }
+
+                let mut ty = Type::Function(callee.ty());
+                ty.instantiate(&GenericMap::new(&callee.generics, generics));
+                Ok(ty)
+            }
+            Operand::Constant(_) => {
+                let generics: Vec<_> = (self.function.generics.iter())
+                    .map(|generic| Type::Generic(generic.clone()))
+                    .collect();
+
+                Ok(self.program.operand_type(self.function, &generics, operand))
+            }
+        }
+    }
+
+    fn value_type(&self, value: &Value) -> Result<Type, Diagnostic> {
+        match value {
+            Value::Use(operand) => self.operand_type(operand),
+            Value::Address(place) => Ok(Type::pointer(self.place_type(place)?)),
+            Value::UnaryOp(op, operand) => {
+                let ty = self.operand_type(operand)?;
+
+                let valid = match op {
+                    UnaryOp::IntNot => matches!(ty, Type::Int(_) | Type::Bool),
+                    UnaryOp::IntNeg => matches!(ty, Type::Int(_)),
+                    UnaryOp::FloatNeg => matches!(ty, Type::Float(_)),
+                };
+
+                if !valid {
+                    return Err(self.error(format!("`{:?}` of `{}`", op, ty)));
+                }
+
+                Ok(ty)
+            }
+            Value::BinaryOp(op, lhs, rhs) => {
+                let lhs = self.operand_type(lhs)?;
+                let rhs = self.operand_type(rhs)?;
+
+                if lhs != rhs {
+                    return Err(self.error(format!(
+                        "`{:?}` of mismatched types `{}` and `{}`",
+                        op, lhs, rhs
+                    )));
+                }
+
+                let valid = match op {
+                    BinOp::IntEq | BinOp::IntNe => matches!(lhs, Type::Int(_) | Type::Bool),
+                    op if is_float_op(*op) => matches!(lhs, Type::Float(_)),
+                    _ => matches!(lhs, Type::Int(_)),
+                };
+
+                if !valid {
+                    return Err(self.error(format!("`{:?}` of `{}`", op, lhs)));
+                }
+
+                if is_comparison(*op) {
+                    Ok(Type::Bool)
+                } else {
+                    Ok(lhs)
+                }
+            }
+            Value::Call(callee, arguments) => {
+                let Type::Function(callee) = self.operand_type(callee)? else {
+                    return Err(self.error("call of a value that isn't a function"));
+                };
+
+                if callee.arguments.len() != arguments.len() {
+                    return Err(self.error(format!(
+                        "call with {} arguments, expected {}",
+                        arguments.len(),
+                        callee.arguments.len()
+                    )));
+                }
+
+                for (i, (argument, expected)) in arguments.iter().zip(&callee.arguments).enumerate()
+                {
+                    let ty = self.operand_type(argument)?;
+                    self.expect(&ty, expected, &format!("argument {}", i))?;
+                }
+
+                Ok(*callee.return_type)
+            }
+            Value::Intrinsic(intrinsic) => self.intrinsic_type(intrinsic),
+        }
+    }
+
+    fn intrinsic_type(&self, intrinsic: &Intrinsic) -> Result<Type, Diagnostic> {
+        let pointer = |operand: &Operand| match self.operand_type(operand)? {
+            Type::Pointer(_) => Ok(()),
+            ty => Err(self.error(format!(
+                "`{}` expected a pointer, found `{}`",
+                intrinsic, ty
+            ))),
+        };
+
+        let usize = |operand: &Operand| {
+            let ty = self.operand_type(operand)?;
+            self.expect(&ty, &Type::USIZE, &format!("size of `{}`", intrinsic))
+        };
+
+        match intrinsic {
+            Intrinsic::Sizeof(_) | Intrinsic::Alignof(_) => Ok(Type::USIZE),
+            Intrinsic::Bitcast(operand, ty) => {
+                self.operand_type(operand)?;
+                Ok(ty.clone())
+            }
+            Intrinsic::Malloc(count, ty) => {
+                usize(count)?;
+                Ok(Type::pointer(ty.clone()))
+            }
+            Intrinsic::Free(operand) => {
+                pointer(operand)?;
+                Ok(Type::Void)
+            }
+            Intrinsic::Memcpy(dst, src, size) => {
+                pointer(dst)?;
+                pointer(src)?;
+                usize(size)?;
+                Ok(Type::Void)
+            }
+            Intrinsic::PtrToInt(operand, from, to) => {
+                self.cast(operand, Type::Pointer(from.clone()), Type::Int(to.clone()))
+            }
+            Intrinsic::IntToPtr(operand, from, to) => {
+                self.cast(operand, Type::Int(from.clone()), Type::Pointer(to.clone()))
+            }
+            Intrinsic::PtrToPtr(operand, from, to) => self.cast(
+                operand,
+                Type::Pointer(from.clone()),
+                Type::Pointer(to.clone()),
+            ),
+            Intrinsic::IntToInt(operand, from, to) => {
+                self.cast(operand, Type::Int(from.clone()), Type::Int(to.clone()))
+            }
+            Intrinsic::IntToFloat(operand, from, to) => {
+                self.cast(operand, Type::Int(from.clone()), Type::Float(to.clone()))
+            }
+            Intrinsic::FloatToInt(operand, from, to) => {
+                self.cast(operand, Type::Float(from.clone()), Type::Int(to.clone()))
+            }
+            Intrinsic::FloatToFloat(operand, from, to) => {
+                self.cast(operand, Type::Float(from.clone()), Type::Float(to.clone()))
+            }
+            Intrinsic::Overflows(op, lhs, rhs) => {
+                self.wrapping_type(intrinsic, *op, lhs, rhs)?;
+                Ok(Type::Bool)
+            }
+            Intrinsic::Wrapping(op, lhs, rhs) => self.wrapping_type(intrinsic, *op, lhs, rhs),
+        }
+    }
+
+    /// Returns the type of the operands of an [`Intrinsic::Overflows`] or
+    /// [`Intrinsic::Wrapping`], only the latter may be applied to generics.
+    fn wrapping_type(
+        &self,
+        intrinsic: &Intrinsic,
+        op: BinOp,
+        lhs: &Operand,
+        rhs: &Operand,
+    ) -> Result<Type, Diagnostic> {
+        let is_wrapping = matches!(intrinsic, Intrinsic::Wrapping(..));
+
+        let lhs = self.operand_type(lhs)?;
+        let rhs = self.operand_type(rhs)?;
+
+        if lhs != rhs {
+            return Err(self.error(format!(
+                "`{}` of mismatched types `{}` and `{}`",
+                intrinsic, lhs, rhs
+            )));
+        }
+
+        let valid_op = match op {
+            BinOp::IntAdd | BinOp::IntSub | BinOp::IntMul => true,
+            BinOp::IntDivSigned => !is_wrapping,
+            _ => false,
+        };
+        let valid_type = match lhs {
+            Type::Int(_) => true,
+            Type::Generic(_) => is_wrapping,
+            _ => false,
+        };
+
+        if !valid_op || !valid_type {
+            return Err(self.error(format!("`{:?}` of `{}` in `{}`", op, lhs, intrinsic)));
+        }
+
+        Ok(lhs)
+    }
+
+    fn cast(&self, operand: &Operand, from: Type, to: Type) -> Result<Type, Diagnostic> {
+        let ty = self.operand_type(operand)?;
+        self.expect(&ty, &from, "cast operand")?;
+        Ok(to)
+    }
+}
+
+fn is_float_op(op: BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::FloatAdd
+            | BinOp::FloatSub
+            | BinOp::FloatMul
+            | BinOp::FloatDiv
+            | BinOp::FloatEq
+            | BinOp::FloatNe
+            | BinOp::FloatLt
+            | BinOp::FloatLe
+            | BinOp::FloatGt
+            | BinOp::FloatGe
+    )
+}
+
+fn is_comparison(op: BinOp) -> bool {
+    !matches!(
+        op,
+        BinOp::IntAdd
+            | BinOp::IntSub
+            | BinOp::IntMul
+            | BinOp::IntDivSigned
+            | BinOp::IntDivUnsigned
+            | BinOp::FloatAdd
+            | BinOp::FloatSub
+            | BinOp::FloatMul
+            | BinOp::FloatDiv
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use ritec_core::{Ident, Inline};
+
+    use super::*;
+    use crate::{Block, Body, Local, SourceInfo};
+
+    /// Adds a function `f() -> i32` with a single `i32` local and `body`
+    /// built by `build`.
+    fn program(build: impl FnOnce(&mut Body)) -> Program {
+        let mut body = Body::new();
+        body.locals.push(Local::new(Type::I32, SourceInfo::DUMMY));
+        build(&mut body);
+
+        let mut program = Program::new();
+        program.functions.push(Function {
+            ident: Ident::from("f"),
+            path: Vec::new(),
+            class: None,
+            generics: Vec::new(),
+            arguments: Vec::new(),
+            return_type: Type::I32,
+            body,
+            inline: Inline::Auto,
+            export: false,
+        });
+
+        program
+    }
+
+    fn error(program: &Program) -> String {
+        let err = validate(program).unwrap_err();
+        let notes: Vec<_> = err.children.iter().map(|c| c.message.clone()).collect();
+        format!("{} ({})", err.title, notes.join(", "))
+    }
+
+    fn local() -> Place {
+        Place::from(crate::LocalId::from_raw_index(0))
+    }
+
+    fn int(value: i64) -> Operand {
+        Operand::Constant(Constant::Integer(value, crate::IntType::I32))
+    }
+
+    #[test]
+    fn valid() {
+        let program = program(|body| {
+            let mut block = Block::new();
+            let sum = Value::BinaryOp(BinOp::IntAdd, int(1), int(2));
+            block.push_assign(local(), sum, SourceInfo::DUMMY);
+            block.terminate_return(Operand::Move(local()), SourceInfo::DUMMY);
+            body.blocks.push(block);
+        });
+
+        assert!(validate(&program).is_ok());
+    }
+
+    #[test]
+    fn missing_terminator() {
+        let program = program(|body| {
+            body.blocks.push(Block::new());
+        });
+
+        assert_eq!(
+            error(&program),
+            "internal compiler error: block has no terminator (in `f` at bb0, \
+            this is a bug in the compiler, the MIR is malformed)"
+        );
+    }
+
+    #[test]
+    fn missing_block() {
+        let program = program(|body| {
+            let mut block = Block::new();
+            block.terminate_goto(BlockId::from_raw_index(3), SourceInfo::DUMMY);
+            body.blocks.push(block);
+        });
+
+        assert!(error(&program).contains("jump to missing block bb3"));
+    }
+
+    #[test]
+    fn invalid_projection() {
+        let program = program(|body| {
+            let mut place = local();
+            place
+                .proj
+                .push(Projection::Field(crate::FieldId::from_raw_index(0)));
+
+            let mut block = Block::new();
+            block.terminate_return(Operand::Copy(place), SourceInfo::DUMMY);
+            body.blocks.push(block);
+        });
+
+        assert!(error(&program).contains("invalid projection in `(_0).0` of `i32`"));
+    }
+
+    #[test]
+    fn mismatched_types() {
+        let sum = program(|body| {
+            let mut block = Block::new();
+            let value = Operand::Constant(Constant::Bool(true));
+            let sum = Value::BinaryOp(BinOp::IntAdd, int(1), value);
+            block.push_assign(local(), sum, SourceInfo::DUMMY);
+            block.terminate_return(Operand::Move(local()), SourceInfo::DUMMY);
+            body.blocks.push(block);
+        });
+
+        assert!(error(&sum).contains("`IntAdd` of mismatched types `i32` and `bool`"));
+
+        let void = program(|body| {
+            let mut block = Block::new();
+            block.terminate_return(Operand::VOID, SourceInfo::DUMMY);
+            body.blocks.push(block);
+        });
+
+        assert!(error(&void).contains("returned value has type `void`, expected `i32`"));
+    }
+}
@@ -1,6 +1,6 @@
 use std::fmt::{self, Display};
 
-use crate::{Constant, FloatType, IntType, Operand, Place, PointerType, Type};
+use crate::{Constant, FloatType, GenericMap, IntType, Operand, Place, PointerType, Type};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum UnaryOp {
@@ -53,6 +53,111 @@ pub enum Intrinsic {
     IntToFloat(Operand, IntType, FloatType),
     FloatToInt(Operand, FloatType, IntType),
     FloatToFloat(Operand, FloatType, FloatType),
+    /// `true` if the integer operation overflows, the signedness is that of
+    /// the operands.
+    Overflows(BinOp, Operand, Operand),
+    /// An integer operation that wraps around, which unlike
+    /// [`Value::BinaryOp`] may be applied to generics.
+    Wrapping(BinOp, Operand, Operand),
+}
+
+impl Intrinsic {
+    pub fn operands(&self) -> Vec<&Operand> {
+        match self {
+            Self::Sizeof(_) | Self::Alignof(_) => Vec::new(),
+            Self::Memcpy(dst, src, size) => vec![dst, src, size],
+            Self::Overflows(_, lhs, rhs) | Self::Wrapping(_, lhs, rhs) => vec![lhs, rhs],
+            Self::Bitcast(operand, _)
+            | Self::Malloc(operand, _)
+            | Self::Free(operand)
+            | Self::PtrToInt(operand, _, _)
+            | Self::IntToPtr(operand, _, _)
+            | Self::PtrToPtr(operand, _, _)
+            | Self::IntToInt(operand, _, _)
+            | Self::IntToFloat(operand, _, _)
+            | Self::FloatToInt(operand, _, _)
+            | Self::FloatToFloat(operand, _, _) => vec![operand],
+        }
+    }
+
+    pub fn operands_mut(&mut self) -> Vec<&mut Operand> {
+        match self {
+            Self::Sizeof(_) | Self::Alignof(_) => Vec::new(),
+            Self::Memcpy(dst, src, size) => vec![dst, src, size],
+            Self::Overflows(_, lhs, rhs) | Self::Wrapping(_, lhs, rhs) => vec![lhs, rhs],
+            Self::Bitcast(operand, _)
+            | Self::Malloc(operand, _)
+            | Self::Free(operand)
+            | Self::PtrToInt(operand, _, _)
+            | Self::IntToPtr(operand, _, _)
+            | Self::PtrToPtr(operand, _, _)
+            | Self::IntToInt(operand, _, _)
+            | Self::IntToFloat(operand, _, _)
+            | Self::FloatToInt(operand, _, _)
+            | Self::FloatToFloat(operand, _, _) => vec![operand],
+        }
+    }
+
+    /// Returns `true` if the intrinsic does anything besides computing its
+    /// result.
+    pub fn has_side_effects(&self) -> bool {
+        matches!(self, Self::Malloc(..) | Self::Free(_) | Self::Memcpy(..))
+    }
+
+    pub fn instantiate(&mut self, generics: &GenericMap) {
+        for operand in self.operands_mut() {
+            operand.instantiate(generics);
+        }
+
+        match self {
+            Self::Sizeof(ty) | Self::Alignof(ty) | Self::Bitcast(_, ty) | Self::Malloc(_, ty) => {
+                ty.instantiate(generics)
+            }
+            Self::PtrToInt(_, pointer, _) | Self::IntToPtr(_, _, pointer) => {
+                pointer.instantiate(generics)
+            }
+            Self::PtrToPtr(_, from, to) => {
+                from.instantiate(generics);
+                to.instantiate(generics);
+            }
+            Self::Free(_)
+            | Self::Memcpy(..)
+            | Self::IntToInt(..)
+            | Self::IntToFloat(..)
+            | Self::FloatToInt(..)
+            | Self::FloatToFloat(..)
+            | Self::Overflows(..)
+            | Self::Wrapping(..) => {}
+        }
+    }
+
+    /// Calls `f` with every type in the intrinsic, see [`Type::visit_mut`].
+    pub fn visit_types_mut(&mut self, f: &mut impl FnMut(&mut Type)) {
+        for operand in self.operands_mut() {
+            operand.visit_types_mut(f);
+        }
+
+        match self {
+            Self::Sizeof(ty) | Self::Alignof(ty) | Self::Bitcast(_, ty) | Self::Malloc(_, ty) => {
+                ty.visit_mut(f)
+            }
+            Self::PtrToInt(_, pointer, _) | Self::IntToPtr(_, _, pointer) => {
+                pointer.pointee.visit_mut(f)
+            }
+            Self::PtrToPtr(_, from, to) => {
+                from.pointee.visit_mut(f);
+                to.pointee.visit_mut(f);
+            }
+            Self::Free(_)
+            | Self::Memcpy(..)
+            | Self::IntToInt(..)
+            | Self::IntToFloat(..)
+            | Self::FloatToInt(..)
+            | Self::FloatToFloat(..)
+            | Self::Overflows(..)
+            | Self::Wrapping(..) => {}
+        }
+    }
 }
 
 impl Display for Intrinsic {
@@ -85,6 +190,8 @@ impl Display for Intrinsic {
             Self::FloatToFloat(operand, from, to) => {
                 write!(f, "floattofloat({} as {} as {})", operand, from, to)
             }
+            Self::Overflows(op, lhs, rhs) => write!(f, "overflows({:?}, {}, {})", op, lhs, rhs),
+            Self::Wrapping(op, lhs, rhs) => write!(f, "wrapping({:?}, {}, {})", op, lhs, rhs),
         }
     }
 }
@@ -127,6 +234,69 @@ impl Value {
     pub fn move_operand(place: impl Into<Place>) -> Self {
         Self::Use(Operand::Move(place.into()))
     }
+
+    /// Returns the operands used by the value, places taken by address aren't
+    /// operands.
+    pub fn operands(&self) -> Vec<&Operand> {
+        match self {
+            Self::Use(operand) | Self::UnaryOp(_, operand) => vec![operand],
+            Self::Address(_) => Vec::new(),
+            Self::BinaryOp(_, lhs, rhs) => vec![lhs, rhs],
+            Self::Call(callee, arguments) => {
+                let mut operands = vec![callee];
+                operands.extend(arguments);
+                operands
+            }
+            Self::Intrinsic(intrinsic) => intrinsic.operands(),
+        }
+    }
+
+    pub fn operands_mut(&mut self) -> Vec<&mut Operand> {
+        match self {
+            Self::Use(operand) | Self::UnaryOp(_, operand) => vec![operand],
+            Self::Address(_) => Vec::new(),
+            Self::BinaryOp(_, lhs, rhs) => vec![lhs, rhs],
+            Self::Call(callee, arguments) => {
+                let mut operands = vec![callee];
+                operands.extend(arguments);
+                operands
+            }
+            Self::Intrinsic(intrinsic) => intrinsic.operands_mut(),
+        }
+    }
+
+    /// Returns `true` if evaluating the value does anything besides computing
+    /// its result, calls are assumed to.
+    pub fn has_side_effects(&self) -> bool {
+        match self {
+            Self::Call(..) => true,
+            Self::Intrinsic(intrinsic) => intrinsic.has_side_effects(),
+            _ => false,
+        }
+    }
+
+    pub fn instantiate(&mut self, generics: &GenericMap) {
+        match self {
+            Self::Intrinsic(intrinsic) => intrinsic.instantiate(generics),
+            _ => {
+                for operand in self.operands_mut() {
+                    operand.instantiate(generics);
+                }
+            }
+        }
+    }
+
+    /// Calls `f` with every type in the value, see [`Type::visit_mut`].
+    pub fn visit_types_mut(&mut self, f: &mut impl FnMut(&mut Type)) {
+        match self {
+            Self::Intrinsic(intrinsic) => intrinsic.visit_types_mut(f),
+            _ => {
+                for operand in self.operands_mut() {
+                    operand.visit_types_mut(f);
+                }
+            }
+        }
+    }
 }
 
 impl From<Operand> for Value {
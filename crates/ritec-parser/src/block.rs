@@ -1,10 +1,11 @@
 use ritec_ast as ast;
 
-use crate::{Delimiter, Parse, ParseResult, ParseStream};
+use crate::{Delimiter, Parse, ParseResult, ParseStream, TokenTree};
 
 impl Parse for ast::Block {
     fn parse(parser: ParseStream) -> ParseResult<Self> {
-        let span = parser.span();
+        // the span of the group includes both braces
+        let span = parser.peek().map_or(parser.span(), TokenTree::span);
         let mut content = parser.delim(Delimiter::Brace)?;
 
         // parse statements in the block
@@ -13,9 +14,6 @@ impl Parse for ast::Block {
             stmts.push(content.parse()?);
         }
 
-        Ok(ast::Block {
-            stmts,
-            span: span | parser.span(),
-        })
+        Ok(ast::Block { stmts, span })
     }
 }
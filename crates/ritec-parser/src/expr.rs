@@ -50,6 +50,7 @@ impl Parse for ast::LiteralExpr {
             parser.parse()?
         };
 
+        let span = literal.span();
         Ok(ast::LiteralExpr { literal, span })
     }
 }
@@ -278,11 +279,15 @@ fn parse_call(parser: ParseStream) -> ParseResult<ast::Expr> {
 }
 
 fn parse_unary(parser: ParseStream) -> ParseResult<ast::Expr> {
+    let span = parser.span();
+
     if let Some(operator) = parser.try_parse::<UnaryOp>() {
+        let operand = parse_unary(parser)?;
+
         Ok(ast::Expr::Unary(ast::UnaryExpr {
             operator,
-            operand: Box::new(parse_unary(parser)?),
-            span: parser.span(),
+            span: span | operand.span(),
+            operand: Box::new(operand),
         }))
     } else {
         parse_call(parser)
@@ -313,25 +318,29 @@ fn parse_binary(parser: ParseStream) -> ParseResult<ast::Expr> {
 
         if let ast::Expr::Binary(ref rhs) = rhs {
             if rhs.operator.precedence() <= operator.precedence() {
+                let span = lhs.span() | rhs.rhs.span();
+                let lhs_span = lhs.span() | rhs.lhs.span();
+
                 return Ok(ast::Expr::Binary(ast::BinaryExpr {
                     lhs: Box::new(ast::Expr::Binary(ast::BinaryExpr {
                         lhs: Box::new(lhs),
                         operator,
                         rhs: rhs.lhs.clone(),
-                        span: parser.span(),
+                        span: lhs_span,
                     })),
                     operator: rhs.operator,
                     rhs: rhs.rhs.clone(),
-                    span: parser.span(),
+                    span,
                 }));
             }
         }
 
+        let span = lhs.span() | rhs.span();
         Ok(ast::Expr::Binary(ast::BinaryExpr {
             operator,
             lhs: Box::new(lhs),
             rhs: Box::new(rhs),
-            span: parser.span(),
+            span,
         }))
     } else {
         Ok(lhs)
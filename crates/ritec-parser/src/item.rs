@@ -6,7 +6,7 @@ impl Parse for ast::ModuleItem {
     fn parse(parser: ParseStream) -> ParseResult<Self> {
         let span = parser.expect(&KeywordKind::Mod)?;
         let ident = parser.parse()?;
-        parser.expect(&SymbolKind::Semicolon)?;
+        parser.expect_semi()?;
 
         Ok(ast::ModuleItem {
             ident,
@@ -39,6 +39,39 @@ impl Parse for ast::Field {
     }
 }
 
+impl Parse for ast::Attribute {
+    fn parse(parser: ParseStream) -> ParseResult<Self> {
+        let span = parser.expect(&SymbolKind::Pound)?;
+        let mut content = parser.delim(Delimiter::Bracket)?;
+        let ident = content.parse()?;
+
+        let arguments = if content.is(&Delimiter::Paren) {
+            content.delim(Delimiter::Paren)?.parse_comma_separated()?
+        } else {
+            Vec::new()
+        };
+
+        if !content.is_empty() {
+            return Err(content.expected("]"));
+        }
+
+        Ok(ast::Attribute {
+            ident,
+            arguments,
+            span: span | parser.span(),
+        })
+    }
+}
+
+fn parse_attributes(parser: ParseStream) -> ParseResult<Vec<ast::Attribute>> {
+    let mut attributes = Vec::new();
+    while parser.is(&SymbolKind::Pound) {
+        attributes.push(parser.parse()?);
+    }
+
+    Ok(attributes)
+}
+
 impl Parse for ast::SelfArgument {
     fn parse(parser: ParseStream) -> ParseResult<Self> {
         if parser.is(&SymbolKind::Amp) || parser.is(&SymbolKind::Star) {
@@ -54,6 +87,7 @@ impl Parse for ast::SelfArgument {
 
 impl Parse for ast::Method {
     fn parse(parser: ParseStream) -> ParseResult<Self> {
+        let attributes = parse_attributes(parser)?;
         let span = parser.expect(&KeywordKind::Fn)?;
         let ident = parser.parse()?;
         let generics = parser.parse()?;
@@ -73,14 +107,17 @@ impl Parse for ast::Method {
             None
         };
 
+        let body: ast::Block = parser.parse()?;
+
         Ok(ast::Method {
+            attributes,
             ident,
             generics,
             self_argument,
             arguments,
             return_type,
-            body: parser.parse()?,
-            span: span | parser.span(),
+            span: span | body.span,
+            body,
         })
     }
 }
@@ -96,7 +133,7 @@ impl Parse for ast::Class {
         let mut methods = Vec::new();
 
         while !contents.is_empty() {
-            if contents.is(&KeywordKind::Fn) {
+            if contents.is(&KeywordKind::Fn) || contents.is(&SymbolKind::Pound) {
                 methods.push(contents.parse()?);
             } else {
                 fields.push(contents.parse()?);
@@ -132,7 +169,15 @@ impl Parse for ast::FunctionArgument {
 
 impl Parse for ast::Function {
     fn parse(parser: ParseStream) -> ParseResult<Self> {
-        let span = parser.expect(&KeywordKind::Fn)?;
+        let attributes = parse_attributes(parser)?;
+        let span = parser.span();
+
+        let is_const = parser.is(&KeywordKind::Const);
+        if is_const {
+            parser.next();
+        }
+
+        parser.expect(&KeywordKind::Fn)?;
 
         let ident = parser.parse()?;
         let generics = parser.parse()?;
@@ -147,16 +192,38 @@ impl Parse for ast::Function {
             None
         };
 
-        let body = parser.parse()?;
+        let body: ast::Block = parser.parse()?;
 
         Ok(ast::Function {
             module: parser.module(),
+            attributes,
+            is_const,
             ident,
             generics,
             arguments,
+            span: span | body.span,
             return_type,
             body,
-            span: span | parser.span(),
+        })
+    }
+}
+
+impl Parse for ast::Const {
+    fn parse(parser: ParseStream) -> ParseResult<Self> {
+        let span = parser.expect(&KeywordKind::Const)?;
+        let ident = parser.parse()?;
+        parser.expect(&SymbolKind::Colon)?;
+        let ty = parser.parse()?;
+        parser.expect(&SymbolKind::Equal)?;
+        let value = parser.parse()?;
+        let end = parser.expect_semi()?;
+
+        Ok(ast::Const {
+            module: parser.module(),
+            ident,
+            ty,
+            value,
+            span: span | end,
         })
     }
 }
@@ -167,8 +234,18 @@ impl Parse for ast::Item {
             Ok(ast::Item::Module(parser.parse()?))
         } else if parser.is(&KeywordKind::Class) {
             Ok(ast::Item::Class(parser.parse()?))
-        } else if parser.is(&KeywordKind::Fn) {
+        } else if parser.is(&KeywordKind::Fn) || parser.is(&SymbolKind::Pound) {
             Ok(ast::Item::Function(parser.parse()?))
+        } else if parser.is(&KeywordKind::Const) {
+            // `const fn` starts a function, otherwise a constant
+            let mut lookahead = parser.clone();
+            lookahead.next();
+
+            if lookahead.is(&KeywordKind::Fn) {
+                Ok(ast::Item::Function(parser.parse()?))
+            } else {
+                Ok(ast::Item::Const(parser.parse()?))
+            }
         } else {
             Err(parser.expected("item"))
         }
@@ -66,6 +66,7 @@ keyword! {
     "super" => Super,
     "let" => Let,
     "fn" => Fn,
+    "const" => Const,
     "if" => If,
     "as" => As,
     "else" => Else,
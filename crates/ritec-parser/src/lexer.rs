@@ -1,5 +1,5 @@
 use ritec_core::{FileId, FloatLiteral, Ident, IntLiteral, IntPrefix, Literal, Span};
-use ritec_error::Diagnostic;
+use ritec_error::{Diagnostic, ErrorCode};
 
 use crate::{Delimiter, Group, Keyword, KeywordKind, Symbol, SymbolKind, TokenStream, TokenTree};
 
@@ -13,10 +13,14 @@ impl Into<Diagnostic> for LexerError {
     fn into(self) -> Diagnostic {
         match self {
             LexerError::UnmatchedDelimiter(delim, span) => {
-                Diagnostic::error(format!("unmatched delimiter: {:?}", delim)).with_span(span)
+                Diagnostic::error(format!("unmatched delimiter: {:?}", delim))
+                    .with_code(ErrorCode::E0003)
+                    .with_span(span)
             }
             LexerError::UnexpectedCharacter(ch, span) => {
-                Diagnostic::error(format!("unexpected character: {:?}", ch)).with_span(span)
+                Diagnostic::error(format!("unexpected character: {:?}", ch))
+                    .with_code(ErrorCode::E0003)
+                    .with_span(span)
             }
         }
     }
@@ -201,7 +205,7 @@ impl<'a> Lexer<'a> {
             let lit = IntLiteral {
                 prefix,
                 value: integer,
-                span,
+                span: span | self.span(),
             };
 
             Literal::Int(lit)
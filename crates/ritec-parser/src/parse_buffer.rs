@@ -2,7 +2,7 @@ use std::fmt::Display;
 
 use ritec_ast::ModuleId;
 use ritec_core::{Ident, Literal, Span};
-use ritec_error::Diagnostic;
+use ritec_error::{Diagnostic, ErrorCode, Suggestion};
 
 use crate::{
     Delimiter, Keyword, Parse, ParseResult, ParseStream, Symbol, SymbolKind, TokenStream, TokenTree,
@@ -170,14 +170,34 @@ impl<'a> ParseBuffer<'a> {
         Ok(self.next().unwrap().span())
     }
 
+    /// Expects a `;`, if it's missing a suggestion to insert it after the previous token is
+    /// attached to the error.
+    pub fn expect_semi(&mut self) -> ParseResult<Span> {
+        if self.is(&SymbolKind::Semicolon) {
+            return Ok(self.next().unwrap().span());
+        }
+
+        let mut err = self.expected(SymbolKind::Semicolon);
+
+        if let Some(previous) = self.index.checked_sub(1).and_then(|i| self.stream.get(i)) {
+            let span = previous.span().shrink_to_hi();
+            err.add_suggestion(Suggestion::machine_applicable("insert `;`", span, ";"));
+        }
+
+        Err(err)
+    }
+
     pub fn ident(&mut self) -> ParseResult<Ident> {
         let token = self.next().ok_or_else(|| {
-            Diagnostic::error("expected identifier").with_msg_span("found end of file", self.span())
+            Diagnostic::error("expected identifier")
+                .with_code(ErrorCode::E0002)
+                .with_msg_span("found end of file", self.span())
         })?;
 
         match token {
             TokenTree::Ident(ident) => Ok(ident.clone()),
             _ => Err(Diagnostic::error("expected identifier")
+                .with_code(ErrorCode::E0002)
                 .with_msg_span(format!("found `{}`", token), token.span())),
         }
     }
@@ -190,6 +210,7 @@ impl<'a> ParseBuffer<'a> {
         let c = delimiter.open_char().unwrap();
         let token = self.next().ok_or_else(|| {
             Diagnostic::error(format!("expected `{}`", c))
+                .with_code(ErrorCode::E0002)
                 .with_msg_span("found end of file", self.span())
         })?;
 
@@ -198,6 +219,7 @@ impl<'a> ParseBuffer<'a> {
                 Ok(ParseBuffer::new(group.stream(), self.module))
             }
             _ => Err(Diagnostic::error(format!("expected `{}`", c))
+                .with_code(ErrorCode::E0002)
                 .with_msg_span(format!("found `{}`", token), token.span())),
         }
     }
@@ -208,12 +230,15 @@ impl<'a> ParseBuffer<'a> {
         }
 
         let token = self.next().ok_or_else(|| {
-            Diagnostic::error("expected literal").with_msg_span("found end of file", self.span())
+            Diagnostic::error("expected literal")
+                .with_code(ErrorCode::E0002)
+                .with_msg_span("found end of file", self.span())
         })?;
 
         match token {
             TokenTree::Literal(literal) => Ok(literal.clone()),
             _ => Err(Diagnostic::error("expected literal")
+                .with_code(ErrorCode::E0002)
                 .with_msg_span(format!("found `{}`", token), token.span())),
         }
     }
@@ -222,8 +247,10 @@ impl<'a> ParseBuffer<'a> {
     pub fn expected<T: Display>(&self, expected: T) -> Diagnostic {
         match self.peek() {
             Some(token) => Diagnostic::error(format!("expected `{}`", expected))
+                .with_code(ErrorCode::E0002)
                 .with_msg_span(format!("found `{}`", token), token.span()),
             None => Diagnostic::error(format!("expected `{}`", expected))
+                .with_code(ErrorCode::E0002)
                 .with_msg_span("found end of file", self.span()),
         }
     }
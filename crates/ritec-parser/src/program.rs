@@ -6,7 +6,7 @@ use std::{
 
 use ritec_ast as ast;
 use ritec_core::{Ident, SourceFile, SourceMap, Span};
-use ritec_error::{Diagnostic, Emitter};
+use ritec_error::{Diagnostic, Emitter, ErrorCode};
 
 use crate::{ParseBuffer, TokenStream};
 
@@ -29,6 +29,7 @@ impl<'a> ProgramParser<'a> {
         // read the file
         let Ok(source) = fs::read_to_string(path) else {
             let err = Diagnostic::error("failed to read file")
+                .with_code(ErrorCode::E0001)
                 .with_msg("failed to read main file".to_string());
 
             self.emitter.emit(err);
@@ -37,10 +38,7 @@ impl<'a> ProgramParser<'a> {
         };
 
         // register the file
-        let source_file = SourceFile {
-            path: source.to_string(),
-            text: source,
-        };
+        let source_file = SourceFile::new(path, source);
         let file_id = self.source_map.insert(source_file);
 
         let source = self.source_map.get(file_id).unwrap();
@@ -81,6 +79,10 @@ impl<'a> ProgramParser<'a> {
                     let id = program.classes.push(item);
                     program.root_mut().classes.push(id);
                 }
+                ast::Item::Const(item) => {
+                    let id = program.consts.push(item);
+                    program.root_mut().consts.push(id);
+                }
                 ast::Item::Module(item) => {
                     let id = self.parse_module(&mut program, path, &item.ident)?;
                     program.root_mut().modules.push(id);
@@ -107,6 +109,7 @@ impl<'a> ProgramParser<'a> {
         // read the file
         let Ok(source) = fs::read_to_string(&path) else {
             let err = Diagnostic::error("failed to read file")
+                .with_code(ErrorCode::E0001)
                 .with_msg_span("failed to read module file".to_string(), ident.span());
 
             self.emitter.emit(err);
@@ -115,10 +118,7 @@ impl<'a> ProgramParser<'a> {
         };
 
         // register the file
-        let source_file = SourceFile {
-            path: source.to_string(),
-            text: source,
-        };
+        let source_file = SourceFile::new(&path, source);
 
         let file_id = self.source_map.insert(source_file);
         let source = self.source_map.get(file_id).unwrap();
@@ -157,6 +157,10 @@ impl<'a> ProgramParser<'a> {
                     let id = program.classes.push(item);
                     program.modules[module].classes.push(id);
                 }
+                ast::Item::Const(item) => {
+                    let id = program.consts.push(item);
+                    program.modules[module].consts.push(id);
+                }
                 ast::Item::Module(item) => {
                     let id = self.parse_module(program, &path, &item.ident)?;
                     program.modules[module].modules.push(id);
@@ -28,12 +28,12 @@ impl Parse for ast::LetStmt {
         };
 
         // parse the `;` symbol
-        parser.expect(&SymbolKind::Semicolon)?;
+        let semi = parser.expect_semi()?;
         Ok(ast::LetStmt {
             ident: name,
             ty,
             init: value,
-            span: span | parser.span(),
+            span: span | semi,
         })
     }
 }
@@ -45,14 +45,12 @@ impl Parse for ast::Stmt {
         } else {
             let expr = parser.parse::<ast::Expr>()?;
 
+            let mut span = expr.span();
             if expr.stmt_needs_semi() {
-                parser.expect(&SymbolKind::Semicolon)?;
+                span |= parser.expect_semi()?;
             }
 
-            Ok(ast::Stmt::Expr(ast::ExprStmt {
-                expr,
-                span: parser.span(),
-            }))
+            Ok(ast::Stmt::Expr(ast::ExprStmt { expr, span }))
         }
     }
 }
@@ -71,6 +71,7 @@ symbol! {
     ';' => Semicolon,
     '<' => Less,
     '>' => Greater,
+    '#' => Pound,
 }
 
 impl SymbolKind {
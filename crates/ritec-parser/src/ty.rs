@@ -82,6 +82,26 @@ impl Parse for ast::PointerType {
     }
 }
 
+impl Parse for ast::ArrayType {
+    fn parse(parser: ParseStream) -> ParseResult<Self> {
+        let span = parser.span();
+        let mut content = parser.delim(Delimiter::Bracket)?;
+        let element = content.parse()?;
+        content.expect(&SymbolKind::Semicolon)?;
+        let length = content.parse()?;
+
+        if !content.is_empty() {
+            return Err(content.expected("]"));
+        }
+
+        Ok(ast::ArrayType {
+            element: Box::new(element),
+            length: Box::new(length),
+            span: span | parser.span(),
+        })
+    }
+}
+
 impl Parse for ast::SliceType {
     fn parse(parser: ParseStream) -> ParseResult<Self> {
         let span = parser.span();
@@ -165,6 +185,10 @@ impl Parse for ast::Type {
         } else if let Some(ty) = parser.try_parse() {
             Ok(ast::Type::Pointer(ty))
 
+        // parse array type
+        } else if let Some(ty) = parser.try_parse() {
+            Ok(ast::Type::Array(ty))
+
         // parse slice type
         } else if let Some(ty) = parser.try_parse() {
             Ok(ast::Type::Slice(ty))
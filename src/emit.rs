@@ -0,0 +1,169 @@
+use std::{
+    fmt::{self, Display},
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+#[cfg(feature = "llvm")]
+use ritec_codegen_llvm::OutputType;
+
+/// An intermediate representation or artifact that can be written with `--emit`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EmitKind {
+    Tokens,
+    Ast,
+    Hir,
+    Thir,
+    /// The MIR after the optimization passes have run.
+    Mir,
+    /// The control flow graph of every function as Graphviz `dot`, drawn from
+    /// the MIR after the optimization passes have run, like [`Self::Mir`].
+    MirCfg,
+    C,
+    LlvmIr,
+    Asm,
+    Obj,
+}
+
+impl EmitKind {
+    pub const ALL: &'static [Self] = &[
+        Self::Tokens,
+        Self::Ast,
+        Self::Hir,
+        Self::Thir,
+        Self::Mir,
+        Self::MirCfg,
+        Self::C,
+        Self::LlvmIr,
+        Self::Asm,
+        Self::Obj,
+    ];
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Tokens => "tokens",
+            Self::Ast => "ast",
+            Self::Hir => "hir",
+            Self::Thir => "thir",
+            Self::Mir => "mir",
+            Self::MirCfg => "mir-cfg",
+            Self::C => "c",
+            Self::LlvmIr => "llvm-ir",
+            Self::Asm => "asm",
+            Self::Obj => "obj",
+        }
+    }
+
+    /// The extension of the file written when no path is given.
+    pub const fn extension(&self) -> &'static str {
+        match self {
+            Self::Tokens => "tokens",
+            Self::Ast => "ast",
+            Self::Hir => "hir",
+            Self::Thir => "thir",
+            Self::Mir => "mir",
+            Self::MirCfg => "dot",
+            Self::C => "c",
+            Self::LlvmIr => "ll",
+            Self::Asm => "s",
+            Self::Obj => "o",
+        }
+    }
+
+    #[cfg(feature = "llvm")]
+    pub const fn output_type(&self) -> Option<OutputType> {
+        match self {
+            Self::LlvmIr => Some(OutputType::LlvmIr),
+            Self::Asm => Some(OutputType::Assembly),
+            Self::Obj => Some(OutputType::Object),
+            _ => None,
+        }
+    }
+}
+
+impl Display for EmitKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for EmitKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Self::ALL.iter().find(|kind| kind.as_str() == s) {
+            Some(&kind) => Ok(kind),
+            None => {
+                let kinds: Vec<_> = Self::ALL.iter().map(EmitKind::as_str).collect();
+                Err(format!(
+                    "unknown emit kind `{}`, expected one of: {}",
+                    s,
+                    kinds.join(", ")
+                ))
+            }
+        }
+    }
+}
+
+/// A single `--emit` request, `kind` or `kind=path`, where a path of `-`
+/// writes to stdout.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Emit {
+    pub kind: EmitKind,
+    pub path: Option<PathBuf>,
+}
+
+impl Emit {
+    pub const fn new(kind: EmitKind) -> Self {
+        Self { kind, path: None }
+    }
+
+    /// Writes `contents` to the requested path, or to the file stem of `input`
+    /// with the extension of the kind if no path was given.
+    pub fn write(&self, input: &Path, contents: &[u8]) -> io::Result<()> {
+        match self.path {
+            Some(ref path) if path.as_os_str() == "-" => io::stdout().lock().write_all(contents),
+            Some(ref path) => fs::write(path, contents),
+            None => {
+                let stem = input.file_stem().unwrap_or_default();
+                let path = Path::new(stem).with_extension(self.kind.extension());
+                fs::write(path, contents)
+            }
+        }
+    }
+}
+
+impl FromStr for Emit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((kind, path)) => Ok(Self {
+                kind: kind.parse()?,
+                path: Some(PathBuf::from(path)),
+            }),
+            None => Ok(Self::new(s.parse()?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!("mir".parse(), Ok(Emit::new(EmitKind::Mir)));
+        assert_eq!("mir-cfg".parse(), Ok(Emit::new(EmitKind::MirCfg)));
+        assert_eq!(
+            "llvm-ir=-".parse(),
+            Ok(Emit {
+                kind: EmitKind::LlvmIr,
+                path: Some(PathBuf::from("-")),
+            })
+        );
+        assert!("foo=out.txt".parse::<Emit>().is_err());
+    }
+}
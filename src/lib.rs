@@ -1,27 +1,336 @@
-use std::path::Path;
+mod emit;
+
+pub use emit::*;
+
+use std::{
+    env,
+    fmt::{self, Display},
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 use ritec_ast_lower::ProgramLowerer as AstLowerer;
+use ritec_codegen_c::CCodegen;
+#[cfg(feature = "llvm")]
 use ritec_codegen_llvm::LLVMCodegen;
-use ritec_core::SourceMap;
+use ritec_core::{SourceFile, SourceMap, TargetLayout};
+use ritec_error::{Diagnostic, Emitter, ErrorCode, ErrorWriter, JsonEmitter};
 use ritec_hir as hir;
-use ritec_mir_build::ProgramBuilder;
-use ritec_parser::ProgramParser;
+use ritec_mir as mir;
+use ritec_mir_build::{thir::ThirPrinter, ProgramBuilder};
+use ritec_mir_interp::Interpreter;
+use ritec_mir_opt::PassManager;
+use ritec_parser::{ProgramParser, TokenStream, TokenTree};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ErrorFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+impl FromStr for ErrorFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unknown error format `{}`", s)),
+        }
+    }
+}
+
+/// The code generator used to build and run programs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    /// Only available with the `llvm` feature.
+    #[cfg(feature = "llvm")]
+    Llvm,
+    /// Translates to C and compiles with the system C compiler.
+    C,
+}
+
+impl Backend {
+    /// LLVM if the `llvm` feature is enabled, C otherwise.
+    #[cfg(feature = "llvm")]
+    pub const DEFAULT: Self = Self::Llvm;
+    /// LLVM if the `llvm` feature is enabled, C otherwise.
+    #[cfg(not(feature = "llvm"))]
+    pub const DEFAULT: Self = Self::C;
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "llvm")]
+            Self::Llvm => "llvm",
+            Self::C => "c",
+        }
+    }
+}
+
+impl Default for Backend {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
 
-pub struct Compiler {}
+impl FromStr for Backend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            #[cfg(feature = "llvm")]
+            "llvm" => Ok(Self::Llvm),
+            #[cfg(not(feature = "llvm"))]
+            "llvm" => Err(String::from(
+                "the llvm backend is disabled, rebuild with `--features llvm`",
+            )),
+            "c" => Ok(Self::C),
+            _ => Err(format!("unknown backend `{}`, expected llvm or c", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Goal {
+    Check,
+    Build,
+    Run,
+}
+
+pub struct Compiler {
+    pub color: bool,
+    pub error_format: ErrorFormat,
+    /// Where [`Compiler::build`] writes the executable.
+    pub output: Option<PathBuf>,
+    /// Intermediate representations to write, see [`Emit`].
+    pub emit: Vec<Emit>,
+    pub backend: Backend,
+    #[cfg(feature = "llvm")]
+    pub codegen: LLVMCodegen,
+    pub c_codegen: CCodegen,
+    /// Optimization passes run on the MIR before it is emitted or compiled.
+    pub passes: PassManager,
+    /// Whether [`Compiler::run`] interprets the MIR instead of compiling it.
+    pub interpret: bool,
+    /// Whether runtime checks for overflow, division by zero and null
+    /// dereferences are compiled in.
+    pub checks: bool,
+}
 
 impl Compiler {
-    pub fn compile(path: impl AsRef<Path>) {
+    pub const fn new() -> Self {
+        Self {
+            color: true,
+            error_format: ErrorFormat::Human,
+            output: None,
+            emit: Vec::new(),
+            backend: Backend::DEFAULT,
+            #[cfg(feature = "llvm")]
+            codegen: LLVMCodegen::new(),
+            c_codegen: CCodegen::new(),
+            passes: PassManager::new(),
+            interpret: false,
+            checks: true,
+        }
+    }
+
+    /// Writes `diagnostics` to stderr and clears them, returns `true` if any of them were errors.
+    pub fn emit_diagnostics(
+        &self,
+        source_map: &SourceMap,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> bool {
+        let has_errors = diagnostics.iter().any(Diagnostic::is_error);
+        let mut stderr = io::stderr().lock();
+
+        match self.error_format {
+            ErrorFormat::Human => {
+                let writer = ErrorWriter::new(source_map).with_color(self.color);
+
+                for diagnostic in diagnostics.drain(..) {
+                    writer.write(&mut stderr, &diagnostic).unwrap();
+                }
+            }
+            ErrorFormat::Json => {
+                let mut emitter = JsonEmitter::new(source_map, &mut stderr);
+
+                for diagnostic in diagnostics.drain(..) {
+                    emitter.emit(diagnostic);
+                }
+            }
+        }
+
+        has_errors
+    }
+
+    /// Returns `true` if nothing after `kind` needs to be compiled for `goal`.
+    fn stops_after(&self, kind: EmitKind, goal: Goal) -> bool {
+        match goal {
+            Goal::Check => kind >= EmitKind::Mir,
+            Goal::Build => {
+                self.output.is_none()
+                    && !self.emit.is_empty()
+                    && self.emit.iter().all(|emit| emit.kind <= kind)
+            }
+            Goal::Run => false,
+        }
+    }
+
+    fn write_emits(
+        &self,
+        kind: EmitKind,
+        input: &Path,
+        emitter: &mut Vec<Diagnostic>,
+        contents: impl FnOnce() -> Result<Vec<u8>, Diagnostic>,
+    ) {
+        let mut emits = self.emit.iter().filter(|emit| emit.kind == kind).peekable();
+        if emits.peek().is_none() {
+            return;
+        }
+
+        let contents = match contents() {
+            Ok(contents) => contents,
+            Err(err) => {
+                emitter.push(err);
+                return;
+            }
+        };
+
+        for emit in emits {
+            if let Err(err) = emit.write(input, &contents) {
+                let err = Diagnostic::error(format!("failed to emit {}: {}", kind, err))
+                    .with_code(ErrorCode::E0012);
+                emitter.push(err);
+            }
+        }
+    }
+
+    fn print_tokens(source_map: &SourceMap) -> Vec<u8> {
+        fn print(out: &mut String, file: &SourceFile, tokens: &TokenStream) {
+            for token in tokens.iter() {
+                let TokenTree::Group(group) = token else {
+                    let (line, column) = file.position(token.span().lo);
+                    out.push_str(&format!("{}:{}\t{}\n", line, column, token));
+                    continue;
+                };
+
+                let delimiter = group.delimiter();
+                if let Some(open) = delimiter.open_char() {
+                    let (line, column) = file.position(group.open_span().lo);
+                    out.push_str(&format!("{}:{}\t{}\n", line, column, open));
+                }
+
+                print(out, file, group.stream());
+
+                if let Some(close) = delimiter.close_char() {
+                    let (line, column) = file.position(group.close_span().lo);
+                    out.push_str(&format!("{}:{}\t{}\n", line, column, close));
+                }
+            }
+        }
+
+        let mut out = String::new();
+        for (id, file) in source_map.iter() {
+            out.push_str(&format!("// {}\n", file.path.display()));
+
+            if let Ok(tokens) = TokenStream::lex(&file.text, Some(id)) {
+                print(&mut out, file, &tokens);
+            }
+        }
+
+        out.into_bytes()
+    }
+
+    fn print_thir(
+        hir_program: &hir::Program,
+        program_builder: &ProgramBuilder,
+    ) -> Result<Vec<u8>, Diagnostic> {
+        let mut out = String::new();
+
+        for (id, function) in hir_program.functions.iter() {
+            let (body, return_type) = program_builder.build_thir(function)?;
+            let printer = ThirPrinter::new(hir_program, function, &body, &return_type);
+
+            out.push_str(&format!("// fn[{}]\n{}\n\n", id.as_raw_index(), printer));
+        }
+
+        Ok(out.into_bytes())
+    }
+
+    /// Reads the textual MIR at `path`.
+    fn read_mir(&self, path: &Path, source_map: &mut SourceMap) -> Result<mir::Program, ()> {
+        let Ok(source) = fs::read_to_string(path) else {
+            let err = Diagnostic::error("failed to read file")
+                .with_code(ErrorCode::E0001)
+                .with_msg("failed to read main file".to_string());
+
+            self.emit_diagnostics(source_map, &mut vec![err]);
+            return Err(());
+        };
+
+        let file = source_map.insert(SourceFile::new(path, source));
+        let source = &source_map.get(file).unwrap().text;
+
+        mir::parse_program(source, file).map_err(|err| {
+            self.emit_diagnostics(source_map, &mut vec![err]);
+        })
+    }
+
+    /// Returns the layout of the target constants are evaluated for.
+    fn target_layout(&self, goal: Goal) -> Result<TargetLayout, Diagnostic> {
+        if goal == Goal::Run && self.interpret {
+            return Ok(ritec_mir_interp::TARGET);
+        }
+
+        #[cfg(feature = "llvm")]
+        if self.backend == Backend::Llvm {
+            return self.codegen.target_layout();
+        }
+
+        Ok(TargetLayout::HOST)
+    }
+
+    /// Compiles the Rite program at `path` to MIR, writing any requested
+    /// emits of the front end.
+    fn build_mir(
+        &self,
+        path: &Path,
+        goal: Goal,
+        source_map: &mut SourceMap,
+    ) -> Result<Option<mir::Program>, ()> {
         let mut emitter = Vec::new();
 
-        let mut source_map = SourceMap::new();
-        let mut program_parser = ProgramParser::new(&mut source_map, &mut emitter);
-        let program = program_parser.parse_program(path.as_ref());
+        let mut program_parser = ProgramParser::new(source_map, &mut emitter);
+        let program = program_parser.parse_program(path);
 
-        for diagnostic in emitter.iter() {
-            println!("{:?}", diagnostic);
+        if self.emit_diagnostics(source_map, &mut emitter) {
+            return Err(());
         }
 
-        let program = program.unwrap();
+        let program = program?;
+
+        self.write_emits(EmitKind::Tokens, path, &mut emitter, || {
+            Ok(Self::print_tokens(source_map))
+        });
+        self.write_emits(EmitKind::Ast, path, &mut emitter, || {
+            Ok(program.to_string().into_bytes())
+        });
+
+        if self.emit_diagnostics(source_map, &mut emitter) {
+            return Err(());
+        }
+
+        if self.stops_after(EmitKind::Ast, goal) {
+            return Ok(None);
+        }
 
         let mut hir_program = hir::Program::new();
         hir_program.add_intrinsics();
@@ -29,23 +338,220 @@ impl Compiler {
         let mut program_lowerer = AstLowerer::new(&mut hir_program, &mut emitter);
         let res = program_lowerer.lower(&program);
 
-        for diagnostic in emitter.iter() {
-            println!("{:?}", diagnostic);
+        if self.emit_diagnostics(source_map, &mut emitter) || res.is_err() {
+            return Err(());
+        }
+
+        let target = self.target_layout(goal).map_err(|err| {
+            self.emit_diagnostics(source_map, &mut vec![err]);
+        })?;
+        let evaluated = ritec_const_eval::evaluate_program(&mut hir_program, target, &mut emitter);
+
+        if self.emit_diagnostics(source_map, &mut emitter) || !evaluated {
+            return Err(());
         }
 
-        res.unwrap();
+        self.write_emits(EmitKind::Hir, path, &mut emitter, || {
+            Ok(hir_program.to_string().into_bytes())
+        });
 
-        let program_builder = ProgramBuilder::new(&hir_program);
-        let res = program_builder.build();
+        if self.emit_diagnostics(source_map, &mut emitter) {
+            return Err(());
+        }
 
-        for diagnostic in emitter.iter() {
-            println!("{:?}", diagnostic);
+        if self.stops_after(EmitKind::Hir, goal) {
+            return Ok(None);
         }
 
-        let mir = res.unwrap();
+        let mut program_builder = ProgramBuilder::new(&hir_program);
+        program_builder.checks = self.checks;
+        self.write_emits(EmitKind::Thir, path, &mut emitter, || {
+            Self::print_thir(&hir_program, &program_builder)
+        });
+
+        if goal == Goal::Check {
+            // report inference errors for every function, not just the first
+            for function in hir_program.functions.values() {
+                if let Err(err) = program_builder.build_thir(function) {
+                    emitter.push(err);
+                }
+            }
+        }
+
+        if self.emit_diagnostics(source_map, &mut emitter) {
+            return Err(());
+        }
+
+        if self.stops_after(EmitKind::Thir, goal) {
+            return Ok(None);
+        }
+
+        let mir = match program_builder.build(&mut emitter) {
+            Ok(mir) => mir,
+            Err(err) => {
+                emitter.push(err);
+                self.emit_diagnostics(source_map, &mut emitter);
+                return Err(());
+            }
+        };
+
+        // building the MIR may warn about unreachable code
+        self.emit_diagnostics(source_map, &mut emitter);
 
-        println!("MIR: {}", mir);
+        Ok(Some(mir))
+    }
+
+    /// Compiles the program at `path` to optimized MIR, writing any
+    /// requested emits, `path` is read as textual MIR if it ends in `.mir`.
+    ///
+    /// Returns `Err` if any errors were reported, and `Ok(None)` if nothing
+    /// more needs to be done for `goal`.
+    fn compile(&self, path: &Path, goal: Goal) -> Result<Option<(mir::Program, SourceMap)>, ()> {
+        let mut source_map = SourceMap::new();
+
+        let mut mir = if path.extension() == Some("mir".as_ref()) {
+            let mir = self.read_mir(path, &mut source_map)?;
+
+            if self.stops_after(EmitKind::Thir, goal) {
+                return Ok(None);
+            }
+
+            mir
+        } else {
+            match self.build_mir(path, goal, &mut source_map)? {
+                Some(mir) => mir,
+                None => return Ok(None),
+            }
+        };
+
+        // building the MIR finds missing returns, there is nothing to
+        // optimize when checking
+        if goal == Goal::Check {
+            return Ok(None);
+        }
+
+        if let Err(err) = self.passes.run(&mut mir) {
+            self.emit_diagnostics(&source_map, &mut vec![err]);
+            return Err(());
+        }
+
+        let mut emitter = Vec::new();
+
+        self.write_emits(EmitKind::Mir, path, &mut emitter, || {
+            Ok(mir.to_string().into_bytes())
+        });
+        self.write_emits(EmitKind::MirCfg, path, &mut emitter, || {
+            Ok(mir::CfgPrinter::new(&mir).to_string().into_bytes())
+        });
+        self.write_emits(EmitKind::C, path, &mut emitter, || {
+            Ok(self.c_codegen.emit(&mir, &source_map)?.into_bytes())
+        });
+
+        // the LLVM module is built once for all of its outputs
+        let kinds: Vec<_> = [EmitKind::LlvmIr, EmitKind::Asm, EmitKind::Obj]
+            .into_iter()
+            .filter(|&kind| self.emit.iter().any(|emit| emit.kind == kind))
+            .collect();
+
+        #[cfg(not(feature = "llvm"))]
+        for kind in kinds {
+            let err = Diagnostic::error(format!("cannot emit {} without the llvm backend", kind))
+                .with_code(ErrorCode::E0012)
+                .with_note("rebuild the compiler with `--features llvm`");
+            emitter.push(err);
+        }
+
+        #[cfg(feature = "llvm")]
+        if !kinds.is_empty() {
+            let output_types: Vec<_> = kinds.iter().filter_map(EmitKind::output_type).collect();
+
+            match self.codegen.emit(&mir, &source_map, &output_types) {
+                Ok(outputs) => {
+                    for (kind, contents) in kinds.into_iter().zip(outputs) {
+                        self.write_emits(kind, path, &mut emitter, || Ok(contents));
+                    }
+                }
+                Err(err) => emitter.push(err),
+            }
+        }
+
+        if self.emit_diagnostics(&source_map, &mut emitter) {
+            return Err(());
+        }
+
+        if self.stops_after(EmitKind::Obj, goal) {
+            return Ok(None);
+        }
+
+        Ok(Some((mir, source_map)))
+    }
+
+    /// Reports the error of a backend step.
+    fn backend<T>(&self, source_map: &SourceMap, result: Result<T, Diagnostic>) -> Result<T, ()> {
+        result.map_err(|err| {
+            self.emit_diagnostics(source_map, &mut vec![err]);
+        })
+    }
+
+    /// Parses and type checks the program at `path`, without generating any
+    /// code, returns `false` if any errors were reported.
+    pub fn check(&self, path: impl AsRef<Path>) -> bool {
+        self.compile(path.as_ref(), Goal::Check).is_ok()
+    }
+
+    /// Compiles the program at `path` to an executable, written to
+    /// [`Compiler::output`] or the file stem of `path` in the current directory.
+    ///
+    /// Returns `false` if any errors were reported.
+    pub fn build(&self, path: impl AsRef<Path>) -> bool {
+        let path = path.as_ref();
+
+        let (mir, source_map) = match self.compile(path, Goal::Build) {
+            Ok(Some(compiled)) => compiled,
+            Ok(None) => return true,
+            Err(()) => return false,
+        };
+
+        let output = match self.output {
+            Some(ref output) => output.clone(),
+            None => {
+                let stem = path.file_stem().unwrap_or_default();
+                Path::new(stem).with_extension(env::consts::EXE_EXTENSION)
+            }
+        };
+
+        let result = match self.backend {
+            #[cfg(feature = "llvm")]
+            Backend::Llvm => self.codegen.build_executable(&mir, &source_map, &output),
+            Backend::C => self.c_codegen.build_executable(&mir, &source_map, &output),
+        };
+
+        self.backend(&source_map, result).is_ok()
+    }
+
+    /// Compiles the program at `path` and runs it in-process with `args`,
+    /// returns the value returned by `main`, or `None` if any errors were
+    /// reported.
+    pub fn run(&self, path: impl AsRef<Path>, args: &[String]) -> Option<i32> {
+        let path = path.as_ref();
+        let (mir, source_map) = self.compile(path, Goal::Run).ok()??;
+
+        let mut argv = vec![path.display().to_string()];
+        argv.extend_from_slice(args);
+
+        let result = match self.backend {
+            _ if self.interpret => Interpreter::new(&mir).run_main(&argv),
+            #[cfg(feature = "llvm")]
+            Backend::Llvm => self.codegen.run(&mir, &source_map, &argv),
+            Backend::C => self.c_codegen.run(&mir, &source_map, &argv),
+        };
+
+        self.backend(&source_map, result).ok()
+    }
+}
 
-        LLVMCodegen::compile(&mir);
+impl Default for Compiler {
+    fn default() -> Self {
+        Self::new()
     }
 }
@@ -1,25 +1,225 @@
-use std::path::PathBuf;
+use std::{
+    io::{self, BufRead},
+    path::PathBuf,
+    process,
+};
 
-use clap::Parser;
-use rite::Compiler;
+use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand};
+use rite::{Backend, Compiler, Emit, ErrorFormat};
+use ritec_core::OptLevel;
+use ritec_error::ErrorCode;
+use ritec_mir_opt::PassToggle;
 use tracing::Level;
 
 #[derive(Parser)]
 pub struct Args {
-    #[clap(default_value = "main.ri")]
-    pub path: PathBuf,
-    #[clap(short, long, default_value = "info")]
+    #[clap(subcommand)]
+    pub command: Option<Command>,
+    #[clap(short, long, default_value = "info", global = true)]
     pub log_level: Level,
+    /// Disable coloured diagnostic output.
+    #[clap(long, global = true)]
+    pub no_color: bool,
+    /// How diagnostics are written, `human` or `json`.
+    #[clap(long, default_value = "human", global = true)]
+    pub error_format: ErrorFormat,
+    /// Print the long description of an error code, e.g. `E0001`.
+    #[clap(long, value_name = "CODE")]
+    pub explain: Option<String>,
+}
+
+/// Options controlling code generation.
+#[derive(clap::Args)]
+pub struct CodegenArgs {
+    /// Optimization level, one of 0, 1, 2, 3 or s.
+    #[clap(short = 'O', default_value = "0", value_name = "LEVEL")]
+    pub opt_level: OptLevel,
+    /// Target triple to compile for, e.g. `aarch64-unknown-linux-gnu`,
+    /// defaults to the host.
+    #[clap(long, value_name = "TRIPLE")]
+    pub target: Option<String>,
+    /// Emit DWARF debug information.
+    #[clap(short = 'g')]
+    pub debug_info: bool,
+    /// Code generator to use, `llvm` or `c`, which compiles through the
+    /// system C compiler. Defaults to `c` if rite was built without the
+    /// `llvm` feature.
+    #[clap(long, default_value_t)]
+    pub backend: Backend,
+    /// Comma separated list of MIR passes to enable, or to disable when
+    /// prefixed with `no-`, where `all` and `none` toggle every pass. The
+    /// passes are inline, const-prop, copy-prop, dead-code and simplify-cfg,
+    /// all of which are enabled by default.
+    #[clap(long, value_name = "PASS", value_delimiter = ',')]
+    pub mir_opt: Vec<PassToggle>,
+    /// Whether to check for overflow, division by zero and null dereferences
+    /// at runtime, on by default without optimizations.
+    #[clap(long, value_name = "BOOL")]
+    pub checks: Option<bool>,
+}
+
+impl CodegenArgs {
+    pub fn apply(self, compiler: &mut Compiler) {
+        if self.backend == Backend::C && self.target.is_some() {
+            let message =
+                "the C backend can only compile for the host, `--target` is not supported";
+            Args::command()
+                .error(ErrorKind::ArgumentConflict, message)
+                .exit();
+        }
+
+        #[cfg(not(feature = "llvm"))]
+        if self.target.is_some() {
+            let message = "`--target` needs the llvm backend, which rite was built without";
+            Args::command()
+                .error(ErrorKind::ArgumentConflict, message)
+                .exit();
+        }
+
+        compiler.backend = self.backend;
+        compiler.checks = self.checks.unwrap_or(self.opt_level == OptLevel::O0);
+
+        for toggle in self.mir_opt {
+            compiler.passes.toggle(toggle);
+        }
+
+        let cflags = &mut compiler.c_codegen.cflags;
+        cflags.push(format!("-O{}", self.opt_level));
+        if self.debug_info {
+            cflags.push(String::from("-g"));
+        }
+
+        #[cfg(feature = "llvm")]
+        {
+            let codegen = &mut compiler.codegen;
+            codegen.opt_level = self.opt_level;
+            codegen.target = self.target;
+            codegen.debug_info = self.debug_info;
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Parse and type check a program without generating any code.
+    Check {
+        #[clap(default_value = "main.ri")]
+        path: PathBuf,
+    },
+    /// Compile a program to an executable, files ending in `.mir` are read
+    /// as the MIR written by `--emit=mir`.
+    Build {
+        #[clap(default_value = "main.ri")]
+        path: PathBuf,
+        /// Where to write the executable, defaults to the name of the input file.
+        #[clap(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+        /// Comma separated list of `KIND[=PATH]` to write, where `KIND` is one of
+        /// tokens, ast, hir, thir, mir, mir-cfg, c, llvm-ir, asm or obj, and a `PATH`
+        /// of `-` writes to stdout. No executable is written unless `-o` is also
+        /// given, mir-cfg writes the control flow graphs as Graphviz `dot`. mir and
+        /// mir-cfg show the optimized MIR, use `--mir-opt=none` to see it as built.
+        #[clap(long, value_name = "KIND[=PATH]", value_delimiter = ',')]
+        emit: Vec<Emit>,
+        #[clap(flatten)]
+        codegen: CodegenArgs,
+    },
+    /// Compile and run a program, exiting with the value returned by `main`.
+    Run {
+        #[clap(default_value = "main.ri")]
+        path: PathBuf,
+        /// Arguments passed to the program.
+        #[clap(last = true)]
+        args: Vec<String>,
+        /// Interpret the MIR instead of compiling it, reporting undefined
+        /// behavior like use after free as errors.
+        #[clap(long)]
+        interp: bool,
+        #[clap(flatten)]
+        codegen: CodegenArgs,
+    },
+    /// Print the readable form of mangled symbols, if none are given every
+    /// word of stdin that is a mangled symbol is replaced.
+    Demangle { symbols: Vec<String> },
+}
+
+/// Replaces every word of `line` that is a mangled symbol.
+fn demangle(line: &str) -> String {
+    let words: Vec<_> = line
+        .split(' ')
+        .map(|word| ritec_mir::demangle(word).unwrap_or_else(|| String::from(word)))
+        .collect();
+
+    words.join(" ")
 }
 
 fn main() {
     let args = Args::parse();
 
+    if let Some(ref code) = args.explain {
+        match ErrorCode::find(code) {
+            Some(code) => print!("{}", code.explanation()),
+            None => {
+                eprintln!("error: `{}` is not a valid error code", code);
+                process::exit(1);
+            }
+        }
+
+        return;
+    }
+
+    let Some(command) = args.command else {
+        Args::command().print_help().unwrap();
+        process::exit(2);
+    };
+
     let layer = tracing_subscriber::fmt()
         .with_max_level(args.log_level)
         .finish();
 
     tracing::subscriber::set_global_default(layer).unwrap();
 
-    Compiler::compile(args.path);
+    let mut compiler = Compiler::new();
+    compiler.color = !args.no_color;
+    compiler.error_format = args.error_format;
+
+    let code = match command {
+        Command::Check { path } => !compiler.check(path) as i32,
+        Command::Build {
+            path,
+            output,
+            emit,
+            codegen,
+        } => {
+            compiler.output = output;
+            compiler.emit = emit;
+            codegen.apply(&mut compiler);
+            !compiler.build(path) as i32
+        }
+        Command::Run {
+            path,
+            args,
+            interp,
+            codegen,
+        } => {
+            codegen.apply(&mut compiler);
+            compiler.interpret = interp;
+            compiler.run(path, &args).unwrap_or(1)
+        }
+        Command::Demangle { symbols } => {
+            if symbols.is_empty() {
+                for line in io::stdin().lock().lines() {
+                    println!("{}", demangle(&line.unwrap()));
+                }
+            }
+
+            for symbol in symbols {
+                println!("{}", demangle(&symbol));
+            }
+
+            0
+        }
+    };
+
+    process::exit(code);
 }
@@ -0,0 +1,29 @@
+mod common;
+
+use std::process::Command;
+
+use common::{compiler, program, temp_path};
+
+#[test]
+fn jit_argv_is_null_terminated() {
+    let path = program("", "argv.ri");
+    let args = [String::from("a"), String::from("bb"), String::from("ccc")];
+
+    // the program path is passed as `argv[0]`
+    assert_eq!(compiler().run(&path, &args), Some(4));
+    assert_eq!(compiler().run(&path, &[]), Some(1));
+}
+
+#[test]
+fn executable_argv_is_null_terminated() {
+    let output = temp_path("");
+
+    let mut compiler = compiler();
+    compiler.output = Some(output.clone());
+    assert!(compiler.build(program("", "argv.ri")));
+
+    let status = Command::new(&output).args(["a", "bb"]).status().unwrap();
+    let _ = std::fs::remove_file(&output);
+
+    assert_eq!(status.code(), Some(3));
+}
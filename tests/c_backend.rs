@@ -0,0 +1,47 @@
+mod common;
+
+use std::path::Path;
+
+use common::{compiler, emit, program};
+use rite::{Backend, EmitKind};
+
+fn run(backend: Backend, path: &Path, args: &[String]) -> Option<i32> {
+    let mut compiler = compiler();
+    compiler.backend = backend;
+    compiler.run(path, args)
+}
+
+#[test]
+fn matches_llvm_backend() {
+    let args = vec![String::from("a"), String::from("b")];
+
+    for (dir, name) in [
+        ("", "argv.ri"),
+        ("", "backends.ri"),
+        ("", "sizeof.ri"),
+        ("instances", "generic.ri"),
+        ("mangle", "main.ri"),
+    ] {
+        let path = program(dir, name);
+        let llvm = run(Backend::Llvm, &path, &args);
+        let c = run(Backend::C, &path, &args);
+
+        assert!(llvm.is_some(), "{} failed to run", name);
+        // exit codes are truncated to a byte
+        assert_eq!(llvm.map(|code| code & 0xff), c, "{}", name);
+    }
+}
+
+#[test]
+fn emits_c() {
+    let source = emit(compiler(), program("", "backends.ri"), EmitKind::C);
+    let source = String::from_utf8(source).unwrap();
+
+    assert!(source.contains("/* Line */\nstruct class_"));
+    assert!(source.contains("int main(int argc, char **argv) {"));
+    // exported, even though every call to it is inlined
+    assert!(source.contains("int32_t _RT3neg(int32_t a0) {"));
+    // overflow checks are plain C99, without compiler builtins
+    assert!(source.contains("INT32_MAX - "));
+    assert!(!source.contains("__builtin"));
+}
@@ -0,0 +1,93 @@
+//! Runtime checks for overflow, division by zero and null dereferences,
+//! which abort the program with a message pointing at the failed operation.
+
+mod common;
+
+use std::{path::PathBuf, process::Command};
+
+use common::temp_path;
+use rite::{Backend, Compiler};
+
+fn program(name: &str) -> PathBuf {
+    common::program("checks", name)
+}
+
+fn compiler(backend: Backend, interpret: bool) -> Compiler {
+    let mut compiler = common::compiler();
+    compiler.backend = backend;
+    compiler.interpret = interpret;
+    compiler
+}
+
+const FAILING: [(&str, &str); 4] = [
+    ("overflow.ri", "3:9: attempt to add with overflow"),
+    ("negate.ri", "3:9: attempt to negate with overflow"),
+    ("divide_by_zero.ri", "3:9: attempt to divide by zero"),
+    ("null.ri", "2:9: attempt to dereference a null pointer"),
+];
+
+#[test]
+fn interpreter_reports_failed_checks() {
+    for (name, _) in FAILING {
+        let compiler = compiler(Backend::Llvm, true);
+        assert_eq!(compiler.run(program(name), &[]), None, "{}", name);
+    }
+}
+
+#[test]
+fn c_backend_aborts() {
+    for (name, _) in FAILING {
+        let compiler = compiler(Backend::C, false);
+        assert_eq!(compiler.run(program(name), &[]), None, "{}", name);
+    }
+}
+
+// a failed check aborts, so the program can't be run in the test process
+#[test]
+fn llvm_executables_abort() {
+    for (name, message) in FAILING {
+        let output = temp_path("");
+
+        let mut compiler = compiler(Backend::Llvm, false);
+        compiler.output = Some(output.clone());
+        assert!(compiler.build(program(name)), "{}", name);
+
+        let result = Command::new(&output).output().unwrap();
+        let _ = std::fs::remove_file(&output);
+
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        assert!(!result.status.success(), "{}", name);
+        assert!(stderr.starts_with("panicked at "), "{}: {}", name, stderr);
+        assert!(stderr.trim_end().ends_with(message), "{}: {}", name, stderr);
+    }
+}
+
+#[test]
+fn checks_can_be_disabled() {
+    let path = program("overflow.ri");
+
+    for (backend, interpret) in [(Backend::Llvm, true), (Backend::Llvm, false)] {
+        let mut compiler = compiler(backend, interpret);
+        compiler.checks = false;
+        assert_eq!(compiler.run(&path, &[]), Some(i32::MIN));
+    }
+
+    let mut compiler = compiler(Backend::C, false);
+    compiler.checks = false;
+    // exit codes are truncated to a byte
+    assert_eq!(compiler.run(&path, &[]), Some(0));
+}
+
+#[test]
+fn wrapping_arithmetic_is_not_checked() {
+    let path = program("wrapping.ri");
+
+    for (backend, interpret) in [
+        (Backend::Llvm, true),
+        (Backend::Llvm, false),
+        (Backend::C, false),
+    ] {
+        let compiler = compiler(backend, interpret);
+        assert_eq!(compiler.run(&path, &[]), Some(7));
+    }
+}
@@ -0,0 +1,64 @@
+//! Helpers shared by the integration tests, each test binary uses only some
+//! of them.
+#![allow(dead_code)]
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use rite::{Compiler, Emit, EmitKind};
+
+/// Returns the path of the test program `name` in `tests/programs/dir`, use
+/// an empty `dir` for the programs directly in `tests/programs`.
+pub fn program(dir: &str, name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/programs")
+        .join(dir)
+        .join(name)
+}
+
+/// Returns a compiler that writes diagnostics without colours.
+pub fn compiler() -> Compiler {
+    let mut compiler = Compiler::new();
+    compiler.color = false;
+    compiler
+}
+
+/// Returns a path in the temporary directory that no other test uses, with
+/// the extension `ext`, or none if it's empty.
+pub fn temp_path(ext: &str) -> PathBuf {
+    ritec_core::temp_path("rite-test", ext)
+}
+
+/// Builds `path` with `compiler`, returning the contents of the emit of
+/// `kind`.
+pub fn emit(mut compiler: Compiler, path: impl AsRef<Path>, kind: EmitKind) -> Vec<u8> {
+    let path = path.as_ref();
+    let output = temp_path(kind.extension());
+
+    compiler.emit = vec![Emit {
+        kind,
+        path: Some(output.clone()),
+    }];
+    assert!(compiler.build(path), "{} failed to build", path.display());
+
+    let contents = fs::read(&output).unwrap();
+    let _ = fs::remove_file(&output);
+    contents
+}
+
+/// Runs `rite <command> path --error-format=json`, returns whether it
+/// succeeded and the diagnostics it wrote, one per line.
+pub fn json_diagnostics(command: &str, path: impl AsRef<Path>) -> (bool, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_rite"))
+        .arg(command)
+        .arg(path.as_ref())
+        .arg("--error-format=json")
+        .output()
+        .unwrap();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    (output.status.success(), stderr)
+}
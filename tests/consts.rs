@@ -0,0 +1,108 @@
+//! Constants and array lengths are evaluated at compile time, calling
+//! `const fn`s and measuring types for the target.
+
+mod common;
+
+use std::path::PathBuf;
+
+use common::{compiler, json_diagnostics};
+use rite::{Backend, EmitKind};
+use ritec_core::OptLevel;
+
+fn program(name: &str) -> PathBuf {
+    common::program("consts", name)
+}
+
+#[test]
+fn evaluates_constants() {
+    let path = program("consts.ri");
+
+    for (backend, interpret) in [
+        (Backend::Llvm, false),
+        (Backend::Llvm, true),
+        (Backend::C, false),
+    ] {
+        let mut compiler = compiler();
+        compiler.backend = backend;
+        compiler.interpret = interpret;
+        assert_eq!(compiler.run(&path, &[]), Some(42), "{:?}", backend);
+    }
+}
+
+#[test]
+fn nan_comparisons_match_runtime() {
+    // `main` returns `10 * NAN_NE + ne(NAN, NAN)`, every comparison with NaN
+    // is false
+    let path = program("nan.ri");
+
+    for (backend, interpret) in [
+        (Backend::Llvm, false),
+        (Backend::Llvm, true),
+        (Backend::C, false),
+    ] {
+        let mut compiler = compiler();
+        compiler.backend = backend;
+        compiler.interpret = interpret;
+        assert_eq!(compiler.run(&path, &[]), Some(0), "{:?}", backend);
+    }
+}
+
+#[test]
+fn invalid_constants_are_errors() {
+    for name in ["non_const_call.ri", "overflow.ri", "cycle.ri"] {
+        assert!(!compiler().check(program(name)), "{}", name);
+    }
+}
+
+#[test]
+fn too_large_type_names_its_lengths() {
+    let (success, stderr) = json_diagnostics("check", program("too_large.ri"));
+    assert!(!success);
+
+    assert!(stderr.contains(r#""code":"E0019""#));
+    assert!(stderr.contains("the type `[[u64; 1099511627776]; 1099511627776]` is too large"));
+}
+
+/// Returns the LLVM IR of `name` built for `target`.
+fn emit_ir(name: &str, target: &str) -> String {
+    let mut compiler = compiler();
+    compiler.codegen.opt_level = OptLevel::O1;
+    compiler.codegen.target = Some(String::from(target));
+
+    let ir = common::emit(compiler, program(name), EmitKind::LlvmIr);
+    String::from_utf8(ir).unwrap()
+}
+
+#[test]
+fn sizeof_follows_target() {
+    // `sizeof<Pointers>()` is folded to a constant by the backend
+    assert!(emit_ir("pointer_size.ri", "riscv32-unknown-elf").contains("ret i32 12"));
+    assert!(emit_ir("pointer_size.ri", "aarch64-unknown-linux-gnu").contains("ret i32 24"));
+}
+
+#[test]
+fn sizeof_agrees_with_backend() {
+    let path = program("alignment.ri");
+
+    for (backend, interpret) in [
+        (Backend::Llvm, false),
+        (Backend::Llvm, true),
+        (Backend::C, false),
+    ] {
+        let mut compiler = compiler();
+        compiler.backend = backend;
+        compiler.interpret = interpret;
+        assert_eq!(compiler.run(&path, &[]), Some(16), "{:?}", backend);
+    }
+
+    // `i64` is aligned to 4 bytes on i686 linux, but to 8 on armv7
+    for (target, size) in [
+        ("x86_64-unknown-linux-gnu", 16),
+        ("i686-unknown-linux-gnu", 12),
+        ("armv7-unknown-linux-gnueabihf", 16),
+        ("riscv32-unknown-elf", 16),
+    ] {
+        let ir = emit_ir("alignment.ri", target);
+        assert!(ir.contains(&format!("ret i32 {}", size)), "{}", target);
+    }
+}
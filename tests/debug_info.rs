@@ -0,0 +1,40 @@
+mod common;
+
+use common::{compiler, program};
+use rite::EmitKind;
+
+fn emit(kind: EmitKind) -> Vec<u8> {
+    let mut compiler = compiler();
+    compiler.codegen.debug_info = true;
+    common::emit(compiler, program("", "argv.ri"), kind)
+}
+
+#[test]
+fn emits_debug_metadata() {
+    let ir = String::from_utf8(emit(EmitKind::LlvmIr)).unwrap();
+
+    assert!(ir.contains("!DICompileUnit("));
+    assert!(ir.contains("!DIFile(filename: \"argv.ri\""));
+    assert!(ir.contains("!DISubprogram(name: \"main\""));
+    assert!(ir.contains("!DILocalVariable(name: \"argc\", arg: 1"));
+    assert!(ir.contains("!DILocalVariable(name: \"count\""));
+    assert!(ir.contains("!DILocation(line: 5"));
+}
+
+#[test]
+fn emits_dwarf_sections() {
+    let object = emit(EmitKind::Obj);
+    let contains = |name: &[u8]| object.windows(name.len()).any(|w| w == name);
+
+    assert!(contains(b".debug_info"));
+    assert!(contains(b".debug_line"));
+}
+
+#[test]
+fn runs_with_debug_info() {
+    let mut compiler = compiler();
+    compiler.codegen.debug_info = true;
+
+    let path = program("", "argv.ri");
+    assert_eq!(compiler.run(path, &[String::from("a")]), Some(2));
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use std::{fs, path::PathBuf};
+
+use common::{compiler, program, temp_path};
+use rite::{Emit, EmitKind};
+
+/// Builds `path` with an emit of each of `kinds`, returns their contents.
+fn emit(path: PathBuf, kinds: &[EmitKind]) -> Vec<Vec<u8>> {
+    let outputs: Vec<_> = kinds
+        .iter()
+        .map(|kind| temp_path(kind.extension()))
+        .collect();
+
+    let mut compiler = compiler();
+    compiler.emit = kinds
+        .iter()
+        .zip(&outputs)
+        .map(|(&kind, output)| Emit {
+            kind,
+            path: Some(output.clone()),
+        })
+        .collect();
+    assert!(compiler.build(path));
+
+    outputs
+        .iter()
+        .map(|output| {
+            let contents = fs::read(output).unwrap();
+            let _ = fs::remove_file(output);
+            contents
+        })
+        .collect()
+}
+
+#[test]
+fn ast_is_printed_as_source() {
+    let ast = emit(program("mangle", "main.ri"), &[EmitKind::Ast]).remove(0);
+    let ast = String::from_utf8(ast).unwrap();
+
+    assert!(ast.contains("mod a {\n\tclass Stack {\n\t\ttop: i32 = 0,\n"));
+    assert!(ast.contains("\t\tfn push(&self, value: i32) {\n\t\t\tself.top = value * 2;\n"));
+    assert!(ast.contains("\treturn value() + a::value() + b::value() + x.top + y.top;\n"));
+}
+
+#[test]
+#[cfg(feature = "llvm")]
+fn emits_every_llvm_output_at_once() {
+    let kinds = [EmitKind::LlvmIr, EmitKind::Asm, EmitKind::Obj];
+    let outputs = emit(program("", "argv.ri"), &kinds);
+
+    let ir = String::from_utf8_lossy(&outputs[0]);
+    assert!(ir.contains("define i32 @main("));
+
+    let asm = String::from_utf8_lossy(&outputs[1]);
+    assert!(asm.contains("main:"));
+
+    assert!(!outputs[2].is_empty());
+}
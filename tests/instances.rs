@@ -0,0 +1,59 @@
+mod common;
+
+use std::path::PathBuf;
+
+use common::{compiler, json_diagnostics};
+use rite::EmitKind;
+use ritec_mir_opt::Pass;
+
+fn program(name: &str) -> PathBuf {
+    common::program("instances", name)
+}
+
+fn emit_llvm_ir() -> String {
+    let mut compiler = compiler();
+    // inlining would leave nothing to instantiate
+    compiler.passes.disable(Pass::Inline);
+
+    let ir = common::emit(compiler, program("generic.ri"), EmitKind::LlvmIr);
+    String::from_utf8(ir).unwrap()
+}
+
+#[test]
+fn runs_generic_instances() {
+    assert_eq!(compiler().run(program("generic.ri"), &[]), Some(42));
+}
+
+#[test]
+fn instances_are_deterministic() {
+    let first = emit_llvm_ir();
+    let second = emit_llvm_ir();
+    assert_eq!(first, second);
+
+    // declared in the order they're discovered from `main`
+    let twice = first.find("@_RT5twiceIlE(").unwrap();
+    let id = first.find("@_RT2idIlE(").unwrap();
+    assert!(twice < id);
+}
+
+#[test]
+fn polymorphic_recursion_is_an_error() {
+    let (success, stderr) = json_diagnostics("run", program("recursion.ri"));
+    assert!(!success);
+
+    let error = stderr
+        .lines()
+        .find(|line| line.contains(r#""level":"error""#))
+        .expect("no error");
+
+    // `wrap<*T>(&value, depth - 1)` on line 6
+    assert!(error.contains(r#""code":"E0013""#));
+    assert!(error.contains("reached the recursion limit while instantiating `wrap<"));
+    assert!(error.contains(r#""line_start":6"#));
+    assert!(error.contains("`wrap` instantiates itself with ever growing generic arguments"));
+}
+
+#[test]
+fn exporting_a_generic_function_is_an_error() {
+    assert!(!compiler().build(program("export_generic.ri")));
+}
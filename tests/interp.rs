@@ -0,0 +1,39 @@
+mod common;
+
+use std::path::Path;
+
+use common::{compiler, program};
+
+fn run(path: &Path, interpret: bool) -> Option<i32> {
+    let args = vec![String::from("a"), String::from("b")];
+
+    let mut compiler = compiler();
+    compiler.interpret = interpret;
+    compiler.run(path, &args)
+}
+
+#[test]
+fn matches_llvm_backend() {
+    for (dir, name) in [
+        ("", "argv.ri"),
+        ("", "backends.ri"),
+        ("", "sizeof.ri"),
+        ("instances", "generic.ri"),
+        ("mangle", "main.ri"),
+    ] {
+        let path = program(dir, name);
+        let llvm = run(&path, false);
+
+        assert!(llvm.is_some(), "{} failed to run", name);
+        // exit codes are truncated to a byte
+        let interp = run(&path, true).map(|code| code & 0xff);
+        assert_eq!(interp, llvm.map(|code| code & 0xff), "{}", name);
+    }
+}
+
+#[test]
+fn detects_undefined_behavior() {
+    for name in ["null.ri", "use_after_free.ri", "out_of_bounds.ri"] {
+        assert_eq!(run(&program("interp", name), true), None, "{}", name);
+    }
+}
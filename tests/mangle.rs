@@ -0,0 +1,54 @@
+mod common;
+
+use common::{compiler, emit, program};
+use rite::EmitKind;
+use ritec_mir_opt::Pass;
+
+#[test]
+fn same_names_in_different_modules() {
+    assert_eq!(
+        compiler().run(program("mangle", "main.ri"), &[]),
+        Some(1151)
+    );
+}
+
+#[test]
+fn symbols_are_mangled() {
+    let mut compiler = compiler();
+    // inlining would leave only `main`
+    compiler.passes.disable(Pass::Inline);
+
+    let ir = emit(compiler, program("mangle", "main.ri"), EmitKind::LlvmIr);
+    let ir = String::from_utf8(ir).unwrap();
+
+    let symbols: Vec<_> = ir
+        .lines()
+        .filter_map(|line| line.strip_prefix("define "))
+        .map(|line| {
+            let start = line.find('@').unwrap() + 1;
+            let end = line.find('(').unwrap();
+            &line[start..end]
+        })
+        .collect();
+
+    assert!(symbols.contains(&"main"));
+
+    let mut demangled: Vec<_> = symbols
+        .iter()
+        .filter_map(|symbol| ritec_mir::demangle(symbol))
+        .collect();
+    demangled.sort();
+
+    assert_eq!(
+        demangled,
+        [
+            "a::Stack::Stack__top__init",
+            "a::Stack::push",
+            "a::value",
+            "b::Stack::Stack__top__init",
+            "b::Stack::push",
+            "b::value",
+            "value",
+        ]
+    );
+}
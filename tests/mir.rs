@@ -0,0 +1,75 @@
+//! Reads programs from textual MIR, the format written by `--emit=mir`.
+
+mod common;
+
+use std::{fs, path::Path};
+
+use common::{program, temp_path};
+use rite::{Compiler, EmitKind};
+use ritec_mir_opt::PassToggle;
+
+fn compiler(passes: &str) -> Compiler {
+    let mut compiler = common::compiler();
+    compiler
+        .passes
+        .toggle(passes.parse::<PassToggle>().unwrap());
+    compiler
+}
+
+/// Compiles `path` and returns its MIR.
+fn emit_mir(path: &Path, passes: &str) -> String {
+    let mir = common::emit(compiler(passes), path, EmitKind::Mir);
+    String::from_utf8(mir).unwrap()
+}
+
+fn run(path: &Path) -> Option<i32> {
+    let mut compiler = compiler("all");
+    compiler.interpret = true;
+    compiler.run(path, &[String::from("a")])
+}
+
+#[test]
+fn round_trip() {
+    let main = Path::new(env!("CARGO_MANIFEST_DIR")).join("main.ri");
+
+    for path in [
+        main,
+        program("", "argv.ri"),
+        program("", "backends.ri"),
+        program("", "sizeof.ri"),
+        program("instances", "generic.ri"),
+        program("mangle", "main.ri"),
+        program("mir_opt", "inline.ri"),
+        program("mir_opt", "simplify_cfg.ri"),
+    ] {
+        let mir = emit_mir(&path, "all");
+
+        let mir_path = temp_path("mir");
+        fs::write(&mir_path, &mir).unwrap();
+
+        let reparsed = emit_mir(&mir_path, "none");
+        let result = run(&mir_path);
+        let _ = fs::remove_file(&mir_path);
+
+        let name = path.display();
+        assert!(mir == reparsed, "MIR of {} changed when reparsed", name);
+        assert_eq!(result, run(&path), "{}", name);
+    }
+}
+
+#[test]
+fn hand_written() {
+    let path = program("mir", "sum.mir");
+
+    // `main` returns sum(10) - argc
+    let mut compiler = compiler("none");
+    compiler.interpret = true;
+    assert_eq!(compiler.run(&path, &[]), Some(54));
+    assert_eq!(run(&path), Some(53));
+}
+
+#[test]
+fn invalid_mir_is_an_error() {
+    let path = program("mir", "undeclared_local.mir");
+    assert!(!compiler("none").build(&path));
+}
@@ -0,0 +1,123 @@
+//! Compares the optimized MIR of the programs in `tests/programs/mir_opt`
+//! with the `.mir` snapshot next to them, run with `RITE_BLESS=1` to update
+//! the snapshots.
+
+mod common;
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use rite::{Compiler, EmitKind};
+use ritec_mir_opt::PassToggle;
+
+fn program(name: &str) -> PathBuf {
+    common::program("mir_opt", name)
+}
+
+fn compiler(passes: &str) -> Compiler {
+    let mut compiler = common::compiler();
+    // keep the snapshots free of runtime checks
+    compiler.checks = false;
+
+    for toggle in passes.split(',') {
+        compiler
+            .passes
+            .toggle(toggle.parse::<PassToggle>().unwrap());
+    }
+
+    compiler
+}
+
+/// Returns the MIR of the functions declared in `path`, leaving out the
+/// intrinsics.
+fn emit_mir(path: &Path, passes: &str) -> String {
+    let mir = common::emit(compiler(passes), path, EmitKind::Mir);
+    let mir = String::from_utf8(mir).unwrap();
+
+    let source = fs::read_to_string(path).unwrap();
+    let is_declared = |name: &str| {
+        source.contains(&format!("fn {}(", name)) || source.contains(&format!("fn {}<", name))
+    };
+
+    // every item ends with a `}` on its own line
+    let mut out = String::new();
+    for item in mir.split_inclusive("\n}\n") {
+        let item = item.trim_start();
+
+        // skip attributes like `#[inline]`
+        let signature = item
+            .lines()
+            .find(|line| !line.starts_with("#["))
+            .unwrap_or("");
+        if let Some(signature) = signature.strip_prefix("fn ") {
+            // methods are named by their path, like `<Counter>::get`
+            let signature = signature.trim_start_matches('<');
+            let path = signature.split(['(', '<']).next().unwrap();
+            if is_declared(path.rsplit("::").next().unwrap()) {
+                out.push_str(item);
+                out.push('\n');
+            }
+        }
+    }
+
+    out
+}
+
+fn check(name: &str, passes: &str) {
+    let path = program(&format!("{}.ri", name));
+    let mir = emit_mir(&path, passes);
+
+    let snapshot = path.with_extension("mir");
+    if env::var_os("RITE_BLESS").is_some() {
+        fs::write(&snapshot, &mir).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&snapshot).unwrap_or_default();
+    assert!(
+        mir == expected,
+        "MIR of {} doesn't match {}, run with RITE_BLESS=1 to update it\n\n{}",
+        name,
+        snapshot.display(),
+        mir
+    );
+
+    // the optimized program must behave like the unoptimized one
+    let args = [String::from("a")];
+    let optimized = compiler(passes).run(&path, &args);
+    assert!(optimized.is_some(), "{} failed to run", name);
+    assert_eq!(optimized, compiler("none").run(&path, &args), "{}", name);
+}
+
+#[test]
+fn const_prop() {
+    check("const_prop", "all,no-inline");
+}
+
+#[test]
+fn copy_prop() {
+    check("copy_prop", "all,no-inline");
+}
+
+#[test]
+fn dead_code() {
+    check("dead_code", "all,no-inline");
+}
+
+#[test]
+fn simplify_cfg() {
+    check("simplify_cfg", "none,simplify-cfg");
+}
+
+#[test]
+fn inline() {
+    check("inline", "all");
+}
+
+#[test]
+fn malformed_inline_is_an_error() {
+    let path = program("malformed_inline.ri");
+    assert!(!compiler("all").build(&path));
+}
@@ -0,0 +1,72 @@
+//! Functions must return on every path, except void functions which return
+//! implicitly at the end.
+
+mod common;
+
+use std::path::PathBuf;
+
+use common::{compiler, json_diagnostics};
+
+fn program(name: &str) -> PathBuf {
+    common::program("returns", name)
+}
+
+#[test]
+fn missing_return_is_an_error() {
+    let (success, stderr) = json_diagnostics("check", program("missing_return.ri"));
+    assert!(!success);
+
+    let error = stderr
+        .lines()
+        .find(|line| line.contains(r#""level":"error""#))
+        .expect("no error");
+
+    // the closing brace of `sign` on line 9
+    assert!(error.contains(r#""code":"E0017""#));
+    assert!(error.contains(r#""message":"not all paths return a value""#));
+    assert!(error.contains(r#""line_start":9"#));
+    assert!(error.contains(r#""column_start":1,"#));
+    assert!(error.contains(r#""column_end":2,"#));
+}
+
+#[test]
+fn every_missing_return_is_reported() {
+    let (success, stderr) = json_diagnostics("check", program("missing_returns.ri"));
+    assert!(!success);
+
+    // the closing braces of `sign` and `abs`
+    let lines: Vec<_> = stderr
+        .lines()
+        .filter(|line| line.contains(r#""code":"E0017""#))
+        .collect();
+    assert_eq!(lines.len(), 2, "{}", stderr);
+    assert!(lines[0].contains(r#""line_start":5"#));
+    assert!(lines[1].contains(r#""line_start":11"#));
+}
+
+#[test]
+fn implicit_return() {
+    let path = program("implicit_return.ri");
+
+    let mut compiler = compiler();
+    compiler.interpret = true;
+    assert_eq!(compiler.run(&path, &[]), Some(5));
+    assert_eq!(self::compiler().run(&path, &[]), Some(5));
+}
+
+#[test]
+fn unreachable_statement_is_a_warning() {
+    let (success, stderr) = json_diagnostics("check", program("implicit_return.ri"));
+    assert!(success);
+
+    let warning = stderr
+        .lines()
+        .find(|line| line.contains(r#""message":"unreachable statement""#))
+        .expect("no unreachable statement warning");
+
+    // `x = 5;` on line 15
+    assert!(warning.contains(r#""level":"warning""#));
+    assert!(warning.contains(r#""line_start":15"#));
+    assert!(warning.contains(r#""column_start":2,"#));
+    assert!(warning.contains(r#""column_end":8,"#));
+}
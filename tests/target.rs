@@ -0,0 +1,57 @@
+mod common;
+
+use std::path::PathBuf;
+
+use common::{compiler, program};
+use rite::{Emit, EmitKind};
+use ritec_core::OptLevel;
+
+fn emit(target: &str, kind: EmitKind) -> Vec<u8> {
+    let mut compiler = compiler();
+    compiler.codegen.opt_level = OptLevel::O1;
+    compiler.codegen.target = Some(String::from(target));
+    common::emit(compiler, program("", "sizeof.ri"), kind)
+}
+
+#[test]
+fn emits_elf_objects_for_target() {
+    // `e_machine` of the ELF header
+    let targets = [
+        ("x86_64-unknown-linux-gnu", 0x3e),
+        ("aarch64-unknown-linux-gnu", 0xb7),
+        ("riscv64-unknown-linux-gnu", 0xf3),
+    ];
+
+    for (target, machine) in targets {
+        let object = emit(target, EmitKind::Obj);
+
+        assert_eq!(&object[..4], b"\x7fELF", "{}", target);
+        assert_eq!(
+            u16::from_le_bytes([object[18], object[19]]),
+            machine,
+            "{}",
+            target
+        );
+    }
+}
+
+#[test]
+fn pointer_width_follows_target() {
+    let ir = String::from_utf8(emit("riscv32-unknown-elf", EmitKind::LlvmIr)).unwrap();
+    assert!(ir.contains("ret i32 4"));
+
+    let ir = String::from_utf8(emit("aarch64-unknown-linux-gnu", EmitKind::LlvmIr)).unwrap();
+    assert!(ir.contains("ret i32 8"));
+}
+
+#[test]
+fn unknown_target_is_an_error() {
+    let mut compiler = compiler();
+    compiler.codegen.target = Some(String::from("unknown-triple"));
+    compiler.emit = vec![Emit {
+        kind: EmitKind::Obj,
+        path: Some(PathBuf::from("-")),
+    }];
+
+    assert!(!compiler.build(program("", "sizeof.ri")));
+}